@@ -1,26 +1,50 @@
 pub mod data;
 pub mod filter;
+pub mod keybindings;
 pub mod layout_manager;
+pub mod monitor;
 pub mod states;
 
-use std::time::Instant;
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
 
+use crossterm::event::KeyEvent;
 use data::*;
 use filter::*;
+use keybindings::*;
 use layout_manager::*;
+use monitor::Monitor;
 use rustc_hash::FxHashMap as HashMap;
 pub use states::*;
 
 use crate::{
     canvas::{
-        components::time_series::LegendPosition, dialogs::process_kill_dialog::ProcessKillDialog,
+        components::time_series::LegendPosition,
+        dialogs::{
+            event_list::EventListDialog, layout_editor::LayoutEditor,
+            process_affinity_dialog::ProcessAffinityDialog,
+            process_columns_dialog::ProcessColumnsDialog, process_kill_dialog::ProcessKillDialog,
+            process_priority_dialog::ProcessPriorityDialog,
+        },
     },
+    collection::custom::CustomSource,
     components::time_series::TimeseriesState,
     constants,
-    options::config::flags::TableGap,
+    options::{
+        config::{
+            Config,
+            flags::TableGap,
+            style::{Styles, ThemeCycle},
+        },
+        config_modified_time,
+    },
     utils::data_units::DataUnit,
     widgets::{
-        DiskWidgetColumn, ProcWidgetColumn, ProcWidgetMode, TempWidgetColumn, TreeCollapsed,
+        DiskWidgetColumn, ProcWidgetColumn, ProcWidgetData, ProcWidgetMode, ProcWidgetState,
+        ProcessTargets, TempWidgetColumn, TreeCollapsed,
     },
 };
 
@@ -42,6 +66,7 @@ pub struct AppConfigFields {
     pub temperature_type: TemperatureType,
     pub use_dot: bool,
     pub cpu_left_legend: bool,
+    pub cpu_legend_stats: bool,
     pub show_average_cpu: bool, // TODO: Unify this in CPU options
     pub show_cpu_decimal: bool,
     pub use_current_cpu_total: bool,
@@ -63,23 +88,33 @@ pub struct AppConfigFields {
     #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
     pub is_advanced_kill: bool,
     pub is_read_only: bool,
+    pub kill_escalation_ms: u64,
     #[cfg(target_os = "linux")]
     pub hide_k_threads: bool,
     #[cfg(feature = "zfs")]
     pub free_arc: bool,
     pub memory_legend_position: Option<LegendPosition>,
+    pub memory_legend_stats: bool,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_legend_position: Option<LegendPosition>,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
     pub network_show_packets: bool,
+    pub network_legend_stats: bool,
     pub retention_ms: u64,
+    pub history_file: Option<PathBuf>,
     pub dedicated_average_row: bool,
     pub default_tree_collapse: bool,
-    pub default_temp_sort_column: Option<TempWidgetColumn>,
-    pub default_disk_sort_column: Option<DiskWidgetColumn>,
+    pub default_temp_sort_columns: Vec<TempWidgetColumn>,
+    pub default_disk_sort_columns: Vec<DiskWidgetColumn>,
     pub temperature_legend_position: Option<LegendPosition>,
+    pub temperature_legend_stats: bool,
+    pub custom_sources: Vec<CustomSource>,
+    pub process_watch_list: Vec<WatchedProcess>,
+    pub keybindings: Keybindings,
+    pub config_path: Option<PathBuf>,
+    pub(crate) themes: ThemeCycle,
 }
 
 /// For filtering out information
@@ -93,11 +128,16 @@ pub struct DataFilters {
 }
 
 pub struct App {
-    awaiting_second_char: bool,
-    second_char: Option<char>,
+    /// The first key of a chord, if one was just pressed.
+    pending_key: Option<KeyPress>,
     pub data_store: DataStore,
     last_key_press: Instant,
     pub(crate) process_kill_dialog: ProcessKillDialog,
+    pub(crate) process_priority_dialog: ProcessPriorityDialog,
+    pub(crate) process_affinity_dialog: ProcessAffinityDialog,
+    pub(crate) process_columns_dialog: ProcessColumnsDialog,
+    pub(crate) layout_editor: LayoutEditor,
+    pub(crate) event_list: EventListDialog,
     pub help_dialog_state: AppHelpDialogState,
     pub is_expanded: bool,
     pub is_force_redraw: bool,
@@ -109,6 +149,18 @@ pub struct App {
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+    pub layout_tabs: LayoutTabs,
+    /// The error from the last failed config reload or theme switch, if any.
+    pub status_error: Option<String>,
+    /// Styles from switching themes, to be picked up by the painter.
+    pub(crate) new_styles: Option<Styles>,
+    /// The command or processes being monitored, if bottom was started with
+    /// one.
+    pub monitor: Option<Monitor>,
+    /// The config this was created from, to tell what changed on a reload.
+    pub(crate) config: Config,
+    /// When the config file was modified by bottom saving the process columns.
+    config_saved_at: Option<SystemTime>,
 }
 
 impl App {
@@ -117,17 +169,24 @@ impl App {
         app_config_fields: AppConfigFields, states: AppWidgetStates,
         widget_map: HashMap<u64, BottomWidget>, current_widget: BottomWidget,
         used_widgets: UsedWidgets, filters: DataFilters, is_expanded: bool,
+        layout_tabs: LayoutTabs,
     ) -> Self {
         let mut data_store = DataStore::new(used_widgets);
         data_store.set_filters(filters.clone());
 
+        let help_text = app_config_fields.keybindings.help_text();
+
         Self {
-            awaiting_second_char: false,
-            second_char: None,
+            pending_key: None,
             data_store,
             last_key_press: Instant::now(),
             process_kill_dialog: ProcessKillDialog::default(),
-            help_dialog_state: AppHelpDialogState::default(),
+            process_priority_dialog: ProcessPriorityDialog::default(),
+            process_affinity_dialog: ProcessAffinityDialog::default(),
+            process_columns_dialog: ProcessColumnsDialog::default(),
+            layout_editor: LayoutEditor::default(),
+            event_list: EventListDialog::default(),
+            help_dialog_state: AppHelpDialogState::new(help_text),
             is_expanded,
             is_force_redraw: false,
             is_determining_widget_boundary: false,
@@ -138,6 +197,202 @@ impl App {
             current_widget,
             used_widgets,
             filters,
+            layout_tabs,
+            status_error: None,
+            new_styles: None,
+            monitor: None,
+            config: Config::default(),
+            config_saved_at: None,
+        }
+    }
+
+    /// Apply the settings of an [`App`] created from a reloaded config.
+    /// Collected data, open dialogs, the current layout and widget, and the
+    /// state of every widget whose config is unchanged are kept. Returns
+    /// whether the current styles should be kept, as the styles in the config
+    /// are unchanged.
+    pub fn reload(&mut self, new_app: App) -> bool {
+        let App {
+            mut states,
+            mut app_config_fields,
+            widget_map,
+            used_widgets,
+            filters,
+            mut layout_tabs,
+            help_dialog_state,
+            config,
+            ..
+        } = new_app;
+
+        let same_styles = config.styles == self.config.styles;
+        let same_base = same_styles && config.flags == self.config.flags;
+        let old_widget_map = &self.widget_map;
+        let is_same_widget = |id: u64| match (old_widget_map.get(&id), widget_map.get(&id)) {
+            (Some(old), Some(new)) => {
+                old.widget_type == new.widget_type && old.custom_source == new.custom_source
+            }
+            _ => false,
+        };
+
+        let old = &mut self.states;
+        let old_config = &self.config;
+        keep_widget_states(
+            &mut old.cpu_state.widget_states,
+            &mut states.cpu_state.widget_states,
+            same_base && config.cpu == old_config.cpu,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.cpu_heatmap_state.widget_states,
+            &mut states.cpu_heatmap_state.widget_states,
+            same_base && config.cpu == old_config.cpu,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.mem_state.widget_states,
+            &mut states.mem_state.widget_states,
+            same_base && config.memory_graph == old_config.memory_graph,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.net_state.widget_states,
+            &mut states.net_state.widget_states,
+            same_base && config.network_graph == old_config.network_graph,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.proc_state.widget_states,
+            &mut states.proc_state.widget_states,
+            same_base && config.processes == old_config.processes,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.temp_state.widget_states,
+            &mut states.temp_state.widget_states,
+            same_base && config.temperature == old_config.temperature,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.temp_graph_state.widget_states,
+            &mut states.temp_graph_state.widget_states,
+            same_base && config.temperature_graph == old_config.temperature_graph,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.disk_state.widget_states,
+            &mut states.disk_state.widget_states,
+            same_base && config.disk == old_config.disk,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.battery_state.widget_states,
+            &mut states.battery_state.widget_states,
+            same_base,
+            is_same_widget,
+        );
+        keep_widget_states(
+            &mut old.custom_state.widget_states,
+            &mut states.custom_state.widget_states,
+            same_base && config.custom == old_config.custom,
+            is_same_widget,
+        );
+        if same_base {
+            states.basic_table_widget_state = old.basic_table_widget_state.take();
+        }
+
+        // Stay on the current layout and widget if they still exist.
+        let current_layout = self.layout_tabs.current;
+        let current_widget = self.current_widget.widget_id;
+        if current_layout < layout_tabs.names.len() {
+            layout_tabs.current = current_layout;
+            if is_same_widget(current_widget) {
+                layout_tabs.selected_widgets[current_layout] = current_widget;
+            }
+        }
+        if let Some(widget) = widget_map.get(&layout_tabs.selected_widgets[layout_tabs.current]) {
+            self.current_widget = widget.clone();
+        }
+
+        if config.keybindings != self.config.keybindings {
+            self.help_dialog_state.index_shortcuts = help_dialog_state.index_shortcuts;
+            self.help_dialog_state.help_text = help_dialog_state.help_text;
+        }
+
+        // Keep the theme switched to, unless the styles changed.
+        if same_styles {
+            mem::swap(
+                &mut app_config_fields.themes,
+                &mut self.app_config_fields.themes,
+            );
+        }
+
+        self.states = states;
+        self.app_config_fields = app_config_fields;
+        self.widget_map = widget_map;
+        self.used_widgets = used_widgets;
+        self.filters = filters;
+        self.layout_tabs = layout_tabs;
+        self.config = config;
+
+        self.data_store.set_used_widgets(self.used_widgets);
+        self.data_store.set_filters(self.filters.clone());
+        if let Some(monitor) = self.monitor.take() {
+            self.set_monitor(monitor);
+        }
+        self.status_error = None;
+        self.reset_multi_tap_keys();
+        self.force_data_update();
+        self.is_force_redraw = true;
+
+        same_styles
+    }
+
+    /// Monitor a command or processes. Processes are always collected while
+    /// monitoring, and process widgets only show the monitored trees.
+    pub fn set_monitor(&mut self, monitor: Monitor) {
+        self.used_widgets.use_proc = true;
+        self.data_store.set_used_widgets(self.used_widgets);
+
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_root_pids(monitor.roots().to_vec());
+        }
+
+        self.monitor = Some(monitor);
+    }
+
+    /// Set the error shown in the status line. Multi-line causes (like
+    /// TOML parse errors) are cut down to their first and last lines so that
+    /// they fit into the status line.
+    pub fn set_status_error(&mut self, err: &anyhow::Error) {
+        let message = err
+            .chain()
+            .flat_map(|cause| {
+                let cause = cause.to_string();
+                let mut lines = cause.lines().map(str::trim).filter(|line| !line.is_empty());
+                let first = lines.next().map(str::to_string);
+                let last = lines.next_back().map(str::to_string);
+                first.into_iter().chain(last)
+            })
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        self.status_error = Some(message);
+        self.is_force_redraw = true;
+    }
+
+    /// Mark every widget's data as needing an update.
+    pub fn force_data_update(&mut self) {
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.force_data_update();
+        }
+        for temp in self.states.temp_state.widget_states.values_mut() {
+            temp.force_data_update();
+        }
+        for cpu in self.states.cpu_state.widget_states.values_mut() {
+            cpu.force_data_update();
+        }
+        for disk in self.states.disk_state.widget_states.values_mut() {
+            disk.force_data_update();
         }
     }
 
@@ -193,22 +448,11 @@ impl App {
 
         self.data_store.reset();
 
-        // Reset zoom.
+        // Reset zoom, and go back to live data as the history is gone.
         // TODO: Make this suck less... should just make it so that calling reset fixes this all (including above too).
-        for widget_state in self.states.cpu_state.widget_states.values_mut() {
-            widget_state.graph.state_mut().reset_zoom();
-        }
-
-        for widget_state in self.states.mem_state.widget_states.values_mut() {
-            widget_state.graph.state_mut().reset_zoom();
-        }
-
-        for widget_state in self.states.net_state.widget_states.values_mut() {
-            widget_state.graph.state_mut().reset_zoom();
-        }
-
-        for widget_state in self.states.temp_graph_state.widget_states.values_mut() {
-            widget_state.graph.state_mut().reset_zoom();
+        for ts_state in self.ts_states_mut() {
+            ts_state.reset_zoom();
+            ts_state.jump_to_live();
         }
     }
 
@@ -298,12 +542,17 @@ impl App {
     }
 
     fn reset_multi_tap_keys(&mut self) {
-        self.awaiting_second_char = false;
-        self.second_char = None;
+        self.pending_key = None;
     }
 
-    fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.process_kill_dialog.is_open()
+    pub fn is_in_dialog(&self) -> bool {
+        self.help_dialog_state.is_showing_help
+            || self.process_kill_dialog.is_open()
+            || self.process_priority_dialog.is_open()
+            || self.process_affinity_dialog.is_open()
+            || self.process_columns_dialog.is_open()
+            || self.layout_editor.is_open()
+            || self.event_list.is_open()
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -366,7 +615,7 @@ impl App {
             // If the sort is now open, move left. Otherwise, if the proc sort was selected,
             // force move right.
             if pws.is_sort_open {
-                pws.sort_table.set_position(pws.table.sort_index());
+                pws.sort_table.set_position(pws.sort_table_position());
                 self.move_widget_selection(&WidgetDirection::Left);
             } else if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
                 self.move_widget_selection(&WidgetDirection::Right);
@@ -375,6 +624,74 @@ impl App {
         }
     }
 
+    /// Adds the column selected in the process sort widget, or the one under
+    /// the header cursor of a temperature or disk table, as another column to
+    /// sort by.
+    pub fn add_sort_key(&mut self) {
+        match self.current_widget.widget_type {
+            BottomWidgetType::ProcSort => {
+                if let Some(pws) = self
+                    .states
+                    .proc_state
+                    .get_mut_widget_state(self.current_widget.widget_id - 2)
+                {
+                    pws.add_sort_table_value();
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp) = self
+                    .states
+                    .temp_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                    && let Some(index) = temp.table.sort_cursor()
+                {
+                    temp.table.add_sort_index(index);
+                    temp.force_data_update();
+                    self.is_force_redraw = true;
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                    && let Some(index) = disk.table.sort_cursor()
+                {
+                    disk.table.add_sort_index(index);
+                    disk.force_data_update();
+                    self.is_force_redraw = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the header cursor of a temperature or disk table, which picks the
+    /// column to add with [`Self::add_sort_key`].
+    fn move_sort_cursor(&mut self, delta: isize) {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Temp => {
+                if let Some(temp) = self
+                    .states
+                    .temp_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    temp.table.move_sort_cursor(delta);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk.table.move_sort_cursor(delta);
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn invert_sort(&mut self) {
         match &self.current_widget.widget_type {
             BottomWidgetType::Proc | BottomWidgetType::ProcSort => {
@@ -486,7 +803,13 @@ impl App {
     pub fn on_enter(&mut self) {
         if self.process_kill_dialog.is_open() {
             // Not the best way of doing things for now but works as glue.
-            self.process_kill_dialog.on_enter();
+            if let Some(description) = self
+                .process_kill_dialog
+                .on_enter(&self.data_store.get_data().process_data)
+            {
+                self.data_store
+                    .record_event(EventKind::ProcessKilled, description);
+            }
         } else if self.help_dialog_state.is_help_searching() {
             self.help_dialog_state.close_search();
             self.is_force_redraw = true;
@@ -552,7 +875,7 @@ impl App {
                 }
             }
             BottomWidgetType::Proc => {
-                self.kill_current_process();
+                self.kill_current_process(false);
             }
             _ => {}
         }
@@ -654,6 +977,13 @@ impl App {
                         battery_widget_state.currently_selected_battery_index -= 1;
                     }
                 }
+                BottomWidgetType::CpuHeatmap => self.change_cpu_heatmap_position(-1),
+                BottomWidgetType::Temp | BottomWidgetType::Disk => self.move_sort_cursor(-1),
+                BottomWidgetType::Cpu
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net
+                | BottomWidgetType::TempGraph
+                | BottomWidgetType::Custom => self.move_inspect_cursor(1),
                 _ => {}
             }
         } else if self.process_kill_dialog.is_open() {
@@ -713,6 +1043,13 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::CpuHeatmap => self.change_cpu_heatmap_position(1),
+                BottomWidgetType::Temp | BottomWidgetType::Disk => self.move_sort_cursor(1),
+                BottomWidgetType::Cpu
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net
+                | BottomWidgetType::TempGraph
+                | BottomWidgetType::Custom => self.move_inspect_cursor(-1),
                 _ => {}
             }
         } else if self.process_kill_dialog.is_open() {
@@ -913,20 +1250,9 @@ impl App {
             return;
         }
 
-        const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
-
-        // Forbid any char key presses when showing a dialog box...
+        // Outside of dialogs, characters are only typed into the process search; everything
+        // else goes through the keybindings.
         if !self.ignore_normal_keybinds() {
-            let current_key_press_inst = Instant::now();
-            if current_key_press_inst
-                .duration_since(self.last_key_press)
-                .as_millis()
-                > MAX_KEY_TIMEOUT_IN_MILLISECONDS.into()
-            {
-                self.reset_multi_tap_keys();
-            }
-            self.last_key_press = current_key_press_inst;
-
             if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
                 let is_in_search_widget = self.is_in_search_widget();
                 if let Some(proc_widget_state) = self
@@ -943,12 +1269,9 @@ impl App {
                             .insert_char(caught_char);
 
                         proc_widget_state.update_query();
-
-                        return;
                     }
                 }
             }
-            self.handle_char(caught_char);
         } else if self.help_dialog_state.is_showing_help {
             if self.help_dialog_state.is_searching() {
                 self.help_dialog_state
@@ -968,7 +1291,6 @@ impl App {
                             }
                         }
                     }
-                    'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                     '/' => {
                         self.help_dialog_state.open_search();
                         self.is_force_redraw = true;
@@ -981,297 +1303,501 @@ impl App {
         }
     }
 
-    /// Kill the currently selected process if we are in the process widget.
+    /// Kill the currently selected process, or the marked processes, if we are
+    /// in the process widget. If `with_descendants` is set, every descendant of
+    /// those processes is killed as well.
     ///
     /// TODO: This ideally gets abstracted out into a separate widget.
-    pub(crate) fn kill_current_process(&mut self) {
+    pub(crate) fn kill_current_process(&mut self, with_descendants: bool) {
+        if let Some((_, targets)) = self.current_process_to_change() {
+            let targets = if with_descendants {
+                let is_using_command = self
+                    .states
+                    .proc_state
+                    .widget_states
+                    .get(&self.current_widget.widget_id)
+                    .is_some_and(|pws| pws.is_using_command());
+                targets.with_descendants(&self.data_store.get_data().process_data, is_using_command)
+            } else {
+                targets
+            };
+
+            let use_simple_selection = {
+                cfg_select! {
+                    any(target_os = "linux", target_os = "macos", target_os = "freebsd") => {
+                        !self.app_config_fields.is_advanced_kill
+                    }
+                    _ => {
+                        true
+                    }
+                }
+            };
+
+            self.process_kill_dialog.start_process_kill(
+                targets,
+                use_simple_selection,
+                Duration::from_millis(self.app_config_fields.kill_escalation_ms),
+            );
+
+            // TODO: I don't think most of this is needed.
+            self.is_determining_widget_boundary = true;
+        }
+    }
+
+    /// The processes an action applies to in the current process widget, along
+    /// with the entry to use for their current state, if they can be changed.
+    fn current_process_to_change(&self) -> Option<(ProcWidgetData, ProcessTargets)> {
         if self.app_config_fields.is_read_only {
-            return;
+            return None;
         }
 
-        if let Some(pws) = self
-            .states
+        self.states
             .proc_state
             .widget_states
-            .get(&self.current_widget.widget_id)
-        {
-            if let Some(current) = pws.table.current_item() {
-                let id = current.id.to_string();
-                if let Some(pids) = pws
-                    .id_pid_map
-                    .get(&id)
-                    .cloned()
-                    .or_else(|| Some(vec![current.pid]))
-                {
-                    let current_process = (id, pids);
+            .get(&self.current_widget.widget_id)?
+            .action_targets(&self.data_store.get_data().process_data)
+    }
 
-                    let use_simple_selection = {
-                        cfg_select! {
-                            any(target_os = "linux", target_os = "macos", target_os = "freebsd") => {
-                                !self.app_config_fields.is_advanced_kill
-                            }
-                            _ => {
-                                true
-                            }
-                        }
-                    };
+    /// Open the priority dialog for the currently selected process, or the
+    /// marked processes, if we are in the process widget.
+    pub(crate) fn open_priority_dialog(&mut self) {
+        if let Some((process, targets)) = self.current_process_to_change() {
+            self.process_priority_dialog.open(&process, targets);
+            self.is_force_redraw = true;
+        }
+    }
 
-                    self.process_kill_dialog.start_process_kill(
-                        current_process.0,
-                        current_process.1,
-                        use_simple_selection,
-                    );
+    /// Whether any process widget shows the affinity column, which is only
+    /// collected if so.
+    pub fn shows_process_affinity(&self) -> bool {
+        self.states
+            .proc_state
+            .widget_states
+            .values()
+            .any(|state| state.is_column_shown(ProcWidgetColumn::Affinity))
+    }
 
-                    // TODO: I don't think most of this is needed.
-                    self.is_determining_widget_boundary = true;
-                }
-            }
+    /// Open the affinity dialog for the currently selected process, or the
+    /// marked processes, if we are in the process widget.
+    pub(crate) fn open_affinity_dialog(&mut self) {
+        if let Some((process, targets)) = self.current_process_to_change() {
+            self.process_affinity_dialog.open(&process, targets);
+            self.is_force_redraw = true;
         }
     }
 
-    // FIXME: Refactor this system...
-    fn handle_char(&mut self, caught_char: char) {
-        match caught_char {
-            '/' => {
-                self.on_slash();
+    /// Handle a key press while the affinity dialog is open.
+    pub fn on_affinity_dialog_key(&mut self, event: KeyEvent) {
+        self.process_affinity_dialog.on_key_event(event);
+        self.is_force_redraw = true;
+    }
+
+    /// Open the column chooser for the current process widget.
+    fn open_columns_dialog(&mut self) {
+        if let Some(proc_widget_state) = self.current_proc_widget_state() {
+            let columns = proc_widget_state.shown_columns();
+            self.process_columns_dialog.open(&columns);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Handle a key press while the column chooser is open, applying any chosen
+    /// columns to the current process widget.
+    pub fn on_columns_dialog_key(&mut self, event: KeyEvent) {
+        if let Some(columns) = self
+            .process_columns_dialog
+            .on_key_event(event, self.app_config_fields.config_path.as_deref())
+        {
+            if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                proc_widget_state.set_columns(&columns);
             }
-            'd' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    let mut is_first_d = true;
-                    if let Some(second_char) = self.second_char {
-                        if self.awaiting_second_char && second_char == 'd' {
-                            is_first_d = false;
-                            self.awaiting_second_char = false;
-                            self.second_char = None;
+        }
+        if let Some(saved_at) = self.process_columns_dialog.take_saved_at() {
+            self.config_saved_at = Some(saved_at);
+        }
+        self.is_force_redraw = true;
+    }
 
-                            self.reset_multi_tap_keys();
+    /// Whether the config file at `path` was last modified by bottom saving
+    /// the process columns. The saved columns are already shown, so there is
+    /// no need to reload the config, which would reset the process widgets.
+    pub fn is_config_saved_by_self(&self, path: &Path) -> bool {
+        self.config_saved_at
+            .is_some_and(|saved_at| config_modified_time(path) == Some(saved_at))
+    }
 
-                            self.kill_current_process();
-                        }
-                    }
+    /// Handle a key press while the priority dialog is open.
+    pub fn on_priority_dialog_key(&mut self, event: KeyEvent) {
+        self.process_priority_dialog.on_key_event(event);
+        self.is_force_redraw = true;
+    }
 
-                    if is_first_d {
-                        self.awaiting_second_char = true;
-                        self.second_char = Some('d');
-                    }
-                } else if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(0);
-                }
+    /// Look up the action bound to a key event, taking into account any pending chord.
+    ///
+    /// Actions that don't apply while a dialog is open are treated as unbound, so the
+    /// dialog can handle the key itself.
+    pub fn lookup_key(&mut self, event: KeyEvent) -> KeyLookup {
+        const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+
+        let current_key_press_inst = Instant::now();
+        if current_key_press_inst
+            .duration_since(self.last_key_press)
+            .as_millis()
+            > MAX_KEY_TIMEOUT_IN_MILLISECONDS.into()
+        {
+            self.reset_multi_tap_keys();
+        }
+        self.last_key_press = current_key_press_inst;
+
+        let key = KeyPress::from(event);
+        let lookup = self.app_config_fields.keybindings.lookup(
+            self.pending_key.take(),
+            key,
+            self.key_scope(),
+        );
+
+        match lookup {
+            KeyLookup::Pending => self.pending_key = Some(key),
+            KeyLookup::Action(action) if !self.is_action_allowed(action) => {
+                return KeyLookup::Unbound;
             }
-            'g' => {
-                let mut is_first_g = true;
-                if let Some(second_char) = self.second_char {
-                    if self.awaiting_second_char && second_char == 'g' {
-                        is_first_g = false;
-                        self.awaiting_second_char = false;
-                        self.second_char = None;
-                        self.skip_to_first();
-                    }
-                }
+            _ => {}
+        }
 
-                if is_first_g {
-                    self.awaiting_second_char = true;
-                    self.second_char = Some('g');
-                }
+        lookup
+    }
+
+    /// The scope of the keybindings that apply to the current widget.
+    fn key_scope(&self) -> KeyScope {
+        if self.is_in_dialog() {
+            return KeyScope::Global;
+        }
+
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc | BottomWidgetType::ProcSort => KeyScope::Process,
+            BottomWidgetType::Disk => KeyScope::Disk,
+            BottomWidgetType::Temp => KeyScope::Temperature,
+            _ if self.current_widget.widget_type.is_widget_graph() => KeyScope::Graph,
+            _ => KeyScope::Global,
+        }
+    }
+
+    /// Whether an action can be performed given any open dialogs.
+    fn is_action_allowed(&self, action: Action) -> bool {
+        if self.help_dialog_state.is_showing_help {
+            matches!(
+                action,
+                Action::Quit
+                    | Action::Search
+                    | Action::Up
+                    | Action::Down
+                    | Action::SkipToFirst
+                    | Action::SkipToLast
+                    | Action::PageUp
+                    | Action::PageDown
+                    | Action::HalfPageUp
+                    | Action::HalfPageDown
+            )
+        } else if self.process_kill_dialog.is_open() {
+            matches!(
+                action,
+                Action::Quit
+                    | Action::Up
+                    | Action::Down
+                    | Action::Left
+                    | Action::Right
+                    | Action::PageUp
+                    | Action::PageDown
+            )
+        } else {
+            true
+        }
+    }
+
+    /// Perform a bound action.
+    ///
+    /// [`Action::Quit`] and [`Action::Reset`] need to be handled by the caller.
+    pub fn perform_action(&mut self, action: Action) {
+        match action {
+            Action::Quit | Action::Reset => {}
+            Action::Freeze => self.data_store.toggle_frozen(),
+            Action::Help => {
+                self.help_dialog_state.is_showing_help = true;
+                self.is_force_redraw = true;
+            }
+            Action::Expand => self.toggle_expand_widget(),
+            Action::Search => self.on_slash(),
+            Action::SelectWidgetLeft => self.move_widget_selection(&WidgetDirection::Left),
+            Action::SelectWidgetRight => self.move_widget_selection(&WidgetDirection::Right),
+            Action::SelectWidgetUp => self.move_widget_selection(&WidgetDirection::Up),
+            Action::SelectWidgetDown => self.move_widget_selection(&WidgetDirection::Down),
+            Action::Left => self.on_left_key(),
+            Action::Right => self.on_right_key(),
+            Action::Up => self.on_up_key(),
+            Action::Down => self.on_down_key(),
+            Action::SkipToFirst => self.skip_to_first(),
+            Action::SkipToLast => self.skip_to_last(),
+            Action::PageUp => self.on_page_up(),
+            Action::PageDown => self.on_page_down(),
+            Action::HalfPageUp => self.scroll_half_page_up(),
+            Action::HalfPageDown => self.scroll_half_page_down(),
+            Action::ZoomIn => self.on_plus(),
+            Action::ZoomOut => self.on_minus(),
+            Action::ResetZoom => self.reset_zoom(),
+            Action::ToggleInspect => self.toggle_inspect(),
+            Action::PanBack => {
+                let history = self.history_span();
+                if let Some(ts_state) = self.current_ts_state() {
+                    ts_state.pan_back(history);
+                }
+            }
+            Action::PanForward => {
+                if let Some(ts_state) = self.current_ts_state() {
+                    ts_state.pan_forward();
+                }
+            }
+            Action::JumpToLive => {
+                if let Some(ts_state) = self.current_ts_state() {
+                    ts_state.jump_to_live();
+                }
+            }
+            Action::TogglePercentages => self.toggle_percentages(),
+            Action::NextLayout => {
+                let len = self.layout_tabs.names.len();
+                self.switch_layout((self.layout_tabs.current + 1) % len);
+            }
+            Action::PreviousLayout => {
+                let len = self.layout_tabs.names.len();
+                self.switch_layout((self.layout_tabs.current + len - 1) % len);
+            }
+            Action::Layout1 => self.switch_layout(0),
+            Action::Layout2 => self.switch_layout(1),
+            Action::Layout3 => self.switch_layout(2),
+            Action::Layout4 => self.switch_layout(3),
+            Action::Layout5 => self.switch_layout(4),
+            Action::Layout6 => self.switch_layout(5),
+            Action::Layout7 => self.switch_layout(6),
+            Action::Layout8 => self.switch_layout(7),
+            Action::Layout9 => self.switch_layout(8),
+            Action::EditLayout => self.open_layout_editor(),
+            Action::CycleTheme => self.cycle_theme(),
+            Action::ShowEvents => {
+                self.event_list.open();
+                self.is_force_redraw = true;
             }
-            'G' => self.skip_to_last(),
-            'k' => self.on_up_key(),
-            'j' => self.on_down_key(),
-            'f' => self.data_store.toggle_frozen(),
-            'c' => {
+            Action::KillProcess => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::Cpu);
-                    }
+                    self.kill_current_process(false);
                 }
             }
-            'm' => {
+            Action::KillTree => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::Mem);
-                    }
-                } else if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(1);
+                    self.kill_current_process(true);
                 }
             }
-            'p' => {
+            Action::ChangePriority => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::PidOrCount);
-                    }
-                } else if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(5);
+                    self.open_priority_dialog();
                 }
             }
-            'P' => {
+            Action::ChangeAffinity => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.toggle_command();
-                    }
+                    self.open_affinity_dialog();
                 }
             }
-            'n' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::ProcNameOrCommand);
-                    }
-                } else if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(3);
+            Action::ToggleSortMenu => self.toggle_sort_menu(),
+            Action::ToggleTreeMode => self.toggle_tree_mode(),
+            Action::ToggleBranch => self.on_space_key(),
+            Action::ToggleGrouping => self.on_tab(),
+            Action::CycleGroupKey => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.cycle_group_key();
                 }
             }
-            #[cfg(feature = "gpu")]
-            'M' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::GpuMem);
-                    }
+            Action::ToggleCommand => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_command();
                 }
             }
-            #[cfg(feature = "gpu")]
-            'C' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.select_column(ProcWidgetColumn::GpuUtil);
-                    }
+            Action::ToggleKernelThreads => {
+                #[cfg(target_os = "linux")]
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_k_thread();
                 }
             }
-            '?' => {
-                self.help_dialog_state.is_showing_help = true;
-                self.is_force_redraw = true;
+            Action::ToggleExited => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_exited();
+                }
             }
-            'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
-            'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
-            'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
-            'J' | 'S' => self.move_widget_selection(&WidgetDirection::Down),
-            't' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    self.toggle_tree_mode()
-                } else if let Some(temp) = self
-                    .states
-                    .temp_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    temp.table.set_sort_index(1);
-                    temp.force_data_update();
-                } else if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(4);
+            Action::ToggleHistory => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_history();
                 }
             }
-            '+' => self.on_plus(),
-            '-' => self.on_minus(),
-            '=' => self.reset_zoom(),
-            'e' => self.toggle_expand_widget(),
-            's' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    self.toggle_sort_menu()
-                } else if let Some(temp) = self
-                    .states
-                    .temp_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    temp.table.set_sort_index(0);
-                    temp.force_data_update();
-                    self.is_force_redraw = true;
+            Action::ToggleFollow => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_follow();
                 }
             }
-            'u' => {
-                if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(2);
+            Action::ChooseColumns => self.open_columns_dialog(),
+            Action::ToggleTreeFilter => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_tree_filter();
                 }
             }
-            'r' => {
-                if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(6);
+            Action::ToggleMark => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.toggle_mark();
                 }
             }
-            'w' => {
-                if let Some(disk) = self
-                    .states
-                    .disk_state
-                    .get_mut_widget_state(self.current_widget.widget_id)
-                {
-                    disk.set_index(7);
+            Action::MarkRange => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.mark_range();
                 }
             }
-            'I' => self.invert_sort(),
-            '%' => self.toggle_percentages(),
-            #[cfg(target_os = "linux")]
-            'z' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .states
-                        .proc_state
-                        .widget_states
-                        .get_mut(&self.current_widget.widget_id)
-                    {
-                        proc_widget_state.toggle_k_thread();
-                    }
+            Action::MarkAll => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.mark_all();
                 }
             }
-            _ => {}
+            Action::ClearMarks => {
+                if let Some(proc_widget_state) = self.current_proc_widget_state() {
+                    proc_widget_state.clear_marks();
+                }
+            }
+            Action::AddSortKey => self.add_sort_key(),
+            Action::InvertSort => self.invert_sort(),
+            Action::SortByCpu => self.select_process_column(ProcWidgetColumn::Cpu),
+            Action::SortByMemory => self.select_process_column(ProcWidgetColumn::Mem),
+            Action::SortByPid => self.select_process_column(ProcWidgetColumn::PidOrCount),
+            Action::SortByName => self.select_process_column(ProcWidgetColumn::ProcNameOrCommand),
+            Action::SortByGpu => {
+                #[cfg(feature = "gpu")]
+                self.select_process_column(ProcWidgetColumn::GpuUtil);
+            }
+            Action::SortByGpuMemory => {
+                #[cfg(feature = "gpu")]
+                self.select_process_column(ProcWidgetColumn::GpuMem);
+            }
+            Action::TempSortBySensor => self.set_temp_sort_index(0),
+            Action::TempSortByValue => self.set_temp_sort_index(1),
+            Action::TempAddSortKey | Action::DiskAddSortKey => self.add_sort_key(),
+            Action::DiskSortByName => self.set_disk_sort_index(0),
+            Action::DiskSortByMount => self.set_disk_sort_index(1),
+            Action::DiskSortByUsed => self.set_disk_sort_index(2),
+            Action::DiskSortByFree => self.set_disk_sort_index(3),
+            Action::DiskSortByTotal => self.set_disk_sort_index(4),
+            Action::DiskSortByUsedPercent => self.set_disk_sort_index(5),
+            Action::DiskSortByRead => self.set_disk_sort_index(6),
+            Action::DiskSortByWrite => self.set_disk_sort_index(7),
         }
+    }
 
-        if let Some(second_char) = self.second_char {
-            if self.awaiting_second_char && caught_char != second_char {
-                self.awaiting_second_char = false;
+    /// The state of the current widget if it is a process widget.
+    fn current_proc_widget_state(&mut self) -> Option<&mut ProcWidgetState> {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.states
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+        } else {
+            None
+        }
+    }
+
+    fn select_process_column(&mut self, column: ProcWidgetColumn) {
+        if let Some(proc_widget_state) = self.current_proc_widget_state() {
+            proc_widget_state.select_column(column);
+        }
+    }
+
+    fn set_temp_sort_index(&mut self, index: usize) {
+        if let Some(temp) = self
+            .states
+            .temp_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            temp.table.set_sort_index(index);
+            temp.force_data_update();
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn set_disk_sort_index(&mut self, index: usize) {
+        if let Some(disk) = self
+            .states
+            .disk_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            disk.set_index(index);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Switch to the layout at the given index, restoring its last selected widget.
+    /// Widget states are shared between layouts, so nothing else is lost.
+    pub fn switch_layout(&mut self, index: usize) {
+        if index == self.layout_tabs.current || index >= self.layout_tabs.names.len() {
+            return;
+        }
+
+        let current = self.layout_tabs.current;
+        self.layout_tabs.selected_widgets[current] = self.current_widget.widget_id;
+        self.layout_tabs.current = index;
+
+        if let Some(new_widget) = self
+            .widget_map
+            .get(&self.layout_tabs.selected_widgets[index])
+        {
+            self.current_widget = new_widget.clone();
+        }
+
+        self.reset_multi_tap_keys();
+        self.is_force_redraw = true;
+    }
+
+    /// Switch to the next theme.
+    fn cycle_theme(&mut self) {
+        match self.app_config_fields.themes.next() {
+            Ok(styles) => {
+                self.new_styles = Some(styles);
+                self.status_error = None;
+            }
+            Err(err) => {
+                let theme = self.app_config_fields.themes.current().to_string();
+                self.set_status_error(&err.context(format!("Couldn't switch to '{theme}'")));
             }
         }
+        self.is_force_redraw = true;
+    }
+
+    /// Open the layout editor for the current layout.
+    fn open_layout_editor(&mut self) {
+        if let Some((section, rows)) = self.layout_tabs.sources.get(self.layout_tabs.current) {
+            self.layout_editor
+                .open(self.layout_tabs.current, section.clone(), rows.clone());
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Handle a key press while the layout editor is open.
+    pub fn on_layout_editor_key(&mut self, event: KeyEvent) {
+        if let Some((index, rows)) = self
+            .layout_editor
+            .on_key_event(event, self.app_config_fields.config_path.as_deref())
+        {
+            if let Some(source) = self.layout_tabs.sources.get_mut(index) {
+                source.1 = rows;
+            }
+        }
+        self.is_force_redraw = true;
+    }
+
+    /// Handle a key press while the event list is open.
+    pub fn on_event_list_key(&mut self, event: KeyEvent) {
+        let num_events = self.data_store.get_data().events.events().len();
+        self.event_list.on_key_event(event, num_events);
+        self.is_force_redraw = true;
     }
 
     fn toggle_expand_widget(&mut self) {
@@ -1809,6 +2335,15 @@ impl App {
                         cpu_widget_state.table.scroll_to_first();
                     }
                 }
+                BottomWidgetType::CpuHeatmap => {
+                    if let Some(cpu_heatmap_state) = self
+                        .states
+                        .cpu_heatmap_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        cpu_heatmap_state.select_first();
+                    }
+                }
 
                 _ => {}
             }
@@ -1870,6 +2405,15 @@ impl App {
                         cpu_widget_state.table.scroll_to_last();
                     }
                 }
+                BottomWidgetType::CpuHeatmap => {
+                    if let Some(cpu_heatmap_state) = self
+                        .states
+                        .cpu_heatmap_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        cpu_heatmap_state.select_last();
+                    }
+                }
                 _ => {}
             }
             self.reset_multi_tap_keys();
@@ -1899,11 +2443,30 @@ impl App {
                 BottomWidgetType::Temp => self.change_temp_position(amount),
                 BottomWidgetType::Disk => self.change_disk_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
+                BottomWidgetType::CpuHeatmap => {
+                    if let Some(cpu_heatmap_state) = self
+                        .states
+                        .cpu_heatmap_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        cpu_heatmap_state.move_selection_rows(amount);
+                    }
+                }
                 _ => {}
             }
         }
     }
 
+    fn change_cpu_heatmap_position(&mut self, num_to_change_by: i64) {
+        if let Some(cpu_heatmap_state) = self
+            .states
+            .cpu_heatmap_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            cpu_heatmap_state.move_selection(num_to_change_by);
+        }
+    }
+
     fn change_process_sort_position(&mut self, num_to_change_by: i64) {
         if let Some(proc_widget_state) = self
             .states
@@ -2073,6 +2636,14 @@ impl App {
             {
                 Some(widget_state.graph.state_mut())
             }
+            BottomWidgetType::Custom
+                if let Some(widget_state) = self
+                    .states
+                    .custom_state
+                    .get_mut_widget_state(self.current_widget.widget_id) =>
+            {
+                Some(widget_state.graph.state_mut())
+            }
             _ => None,
         }
     }
@@ -2095,9 +2666,87 @@ impl App {
         }
     }
 
+    fn toggle_inspect(&mut self) {
+        if let Some(ts_state) = self.current_ts_state() {
+            ts_state.toggle_inspect();
+        }
+    }
+
+    /// Move the inspect cursor of the current graph by `steps` columns, where
+    /// positive steps move back in time.
+    fn move_inspect_cursor(&mut self, steps: i64) {
+        if let Some(ts_state) = self.current_ts_state() {
+            ts_state.move_inspect_cursor(steps);
+        }
+    }
+
+    /// How much time the retained graph data spans, in milliseconds.
+    fn history_span(&self) -> u64 {
+        let times = &self.data_store.get_data().time_series(Duration::MAX).time;
+        match (times.first(), times.last()) {
+            (Some(first), Some(last)) => last.duration_since(*first).as_millis() as u64,
+            _ => 0,
+        }
+    }
+
+    /// Every graph's [`TimeseriesState`].
+    fn ts_states_mut(&mut self) -> impl Iterator<Item = &mut TimeseriesState> {
+        let states = &mut self.states;
+        let cpu = states
+            .cpu_state
+            .widget_states
+            .values_mut()
+            .map(|widget_state| widget_state.graph.state_mut());
+        let mem = states
+            .mem_state
+            .widget_states
+            .values_mut()
+            .map(|widget_state| widget_state.graph.state_mut());
+        let net = states
+            .net_state
+            .widget_states
+            .values_mut()
+            .map(|widget_state| widget_state.graph.state_mut());
+        let temp = states
+            .temp_graph_state
+            .widget_states
+            .values_mut()
+            .map(|widget_state| widget_state.graph.state_mut());
+        let custom = states
+            .custom_state
+            .widget_states
+            .values_mut()
+            .map(|widget_state| widget_state.graph.state_mut());
+
+        cpu.chain(mem).chain(net).chain(temp).chain(custom)
+    }
+
+    /// Update which CPU heatmap cell the mouse is over, and move the cursor of
+    /// any graph being inspected to the mouse. Returns whether anything changed
+    /// and needs to be redrawn.
+    pub fn on_mouse_move(&mut self, x: u16, y: u16) -> bool {
+        let mut changed = false;
+        for cpu_heatmap_state in self.states.cpu_heatmap_state.widget_states.values_mut() {
+            let hovered = cpu_heatmap_state.core_at(x, y);
+            if cpu_heatmap_state.hovered != hovered {
+                cpu_heatmap_state.hovered = hovered;
+                changed = true;
+            }
+        }
+
+        for ts_state in self.ts_states_mut() {
+            changed |= ts_state.set_inspect_cursor_at(x, y);
+        }
+
+        changed
+    }
+
     /// Moves the mouse to the widget that was clicked on, then propagates the
     /// click down to be handled by the widget specifically.
-    pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
+    ///
+    /// If `is_shift` is set, clicking a table header adds that column as
+    /// another column to sort by.
+    pub fn on_left_mouse_up(&mut self, x: u16, y: u16, is_shift: bool) {
         // Pretty dead simple - iterate through the widget map and go to the widget
         // where the click is within.
 
@@ -2174,6 +2823,20 @@ impl App {
             }
         }
 
+        // Check if we clicked a layout tab.
+        if let Some(tab_click_locs) = &self.layout_tabs.tab_click_locs {
+            if let Some(index) =
+                tab_click_locs
+                    .iter()
+                    .position(|((tlc_x, tlc_y), (brc_x, brc_y))| {
+                        (x >= *tlc_x && y >= *tlc_y) && (x <= *brc_x && y <= *brc_y)
+                    })
+            {
+                self.switch_layout(index);
+                return;
+            }
+        }
+
         // Second short circuit --- are we in the dd dialog state?  If so, only check
         // yes/no/signals and bail after.
         if self.process_kill_dialog.is_open() && self.process_kill_dialog.on_click(x, y) {
@@ -2344,8 +3007,14 @@ impl App {
                                             .proc_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if state.table.try_select_location(x, y).is_some() {
+                                            let selected = if is_shift {
+                                                state.table.try_add_location(x, y)
+                                            } else {
+                                                state.table.try_select_location(x, y)
+                                            };
+                                            if selected.is_some() {
                                                 state.force_data_update();
+                                                self.is_force_redraw = true;
                                             }
                                         }
                                     }
@@ -2355,8 +3024,14 @@ impl App {
                                             .temp_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if temp.table.try_select_location(x, y).is_some() {
+                                            let selected = if is_shift {
+                                                temp.table.try_add_location(x, y)
+                                            } else {
+                                                temp.table.try_select_location(x, y)
+                                            };
+                                            if selected.is_some() {
                                                 temp.force_data_update();
+                                                self.is_force_redraw = true;
                                             }
                                         }
                                     }
@@ -2366,8 +3041,14 @@ impl App {
                                             .disk_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if disk.table.try_select_location(x, y).is_some() {
+                                            let selected = if is_shift {
+                                                disk.table.try_add_location(x, y)
+                                            } else {
+                                                disk.table.try_select_location(x, y)
+                                            };
+                                            if selected.is_some() {
                                                 disk.force_data_update();
+                                                self.is_force_redraw = true;
                                             }
                                         }
                                     }
@@ -2405,6 +3086,17 @@ impl App {
                             }
                         }
                     }
+                    BottomWidgetType::CpuHeatmap => {
+                        if let Some(cpu_heatmap_state) = self
+                            .states
+                            .cpu_heatmap_state
+                            .get_mut_widget_state(self.current_widget.widget_id)
+                        {
+                            if let Some(core) = cpu_heatmap_state.core_at(x, y) {
+                                cpu_heatmap_state.selected = Some(core);
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -2452,3 +3144,22 @@ impl App {
         }
     }
 }
+
+/// Move the states of widgets that are the same in both layouts from `old` to
+/// `new`, if the config they were created from is unchanged.
+fn keep_widget_states<T>(
+    old: &mut HashMap<u64, T>, new: &mut HashMap<u64, T>, same_config: bool,
+    is_same_widget: impl Fn(u64) -> bool,
+) {
+    if !same_config {
+        return;
+    }
+
+    for (id, state) in new.iter_mut() {
+        if is_same_widget(*id) {
+            if let Some(old_state) = old.remove(id) {
+                *state = old_state;
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Downsampled copies of the time series data, so that long retention periods
+//! don't require keeping (and drawing) every raw sample.
+//!
+//! Raw samples are only kept for [`RAW_DATA_AGE`]. Past that, each tier keeps
+//! averages over a fixed resolution, with coarser tiers keeping data for
+//! longer. A tier is only used if the retention period is longer than what the
+//! finer tiers keep.
+
+use std::time::{Duration, Instant};
+
+use super::TimeSeriesData;
+
+/// How long raw samples are kept for.
+pub const RAW_DATA_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// The resolution and maximum age of each tier, from finest to coarsest. The
+/// last tier keeps data for the whole retention period.
+const TIERS: [(Duration, Duration); 2] = [
+    (Duration::from_secs(10), Duration::from_secs(6 * 60 * 60)),
+    (Duration::from_secs(60), Duration::MAX),
+];
+
+/// Time series data downsampled to a lower resolution.
+#[derive(Clone, Debug)]
+pub struct DownsampledTier {
+    /// How much time each point covers.
+    pub resolution: Duration,
+
+    /// How long points are kept for, before taking the retention period into
+    /// account.
+    max_age: Duration,
+
+    /// How long the finer tiers keep data for. This tier is only needed if
+    /// the retention period is longer than this.
+    min_age: Duration,
+
+    pub data: TimeSeriesData,
+
+    /// The time of the oldest raw sample that hasn't been averaged yet.
+    bucket_start: Option<Instant>,
+}
+
+/// All downsampled tiers of the time series data.
+#[derive(Clone, Debug)]
+pub struct DownsampledData {
+    pub(super) tiers: Vec<DownsampledTier>,
+}
+
+impl Default for DownsampledData {
+    fn default() -> Self {
+        let mut min_age = RAW_DATA_AGE;
+        let tiers = TIERS
+            .iter()
+            .map(|&(resolution, max_age)| {
+                let tier = DownsampledTier {
+                    resolution,
+                    max_age,
+                    min_age,
+                    data: TimeSeriesData {
+                        resolution: Some(resolution),
+                        ..Default::default()
+                    },
+                    bucket_start: None,
+                };
+                min_age = max_age;
+
+                tier
+            })
+            .collect();
+
+        Self { tiers }
+    }
+}
+
+impl DownsampledData {
+    /// The tiers that are used with the retention period.
+    pub(super) fn active_tiers_mut(
+        &mut self, retention: Duration,
+    ) -> impl Iterator<Item = &mut DownsampledTier> {
+        self.tiers
+            .iter_mut()
+            .filter(move |tier| retention > tier.min_age)
+    }
+
+    /// Update the tiers with the newest sample in `raw`. Once a tier has
+    /// collected samples over its resolution, they're averaged into one point.
+    pub fn update(&mut self, raw: &TimeSeriesData, retention: Duration) {
+        let Some(&newest) = raw.time.last() else {
+            return;
+        };
+
+        for tier in self.active_tiers_mut(retention) {
+            match tier.bucket_start {
+                Some(start) if newest.duration_since(start) >= tier.resolution => {
+                    tier.data.push_average(raw, start..newest);
+                    tier.bucket_start = Some(newest);
+                }
+                Some(_) => {}
+                None => tier.bucket_start = Some(newest),
+            }
+        }
+    }
+
+    /// Prune the raw data and each tier to how long they keep data for.
+    pub fn prune(&mut self, raw: &mut TimeSeriesData, retention: Duration) {
+        raw.prune(retention.min(RAW_DATA_AGE));
+
+        for tier in self.active_tiers_mut(retention) {
+            tier.data.prune(retention.min(tier.max_age));
+        }
+    }
+
+    /// Pick the data for a graph showing `span` of time: the raw data if it
+    /// covers the span, otherwise the finest tier that does. If no tier has any
+    /// data yet, the raw data is used.
+    pub fn select<'a>(&'a self, raw: &'a TimeSeriesData, span: Duration) -> &'a TimeSeriesData {
+        if span <= RAW_DATA_AGE {
+            return raw;
+        }
+
+        let mut tiers = self
+            .tiers
+            .iter()
+            .filter(|tier| !tier.data.time.is_empty())
+            .peekable();
+        while let Some(tier) = tiers.next() {
+            if span <= tier.max_age || tiers.peek().is_none() {
+                return &tier.data;
+            }
+        }
+
+        raw
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{DownsampledData, RAW_DATA_AGE};
+    use crate::app::data::TimeSeriesData;
+
+    /// Build raw data with one CPU sample per second, where sample `i` has a
+    /// usage of `i`.
+    fn raw(start: Instant, seconds: u64) -> TimeSeriesData {
+        let mut raw = TimeSeriesData::default();
+        raw.cpu.push(Default::default());
+        for i in 0..seconds {
+            raw.time.push(start + Duration::from_secs(i));
+            raw.cpu[0].push(i as f64);
+        }
+        raw
+    }
+
+    #[test]
+    fn averages() {
+        let start = Instant::now();
+        let retention = Duration::from_secs(24 * 60 * 60);
+        let mut downsampled = DownsampledData::default();
+
+        // Feed samples one at a time, like they are collected.
+        let full = raw(start, 31);
+        let mut partial = TimeSeriesData::default();
+        partial.cpu.push(Default::default());
+        for (time, value) in full.cpu[0].iter_along_base(&full.time) {
+            partial.time.push(*time);
+            partial.cpu[0].push(*value);
+            downsampled.update(&partial, retention);
+        }
+
+        // The 10 second tier averages 0-9, 10-19 and 20-29.
+        let tier = &downsampled.tiers[0].data;
+        assert_eq!(
+            tier.cpu[0].iter().copied().collect::<Vec<_>>(),
+            vec![4.5, 14.5, 24.5]
+        );
+        assert_eq!(tier.time[0], start + Duration::from_secs(5));
+        assert_eq!(tier.resolution, Some(Duration::from_secs(10)));
+
+        // The 1 minute tier doesn't have a full minute yet.
+        assert!(downsampled.tiers[1].data.time.is_empty());
+    }
+
+    #[test]
+    fn short_retention() {
+        let start = Instant::now();
+        let mut downsampled = DownsampledData::default();
+        let full = raw(start, 30);
+        downsampled.update(&full, RAW_DATA_AGE);
+
+        // Everything fits in the raw data, so the tiers aren't used.
+        assert!(
+            downsampled
+                .tiers
+                .iter()
+                .all(|tier| tier.bucket_start.is_none())
+        );
+    }
+
+    #[test]
+    fn select() {
+        let start = Instant::now();
+        let raw = raw(start, 1);
+        let mut downsampled = DownsampledData::default();
+
+        let minute = Duration::from_secs(60);
+        let hour = 60 * minute;
+
+        // Without any downsampled data, the raw data is always used.
+        assert!(std::ptr::eq(downsampled.select(&raw, 24 * hour), &raw));
+
+        downsampled.tiers[0].data.time.push(start);
+        downsampled.tiers[1].data.time.push(start);
+        assert!(std::ptr::eq(downsampled.select(&raw, minute), &raw));
+        assert!(std::ptr::eq(
+            downsampled.select(&raw, hour),
+            &downsampled.tiers[0].data
+        ));
+        assert!(std::ptr::eq(
+            downsampled.select(&raw, 24 * hour),
+            &downsampled.tiers[1].data
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+//! A timeline of notable events, which are drawn as markers on graphs.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use super::WatchedProcess;
+use crate::collection::processes::{Pid, ProcessHarvest};
+
+/// The kind of an [`Event`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    ProcessStarted,
+    ProcessExited,
+    ProcessKilled,
+    InterfaceUp,
+    InterfaceDown,
+    Mounted,
+    Unmounted,
+    Battery,
+}
+
+impl EventKind {
+    /// Return the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::ProcessStarted => "Started",
+            EventKind::ProcessExited => "Exited",
+            EventKind::ProcessKilled => "Killed",
+            EventKind::InterfaceUp => "Up",
+            EventKind::InterfaceDown => "Down",
+            EventKind::Mounted => "Mounted",
+            EventKind::Unmounted => "Unmounted",
+            EventKind::Battery => "Battery",
+        }
+    }
+}
+
+/// Something notable that happened at some point in time.
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub time: Instant,
+    pub kind: EventKind,
+    pub description: String,
+}
+
+/// All recorded events, from oldest to newest, along with what was last seen
+/// so changes can be detected. Nothing is recorded the first time something is
+/// seen, as everything would look new.
+#[derive(Clone, Debug, Default)]
+pub struct EventLog {
+    events: VecDeque<Event>,
+    watched_processes: Option<HashMap<Pid, String>>,
+    interfaces: Option<HashMap<String, bool>>,
+    mounts: Option<HashSet<String>>,
+    batteries: Option<Vec<&'static str>>,
+}
+
+impl EventLog {
+    /// Record an event.
+    pub fn push(&mut self, time: Instant, kind: EventKind, description: String) {
+        self.events.push_back(Event {
+            time,
+            kind,
+            description,
+        });
+    }
+
+    /// All recorded events, from oldest to newest.
+    pub fn events(&self) -> impl DoubleEndedIterator<Item = &Event> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    /// The times of all recorded events, from oldest to newest.
+    pub fn times(&self) -> Vec<Instant> {
+        self.events.iter().map(|event| event.time).collect()
+    }
+
+    /// Remove any events older than the given duration.
+    pub fn prune(&mut self, max_age: Duration) {
+        let now = Instant::now();
+        while self
+            .events
+            .front()
+            .is_some_and(|event| now.duration_since(event.time) > max_age)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    /// Record watched processes starting or exiting.
+    pub fn update_processes(
+        &mut self, time: Instant, processes: &[ProcessHarvest], watched: &[WatchedProcess],
+    ) {
+        if watched.is_empty() {
+            return;
+        }
+
+        let current: HashMap<Pid, String> = processes
+            .iter()
+            .filter(|process| watched.iter().any(|watched| watched.matches(process)))
+            .map(|process| (process.pid, process.name.clone()))
+            .collect();
+
+        if let Some(previous) = &self.watched_processes {
+            let mut exited: Vec<_> = previous
+                .iter()
+                .filter(|(pid, _)| !current.contains_key(pid))
+                .map(|(pid, name)| (*pid, format!("{name} ({pid}) exited")))
+                .collect();
+            let mut started: Vec<_> = current
+                .iter()
+                .filter(|(pid, _)| !previous.contains_key(pid))
+                .map(|(pid, name)| (*pid, format!("{name} ({pid}) started")))
+                .collect();
+            exited.sort_unstable_by_key(|(pid, _)| *pid);
+            started.sort_unstable_by_key(|(pid, _)| *pid);
+
+            for (_, description) in exited {
+                self.push(time, EventKind::ProcessExited, description);
+            }
+            for (_, description) in started {
+                self.push(time, EventKind::ProcessStarted, description);
+            }
+        }
+
+        self.watched_processes = Some(current);
+    }
+
+    /// Record network interfaces going up or down. Each interface is given
+    /// with whether it is up; interfaces that disappear count as going down.
+    pub fn update_interfaces(&mut self, time: Instant, interfaces: &[(String, bool)]) {
+        let current: HashMap<String, bool> = interfaces.iter().cloned().collect();
+
+        if let Some(previous) = &self.interfaces {
+            let mut changes: Vec<_> = current
+                .iter()
+                .filter(|(name, is_up)| previous.get(*name).unwrap_or(&false) != *is_up)
+                .map(|(name, is_up)| (name.clone(), *is_up))
+                .chain(
+                    previous
+                        .iter()
+                        .filter(|(name, is_up)| **is_up && !current.contains_key(*name))
+                        .map(|(name, _)| (name.clone(), false)),
+                )
+                .collect();
+            changes.sort_unstable();
+
+            for (name, is_up) in changes {
+                if is_up {
+                    self.push(time, EventKind::InterfaceUp, format!("{name} went up"));
+                } else {
+                    self.push(time, EventKind::InterfaceDown, format!("{name} went down"));
+                }
+            }
+        }
+
+        self.interfaces = Some(current);
+    }
+
+    /// Record filesystems being mounted or unmounted.
+    pub fn update_mounts<'a>(&mut self, time: Instant, mounts: impl Iterator<Item = &'a str>) {
+        let current: HashSet<String> = mounts.map(str::to_string).collect();
+
+        if let Some(previous) = &self.mounts {
+            let mut unmounted: Vec<_> = previous.difference(&current).cloned().collect();
+            let mut mounted: Vec<_> = current.difference(previous).cloned().collect();
+            unmounted.sort_unstable();
+            mounted.sort_unstable();
+
+            for mount in unmounted {
+                self.push(time, EventKind::Unmounted, format!("{mount} unmounted"));
+            }
+            for mount in mounted {
+                self.push(time, EventKind::Mounted, format!("{mount} mounted"));
+            }
+        }
+
+        self.mounts = Some(current);
+    }
+
+    /// Record batteries changing state (e.g. from charging to discharging).
+    pub fn update_batteries(&mut self, time: Instant, states: Vec<&'static str>) {
+        if let Some(previous) = &self.batteries {
+            let changes: Vec<_> = states
+                .iter()
+                .zip(previous)
+                .enumerate()
+                .filter(|(_, (state, previous))| state != previous)
+                .map(|(index, (state, previous))| format!("Battery {index}: {previous} to {state}"))
+                .collect();
+
+            for description in changes {
+                self.push(time, EventKind::Battery, description);
+            }
+        }
+
+        self.batteries = Some(states);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{EventKind, EventLog};
+    use crate::{app::data::WatchedProcess, collection::processes::ProcessHarvest};
+
+    fn kinds(log: &EventLog) -> Vec<(EventKind, &str)> {
+        log.events()
+            .map(|event| (event.kind, event.description.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn processes() {
+        let now = Instant::now();
+        let mut log = EventLog::default();
+        let watched = vec![
+            WatchedProcess::Name("backup".into()),
+            WatchedProcess::Pid(4),
+        ];
+        let process = |pid, name: &str| ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        // Already running processes aren't new.
+        log.update_processes(now, &[process(1, "backup"), process(2, "sh")], &watched);
+        assert!(kinds(&log).is_empty());
+
+        log.update_processes(now, &[process(2, "sh"), process(3, "backup")], &watched);
+        assert_eq!(
+            kinds(&log),
+            vec![
+                (EventKind::ProcessExited, "backup (1) exited"),
+                (EventKind::ProcessStarted, "backup (3) started"),
+            ]
+        );
+
+        // Watched PIDs are recorded whatever their name.
+        log.update_processes(now, &[process(3, "backup"), process(4, "sh")], &watched);
+        assert_eq!(
+            kinds(&log)[2..],
+            [(EventKind::ProcessStarted, "sh (4) started")]
+        );
+    }
+
+    #[test]
+    fn interfaces() {
+        let now = Instant::now();
+        let mut log = EventLog::default();
+
+        log.update_interfaces(now, &[("eth0".into(), true), ("wlan0".into(), false)]);
+        log.update_interfaces(now, &[("wlan0".into(), true), ("tun0".into(), false)]);
+        assert_eq!(
+            kinds(&log),
+            vec![
+                (EventKind::InterfaceDown, "eth0 went down"),
+                (EventKind::InterfaceUp, "wlan0 went up"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mounts_and_batteries() {
+        let now = Instant::now();
+        let mut log = EventLog::default();
+
+        log.update_mounts(now, ["/", "/mnt/usb"].into_iter());
+        log.update_mounts(now, ["/", "/mnt/backup"].into_iter());
+        log.update_batteries(now, vec!["Charging"]);
+        log.update_batteries(now, vec!["Discharging"]);
+        assert_eq!(
+            kinds(&log),
+            vec![
+                (EventKind::Unmounted, "/mnt/usb unmounted"),
+                (EventKind::Mounted, "/mnt/backup mounted"),
+                (EventKind::Battery, "Battery 0: Charging to Discharging"),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune() {
+        let mut log = EventLog::default();
+        let now = Instant::now();
+        log.push(
+            now - Duration::from_secs(120),
+            EventKind::Mounted,
+            "old".into(),
+        );
+        log.push(now, EventKind::Mounted, "new".into());
+
+        log.prune(Duration::from_secs(60));
+        assert_eq!(kinds(&log), vec![(EventKind::Mounted, "new")]);
+    }
+}
@@ -0,0 +1,413 @@
+//! Saving the time series data to disk, so graphs don't start out empty after
+//! bottom is restarted.
+//!
+//! [`Instant`]s can't be saved, so times are saved as milliseconds since the
+//! Unix epoch and converted back relative to the current time when loaded.
+//! Values are saved with one entry per time value, where gaps are `null`.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    ops::Range,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, bail};
+use indexmap::IndexMap;
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+use timeless::data::ChunkedData;
+
+use super::{DownsampledData, TimeSeriesData};
+use crate::{app::AppConfigFields, utils::data_units::DataUnit};
+
+/// How often the history is saved while bottom is running.
+pub const HISTORY_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The version of the history file format. Bump this if the format changes in
+/// a way that older files can't be read.
+const HISTORY_VERSION: u32 = 1;
+
+/// A series with one value per time value, where `None` is a gap.
+type SavedValues<F> = Vec<Option<F>>;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryFile {
+    version: u32,
+
+    /// The unit temperatures were saved in.
+    temperature_unit: String,
+
+    /// Whether network data was saved in bytes rather than bits.
+    network_use_bytes: bool,
+
+    raw: SavedSeries,
+
+    /// The downsampled tiers, from finest to coarsest.
+    tiers: Vec<SavedSeries>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SavedSeries {
+    /// Time values, in milliseconds since the Unix epoch.
+    time: Vec<u64>,
+    rx: SavedValues<f64>,
+    tx: SavedValues<f64>,
+    cpu: Vec<SavedValues<f64>>,
+    ram: SavedValues<f64>,
+    swap: SavedValues<f64>,
+    cache_mem: SavedValues<f64>,
+    arc_mem: SavedValues<f64>,
+    gpu_mem: HashMap<String, SavedValues<f64>>,
+    temperature: HashMap<String, SavedValues<f32>>,
+    custom: HashMap<String, IndexMap<String, SavedValues<f64>>>,
+}
+
+/// Used to convert between [`Instant`]s and wall clock times.
+#[derive(Clone, Copy)]
+struct Clock {
+    now: Instant,
+    wall_now: SystemTime,
+}
+
+impl Clock {
+    fn new() -> Self {
+        Self {
+            now: Instant::now(),
+            wall_now: SystemTime::now(),
+        }
+    }
+
+    fn millis_since_epoch(self, time: Instant) -> u64 {
+        self.wall_now
+            .checked_sub(self.now.duration_since(time))
+            .and_then(|wall| wall.duration_since(UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    /// Convert a saved time back to an [`Instant`]. This fails for times in the
+    /// future (e.g. if the system clock was changed), or too far in the past
+    /// to be represented.
+    fn instant(self, millis: u64) -> Option<Instant> {
+        let age = self
+            .wall_now
+            .duration_since(UNIX_EPOCH + Duration::from_millis(millis))
+            .ok()?;
+
+        self.now.checked_sub(age)
+    }
+}
+
+fn save_values<F: Copy>(values: &ChunkedData<F>, len: usize) -> SavedValues<F> {
+    let mut saved = vec![None; len];
+    for (index, value) in values.iter_with_index() {
+        if let Some(entry) = saved.get_mut(index) {
+            *entry = Some(*value);
+        }
+    }
+
+    saved
+}
+
+/// Load the values in `range`, padding with gaps if the saved series is too
+/// short. A break is inserted at the end, so new values aren't joined up with
+/// the loaded ones.
+fn load_values<F: Default>(saved: SavedValues<F>, range: &Range<usize>) -> ChunkedData<F> {
+    let mut values = ChunkedData::default();
+    for value in saved.into_iter().skip(range.start).take(range.len()) {
+        values.try_push(value);
+    }
+    while values.length() < range.len() {
+        values.try_push(None);
+    }
+    values.insert_break();
+
+    values
+}
+
+impl SavedSeries {
+    fn save(data: &TimeSeriesData, clock: Clock) -> Self {
+        let len = data.time.len();
+
+        let mut saved = Self {
+            time: data
+                .time
+                .iter()
+                .map(|&time| clock.millis_since_epoch(time))
+                .collect(),
+            rx: save_values(&data.rx, len),
+            tx: save_values(&data.tx, len),
+            cpu: data.cpu.iter().map(|cpu| save_values(cpu, len)).collect(),
+            ram: save_values(&data.ram, len),
+            swap: save_values(&data.swap, len),
+            temperature: data
+                .temperature
+                .iter()
+                .map(|(name, values)| (name.clone(), save_values(values, len)))
+                .collect(),
+            custom: data
+                .custom
+                .iter()
+                .map(|(name, source)| {
+                    let source = source
+                        .iter()
+                        .map(|(key, values)| (key.clone(), save_values(values, len)))
+                        .collect();
+                    (name.clone(), source)
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            saved.cache_mem = save_values(&data.cache_mem, len);
+        }
+
+        #[cfg(feature = "zfs")]
+        {
+            saved.arc_mem = save_values(&data.arc_mem, len);
+        }
+
+        #[cfg(feature = "gpu")]
+        {
+            saved.gpu_mem = data
+                .gpu_mem
+                .iter()
+                .map(|(name, values)| (name.clone(), save_values(values, len)))
+                .collect();
+        }
+
+        saved
+    }
+
+    /// Convert the saved series back. Times that can't be converted are
+    /// dropped; as times only go up, these are either the oldest or newest
+    /// ones. If `network` or `temperature` is false, those series are saved in
+    /// a different unit, and are left empty.
+    fn load(self, clock: Clock, network: bool, temperature: bool) -> TimeSeriesData {
+        let times = self
+            .time
+            .iter()
+            .map(|&millis| clock.instant(millis))
+            .collect::<Vec<_>>();
+        let start = times
+            .iter()
+            .position(Option::is_some)
+            .unwrap_or(times.len());
+        let end = start + times[start..].iter().take_while(|t| t.is_some()).count();
+        let range = start..end;
+
+        let (rx, tx) = if network {
+            (self.rx, self.tx)
+        } else {
+            Default::default()
+        };
+        let temperatures = if temperature {
+            self.temperature
+        } else {
+            Default::default()
+        };
+
+        TimeSeriesData {
+            time: times[range.clone()].iter().flatten().copied().collect(),
+            resolution: None,
+            rx: load_values(rx, &range),
+            tx: load_values(tx, &range),
+            cpu: self
+                .cpu
+                .into_iter()
+                .map(|cpu| load_values(cpu, &range))
+                .collect(),
+            ram: load_values(self.ram, &range),
+            swap: load_values(self.swap, &range),
+            #[cfg(not(target_os = "windows"))]
+            cache_mem: load_values(self.cache_mem, &range),
+            #[cfg(feature = "zfs")]
+            arc_mem: load_values(self.arc_mem, &range),
+            #[cfg(feature = "gpu")]
+            gpu_mem: self
+                .gpu_mem
+                .into_iter()
+                .map(|(name, values)| (name, load_values(values, &range)))
+                .collect(),
+            temperature: temperatures
+                .into_iter()
+                .map(|(name, values)| (name, load_values(values, &range)))
+                .collect(),
+            custom: self
+                .custom
+                .into_iter()
+                .map(|(name, source)| {
+                    let source = source
+                        .into_iter()
+                        .map(|(key, values)| (key, load_values(values, &range)))
+                        .collect();
+                    (name, source)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Save the time series data and its downsampled tiers to `path`. The file is
+/// written to a temporary file first, so a partially written file never
+/// replaces the previous one.
+pub(super) fn save_history(
+    path: &Path, raw: &TimeSeriesData, downsampled: &DownsampledData, settings: &AppConfigFields,
+) -> anyhow::Result<()> {
+    let clock = Clock::new();
+    let history = HistoryFile {
+        version: HISTORY_VERSION,
+        temperature_unit: settings.temperature_type.unit().to_string(),
+        network_use_bytes: matches!(settings.network_unit_type, DataUnit::Byte),
+        raw: SavedSeries::save(raw, clock),
+        tiers: downsampled
+            .tiers
+            .iter()
+            .map(|tier| SavedSeries::save(&tier.data, clock))
+            .collect(),
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Couldn't create {}", parent.display()))?;
+        }
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    fs::write(&temp_path, serde_json::to_vec(&history)?)
+        .with_context(|| format!("Couldn't write to {}", path.display()))?;
+    fs::rename(&temp_path, path)
+        .with_context(|| format!("Couldn't write to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Load the time series data and its downsampled tiers from `path`, dropping
+/// anything older than the retention period. Nothing is loaded if the file
+/// doesn't exist yet.
+pub(super) fn load_history(
+    path: &Path, raw: &mut TimeSeriesData, downsampled: &mut DownsampledData,
+    settings: &AppConfigFields,
+) -> anyhow::Result<()> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Couldn't read {}", path.display()));
+        }
+    };
+
+    let history: HistoryFile = serde_json::from_slice(&contents)
+        .with_context(|| format!("{} is not a valid history file", path.display()))?;
+    if history.version != HISTORY_VERSION {
+        bail!(
+            "{} has an unsupported history file version ({})",
+            path.display(),
+            history.version
+        );
+    }
+
+    let clock = Clock::new();
+    let network = history.network_use_bytes == matches!(settings.network_unit_type, DataUnit::Byte);
+    let temperature = history.temperature_unit == settings.temperature_type.unit();
+    let retention = Duration::from_millis(settings.retention_ms);
+
+    *raw = history.raw.load(clock, network, temperature);
+    for (tier, saved) in downsampled.active_tiers_mut(retention).zip(history.tiers) {
+        tier.data = TimeSeriesData {
+            resolution: tier.data.resolution,
+            ..saved.load(clock, network, temperature)
+        };
+    }
+    downsampled.prune(raw, retention);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{Clock, SavedSeries, load_values, save_values};
+    use crate::app::data::TimeSeriesData;
+
+    #[test]
+    fn values_round_trip() {
+        let mut values = timeless::data::ChunkedData::default();
+        values.push(1.0);
+        values.try_push(None);
+        values.push(3.0);
+
+        let saved = save_values(&values, 4);
+        assert_eq!(saved, vec![Some(1.0), None, Some(3.0), None]);
+
+        let loaded = load_values(saved, &(1..4));
+        assert_eq!(loaded.length(), 3);
+        assert_eq!(
+            loaded.iter_with_index().collect::<Vec<_>>(),
+            vec![(1, &3.0)]
+        );
+    }
+
+    #[test]
+    fn series_round_trip() {
+        let clock = Clock::new();
+        let mut data = TimeSeriesData::default();
+        data.cpu.push(Default::default());
+        for i in 0..3 {
+            data.time.push(clock.now - Duration::from_secs(3 - i));
+            data.cpu[0].push(i as f64);
+            data.rx.push(i as f64);
+        }
+
+        let saved = SavedSeries::save(&data, clock);
+
+        // Load a bit later, like on a restart.
+        let later = Clock {
+            now: clock.now + Duration::from_secs(10),
+            wall_now: clock.wall_now + Duration::from_secs(10),
+        };
+        let loaded = saved.load(later, false, true);
+
+        // Times are within a millisecond, as that's what is saved.
+        for (loaded, original) in loaded.time.iter().zip(&data.time) {
+            let diff = loaded
+                .duration_since(*original)
+                .max(original.duration_since(*loaded));
+            assert!(diff < Duration::from_millis(1));
+        }
+        assert_eq!(
+            loaded.cpu[0].iter().copied().collect::<Vec<_>>(),
+            vec![0.0, 1.0, 2.0]
+        );
+
+        // Network data in a different unit is dropped, but still lines up.
+        assert_eq!(loaded.rx.length(), 3);
+        assert!(loaded.rx.no_elements());
+    }
+
+    #[test]
+    fn future_times_are_dropped() {
+        let clock = Clock::new();
+        let mut data = TimeSeriesData::default();
+        data.time.push(clock.now);
+        data.ram.push(1.0);
+        let saved = SavedSeries::save(&data, clock);
+
+        // The clock went backwards since saving.
+        let earlier = Clock {
+            now: Instant::now(),
+            wall_now: clock.wall_now - Duration::from_secs(60),
+        };
+        let loaded = saved.load(earlier, true, true);
+        assert!(loaded.time.is_empty());
+        assert_eq!(loaded.ram.length(), 0);
+    }
+}
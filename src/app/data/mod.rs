@@ -3,8 +3,17 @@
 mod time_series;
 pub use time_series::{TimeSeriesData, Values};
 
+mod downsample;
+pub use downsample::*;
+
+mod events;
+pub use events::*;
+
+mod history;
+pub use history::HISTORY_SAVE_INTERVAL;
+
 mod process;
-pub use process::ProcessData;
+pub use process::*;
 
 mod store;
 pub use store::*;
@@ -1,10 +1,55 @@
-use std::{collections::BTreeMap, vec::Vec};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Instant,
+    vec::Vec,
+};
 
 use crate::{
-    collection::processes::{Pid, ProcessHarvest},
-    utils::int_hash::IntHashMap,
+    collection::processes::{Bytes, Pid, ProcessHarvest},
+    utils::int_hash::{IntHashMap, IntHashSet},
 };
 
+/// How many exited processes are kept.
+pub const MAX_EXITED_PROCESSES: usize = 200;
+
+/// How many samples of usage history are kept for each process.
+pub const PROCESS_HISTORY_LEN: usize = 120;
+
+/// A sample of a process' usage.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProcessSample {
+    pub cpu_usage_percent: f32,
+    pub mem_usage: Bytes,
+    /// Bytes read and written per second.
+    pub io_per_sec: Bytes,
+}
+
+/// A process whose usage history is shown, by name or PID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchedProcess {
+    Name(String),
+    Pid(Pid),
+}
+
+impl WatchedProcess {
+    /// Whether the process is the one being watched.
+    pub fn matches(&self, process: &ProcessHarvest) -> bool {
+        match self {
+            WatchedProcess::Name(name) => process.name == *name,
+            WatchedProcess::Pid(pid) => process.pid == *pid,
+        }
+    }
+}
+
+/// A process that has exited, as it was last seen.
+#[derive(Clone, Debug)]
+pub struct ExitedProcess {
+    pub process: ProcessHarvest,
+
+    /// When the process was first seen to be gone.
+    pub exit_time: Instant,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ProcessData {
     /// A PID to process data map.
@@ -15,10 +60,18 @@ pub struct ProcessData {
 
     /// PIDs corresponding to processes that have no parents.
     pub orphan_pids: Vec<Pid>,
+
+    /// The most recently exited processes, from oldest to newest.
+    pub exited: VecDeque<ExitedProcess>,
+
+    /// The recent usage of each process, from oldest to newest.
+    pub history: IntHashMap<Pid, VecDeque<ProcessSample>>,
 }
 
 impl ProcessData {
-    pub(super) fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    pub(super) fn ingest(
+        &mut self, list_of_processes: Vec<ProcessHarvest>, harvested_time: Instant,
+    ) {
         self.process_parent_mapping.clear();
 
         // Reverse as otherwise the pid mappings are in the wrong order.
@@ -34,12 +87,14 @@ impl ProcessData {
         });
 
         self.process_parent_mapping.shrink_to_fit();
+        self.record_history(&list_of_processes);
 
         let process_pid_map = list_of_processes
             .into_iter()
             .map(|process| (process.pid, process))
             .collect();
-        self.process_harvest = process_pid_map;
+        let previous = std::mem::replace(&mut self.process_harvest, process_pid_map);
+        self.record_exited(previous, harvested_time);
 
         // We collect all processes that either:
         // - Do not have a parent PID (that is, they are orphan processes)
@@ -53,4 +108,180 @@ impl ProcessData {
             })
             .collect();
     }
+
+    /// The PIDs of the given processes and all their descendants, following
+    /// the same parent links as tree mode. Processes that are gone are skipped.
+    pub fn subtree_pids(&self, roots: &[Pid]) -> IntHashSet<Pid> {
+        let mut pids: IntHashSet<Pid> = roots
+            .iter()
+            .copied()
+            .filter(|pid| self.process_harvest.contains_key(pid))
+            .collect();
+        let mut stack: Vec<Pid> = pids.iter().copied().collect();
+
+        while let Some(pid) = stack.pop() {
+            if let Some(children) = self.process_parent_mapping.get(&pid) {
+                for &child in children {
+                    if self.process_harvest.contains_key(&child) && pids.insert(child) {
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+
+        pids
+    }
+
+    /// Add a sample to the history of each process, and forget the history of
+    /// any that are gone. This must be called before the harvest is replaced,
+    /// as a PID now used by a process with a different name starts over.
+    fn record_history(&mut self, processes: &[ProcessHarvest]) {
+        let mut previous = std::mem::take(&mut self.history);
+
+        self.history = processes
+            .iter()
+            .map(|process| {
+                let is_same_process = self
+                    .process_harvest
+                    .get(&process.pid)
+                    .is_some_and(|previous| previous.name == process.name);
+                let mut samples = previous
+                    .remove(&process.pid)
+                    .filter(|_| is_same_process)
+                    .unwrap_or_default();
+
+                if samples.len() >= PROCESS_HISTORY_LEN {
+                    samples.pop_front();
+                }
+                samples.push_back(ProcessSample {
+                    cpu_usage_percent: process.cpu_usage_percent,
+                    mem_usage: process.mem_usage,
+                    io_per_sec: process.read_per_sec + process.write_per_sec,
+                });
+
+                (process.pid, samples)
+            })
+            .collect();
+    }
+
+    /// Keep any processes from `previous` that are gone. A PID now used by a
+    /// process with a different name also means the old process is gone.
+    fn record_exited(&mut self, previous: BTreeMap<Pid, ProcessHarvest>, exit_time: Instant) {
+        let exited = previous.into_values().filter(|process| {
+            self.process_harvest
+                .get(&process.pid)
+                .is_none_or(|current| current.name != process.name)
+        });
+
+        self.exited
+            .extend(exited.map(|process| ExitedProcess { process, exit_time }));
+
+        let excess = self.exited.len().saturating_sub(MAX_EXITED_PROCESSES);
+        self.exited.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::{MAX_EXITED_PROCESSES, PROCESS_HISTORY_LEN, ProcessData};
+    use crate::collection::processes::ProcessHarvest;
+
+    fn process(pid: usize, name: &str) -> ProcessHarvest {
+        ProcessHarvest {
+            pid: pid as _,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn exited_pids(data: &ProcessData) -> Vec<usize> {
+        data.exited
+            .iter()
+            .map(|exited| exited.process.pid as usize)
+            .collect()
+    }
+
+    #[test]
+    fn exited_processes() {
+        let now = Instant::now();
+        let mut data = ProcessData::default();
+
+        data.ingest(
+            vec![process(1, "init"), process(2, "cron"), process(3, "job")],
+            now,
+        );
+        assert!(data.exited.is_empty());
+
+        // PID 3 is reused by another process.
+        data.ingest(vec![process(1, "init"), process(3, "other")], now);
+        assert_eq!(exited_pids(&data), vec![2, 3]);
+        assert_eq!(data.exited[1].process.name, "job");
+
+        for pid in 10..10 + MAX_EXITED_PROCESSES {
+            data.ingest(vec![process(1, "init"), process(pid, "job")], now);
+        }
+        assert_eq!(data.exited.len(), MAX_EXITED_PROCESSES);
+        assert_eq!(
+            data.exited
+                .back()
+                .map(|exited| exited.process.name.as_str()),
+            Some("job")
+        );
+    }
+
+    #[test]
+    fn process_history() {
+        let now = Instant::now();
+        let mut data = ProcessData::default();
+
+        for cpu in 0..PROCESS_HISTORY_LEN + 5 {
+            let mut job = process(2, "job");
+            job.cpu_usage_percent = cpu as f32;
+            data.ingest(vec![process(1, "init"), job], now);
+        }
+
+        let history = &data.history[&2];
+        assert_eq!(history.len(), PROCESS_HISTORY_LEN);
+        assert_eq!(history.front().unwrap().cpu_usage_percent, 5.0);
+        assert_eq!(
+            history.back().unwrap().cpu_usage_percent,
+            (PROCESS_HISTORY_LEN + 4) as f32
+        );
+
+        // History is forgotten once a process is gone, including if its PID is
+        // reused by another process.
+        data.ingest(vec![process(2, "other")], now);
+        assert!(!data.history.contains_key(&1));
+        assert_eq!(data.history[&2].len(), 1);
+    }
+
+    #[test]
+    fn subtree_pids() {
+        let now = Instant::now();
+        let mut data = ProcessData::default();
+
+        let child = |pid: usize, parent: usize| ProcessHarvest {
+            parent_pid: Some(parent as _),
+            ..process(pid, "job")
+        };
+        data.ingest(
+            vec![
+                process(1, "init"),
+                child(2, 1),
+                child(3, 2),
+                child(4, 3),
+                child(5, 1),
+            ],
+            now,
+        );
+
+        let mut pids = data.subtree_pids(&[2, 9]).into_iter().collect::<Vec<_>>();
+        pids.sort_unstable();
+        assert_eq!(pids, vec![2, 3, 4]);
+
+        assert_eq!(data.subtree_pids(&[1]).len(), 5);
+        assert!(data.subtree_pids(&[]).is_empty());
+    }
 }
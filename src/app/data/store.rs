@@ -1,9 +1,10 @@
 use std::{
+    path::Path,
     time::{Duration, Instant},
     vec::Vec,
 };
 
-use super::{ProcessData, TimeSeriesData};
+use super::{DownsampledData, EventKind, EventLog, ProcessData, TimeSeriesData, history};
 #[cfg(feature = "battery")]
 use crate::collection::batteries;
 use crate::{
@@ -28,6 +29,9 @@ pub struct StoredData {
     // FIXME: (points_rework_v1) we could be able to remove this with some more refactoring.
     pub last_update_time: Instant,
     pub time_series_data: TimeSeriesData,
+    /// Averages of the time series data over longer periods, kept for longer
+    /// than the raw samples.
+    pub downsampled_data: DownsampledData,
     pub network_harvest: NetworkHarvest,
     pub ram_harvest: Option<MemData>,
     pub swap_harvest: Option<MemData>,
@@ -47,6 +51,7 @@ pub struct StoredData {
     pub temp_data: Vec<TempWidgetData>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryData>,
+    pub events: EventLog,
 }
 
 impl Default for StoredData {
@@ -54,6 +59,7 @@ impl Default for StoredData {
         StoredData {
             last_update_time: Instant::now(),
             time_series_data: TimeSeriesData::default(),
+            downsampled_data: DownsampledData::default(),
             network_harvest: NetworkHarvest::default(),
             ram_harvest: None,
             #[cfg(not(target_os = "windows"))]
@@ -71,6 +77,7 @@ impl Default for StoredData {
             arc_harvest: None,
             #[cfg(feature = "gpu")]
             gpu_harvest: Vec::default(),
+            events: EventLog::default(),
         }
     }
 }
@@ -80,6 +87,12 @@ impl StoredData {
         *self = StoredData::default();
     }
 
+    /// The time series data to draw a graph showing `span` of time with. Past
+    /// the age of the raw data, this is downsampled data.
+    pub fn time_series(&self, span: Duration) -> &TimeSeriesData {
+        self.downsampled_data.select(&self.time_series_data, span)
+    }
+
     #[allow(
         clippy::boxed_local,
         reason = "This avoids warnings on certain platforms (e.g. 32-bit)."
@@ -102,9 +115,15 @@ impl StoredData {
         if !settings.use_basic_mode {
             self.time_series_data
                 .add(&data, used_widgets, settings, filters);
+            self.downsampled_data.update(
+                &self.time_series_data,
+                Duration::from_millis(settings.retention_ms),
+            );
         }
 
         if let Some(network) = data.network {
+            self.events
+                .update_interfaces(harvested_time, &network.interfaces);
             self.network_harvest = network;
         }
 
@@ -151,18 +170,35 @@ impl StoredData {
             .unwrap_or_default();
 
         if let Some(disks) = data.disks {
+            self.events.update_mounts(
+                harvested_time,
+                disks.iter().map(|disk| disk.mount_point.as_str()),
+            );
+
             if let Some(io) = data.io {
                 self.eat_disks(disks, io, harvested_time);
             }
         }
 
         if let Some(list_of_processes) = data.list_of_processes {
-            self.process_data.ingest(list_of_processes);
+            self.events.update_processes(
+                harvested_time,
+                &list_of_processes,
+                &settings.process_watch_list,
+            );
+            self.process_data.ingest(list_of_processes, harvested_time);
         }
 
         #[cfg(feature = "battery")]
         {
             if let Some(list_of_batteries) = data.list_of_batteries {
+                self.events.update_batteries(
+                    harvested_time,
+                    list_of_batteries
+                        .iter()
+                        .map(|battery| battery.state.as_str())
+                        .collect(),
+                );
                 self.battery_harvest = list_of_batteries;
             }
         }
@@ -339,10 +375,19 @@ impl DataStore {
         }
     }
 
+    /// Return the latest process data, even if the data is frozen.
+    pub fn live_process_data(&self) -> &ProcessData {
+        &self.main.process_data
+    }
+
     pub fn set_filters(&mut self, filters: DataFilters) {
         self.filters = filters;
     }
 
+    pub fn set_used_widgets(&mut self, used_widgets: UsedWidgets) {
+        self.used_widgets = used_widgets;
+    }
+
     /// Eat data.
     pub fn eat_data(&mut self, data: Box<Data>, settings: &AppConfigFields) {
         self.main
@@ -351,7 +396,37 @@ impl DataStore {
 
     /// Clean data.
     pub fn clean_data(&mut self, max_duration: Duration) {
-        self.main.time_series_data.prune(max_duration);
+        self.main
+            .downsampled_data
+            .prune(&mut self.main.time_series_data, max_duration);
+        self.main.events.prune(max_duration);
+    }
+
+    /// Record an event caused by the user, such as killing a process.
+    pub fn record_event(&mut self, kind: EventKind, description: String) {
+        self.main.events.push(Instant::now(), kind, description);
+    }
+
+    /// Save the time series data to `path`, to be loaded again on the next
+    /// start.
+    pub fn save_history(&self, path: &Path, settings: &AppConfigFields) -> anyhow::Result<()> {
+        history::save_history(
+            path,
+            &self.main.time_series_data,
+            &self.main.downsampled_data,
+            settings,
+        )
+    }
+
+    /// Load time series data previously saved to `path`, within the retention
+    /// period.
+    pub fn load_history(&mut self, path: &Path, settings: &AppConfigFields) -> anyhow::Result<()> {
+        history::load_history(
+            path,
+            &mut self.main.time_series_data,
+            &mut self.main.downsampled_data,
+            settings,
+        )
     }
 
     /// Reset data state.
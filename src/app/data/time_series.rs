@@ -2,10 +2,12 @@
 
 use std::{
     cmp::Ordering,
+    ops::Range,
     time::{Duration, Instant},
     vec::Vec,
 };
 
+use indexmap::IndexMap;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use timeless::data::ChunkedData;
 
@@ -17,6 +19,67 @@ use crate::{
 /// Values corresponding to a time slice.
 pub type Values = ChunkedData<f64>;
 
+/// A value that can be averaged when downsampling.
+trait Average: Copy + Default + Into<f64> {
+    fn from_average(average: f64) -> Self;
+}
+
+impl Average for f64 {
+    fn from_average(average: f64) -> Self {
+        average
+    }
+}
+
+impl Average for f32 {
+    fn from_average(average: f64) -> Self {
+        average as f32
+    }
+}
+
+/// Return the average of the values in `values` whose times are in `range`, if
+/// there are any.
+fn average<F: Average>(
+    values: &ChunkedData<F>, times: &[Instant], range: &Range<Instant>,
+) -> Option<F> {
+    let (sum, count) = values
+        .iter_along_base(times)
+        .rev()
+        .skip_while(|(time, _)| **time >= range.end)
+        .take_while(|(time, _)| **time >= range.start)
+        .fold((0.0, 0), |(sum, count), (_, &value)| {
+            (sum + value.into(), count + 1)
+        });
+
+    (count > 0).then(|| F::from_average(sum / count as f64))
+}
+
+/// Push `value` to `series`, first padding it with gaps so the value lines up
+/// with the last of `len` time values.
+fn push_aligned<F>(series: &mut ChunkedData<F>, len: usize, value: Option<F>) {
+    while series.length() + 1 < len {
+        series.try_push(None);
+    }
+    series.try_push(value);
+}
+
+/// Push the average of every series in `source` over `range` to the matching
+/// series in `target`. Series that are only in `target` get a gap.
+fn push_average_map<F: Average>(
+    target: &mut HashMap<String, ChunkedData<F>>, source: &HashMap<String, ChunkedData<F>>,
+    times: &[Instant], range: &Range<Instant>, len: usize,
+) {
+    for (name, values) in source {
+        let series = target.entry(name.clone()).or_default();
+        push_aligned(series, len, average(values, times, range));
+    }
+
+    for series in target.values_mut() {
+        if series.length() < len {
+            push_aligned(series, len, None);
+        }
+    }
+}
+
 /// Represents time series data in a chunked, deduped manner.
 ///
 /// Properties:
@@ -34,6 +97,9 @@ pub struct TimeSeriesData {
     /// only.
     pub time: Vec<Instant>,
 
+    /// How much time each point is an average over, if this is downsampled.
+    pub resolution: Option<Duration>,
+
     /// Network RX data.
     pub rx: Values,
 
@@ -65,6 +131,9 @@ pub struct TimeSeriesData {
     ///
     /// TODO: Maybe make this use TypedTemperature?
     pub temperature: HashMap<String, ChunkedData<f32>>,
+
+    /// Custom source data, keyed by source name and then by series key.
+    pub custom: HashMap<String, IndexMap<String, Values>>,
 }
 
 impl TimeSeriesData {
@@ -224,6 +293,106 @@ impl TimeSeriesData {
                 }
             }
         }
+
+        if used_widgets.use_custom {
+            let num_points = self.time.len();
+
+            if let Some(custom) = &data.custom {
+                for harvest in custom {
+                    let source = self.custom.entry(harvest.name.clone()).or_default();
+
+                    for (key, value) in &harvest.values {
+                        let series = source.entry(key.clone()).or_insert_with(|| {
+                            // Pad out new series so they line up with the time values.
+                            let mut series = Values::default();
+                            for _ in 1..num_points {
+                                series.try_push(None);
+                            }
+                            series
+                        });
+
+                        // Skip duplicate keys, we only want one value per point.
+                        if series.length() < num_points {
+                            series.push(*value);
+                        }
+                    }
+                }
+            }
+
+            // Anything that didn't get a value this time around gets a gap.
+            for source in self.custom.values_mut() {
+                for series in source.values_mut() {
+                    if series.length() < num_points {
+                        series.try_push(None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add a point with the average of every series in `source` over `range`,
+    /// placed in the middle of the range. This is used to build downsampled
+    /// copies of the raw data; series without values in the range get a gap.
+    pub(super) fn push_average(&mut self, source: &TimeSeriesData, range: Range<Instant>) {
+        let times = &source.time;
+        self.time
+            .push(range.start + range.end.duration_since(range.start) / 2);
+        let len = self.time.len();
+
+        push_aligned(&mut self.rx, len, average(&source.rx, times, &range));
+        push_aligned(&mut self.tx, len, average(&source.tx, times, &range));
+
+        if self.cpu.len() < source.cpu.len() {
+            self.cpu.resize_with(source.cpu.len(), Default::default);
+        }
+        for (index, series) in self.cpu.iter_mut().enumerate() {
+            let value = source
+                .cpu
+                .get(index)
+                .and_then(|values| average(values, times, &range));
+            push_aligned(series, len, value);
+        }
+
+        push_aligned(&mut self.ram, len, average(&source.ram, times, &range));
+        push_aligned(&mut self.swap, len, average(&source.swap, times, &range));
+
+        #[cfg(not(target_os = "windows"))]
+        push_aligned(
+            &mut self.cache_mem,
+            len,
+            average(&source.cache_mem, times, &range),
+        );
+
+        #[cfg(feature = "zfs")]
+        push_aligned(
+            &mut self.arc_mem,
+            len,
+            average(&source.arc_mem, times, &range),
+        );
+
+        #[cfg(feature = "gpu")]
+        push_average_map(&mut self.gpu_mem, &source.gpu_mem, times, &range, len);
+
+        push_average_map(
+            &mut self.temperature,
+            &source.temperature,
+            times,
+            &range,
+            len,
+        );
+
+        for (name, source_series) in &source.custom {
+            let target = self.custom.entry(name.clone()).or_default();
+            for (key, values) in source_series {
+                let series = target.entry(key.clone()).or_default();
+                push_aligned(series, len, average(values, times, &range));
+            }
+        }
+        for series in self.custom.values_mut().flat_map(IndexMap::values_mut) {
+            if series.length() < len {
+                push_aligned(series, len, None);
+            }
+        }
     }
 
     /// Prune any data older than the given duration.
@@ -297,5 +466,20 @@ impl TimeSeriesData {
                 true
             }
         });
+
+        self.custom.retain(|_, source| {
+            source.retain(|_, data| {
+                let _ = data.prune(end);
+
+                if data.no_elements() {
+                    false
+                } else {
+                    data.shrink_to_fit();
+                    true
+                }
+            });
+
+            !source.is_empty()
+        });
     }
 }
@@ -0,0 +1,662 @@
+//! Keybindings, which map key presses (or two-key chords) to actions.
+
+use std::{fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::constants::{HELP_TEXT, HelpSection};
+
+/// The widgets where an action's bindings are active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyScope {
+    /// Active everywhere.
+    Global,
+    Process,
+    Disk,
+    Temperature,
+    /// Active in graph widgets.
+    Graph,
+}
+
+impl KeyScope {
+    /// Whether bindings in both scopes can be active at the same time.
+    fn overlaps(self, other: KeyScope) -> bool {
+        self == other || self == KeyScope::Global || other == KeyScope::Global
+    }
+}
+
+macro_rules! actions {
+    ($($variant:ident => $name:literal, $scope:ident, [$($key:literal),*];)*) => {
+        /// An action that can be bound to a key.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Action {
+            $($variant,)*
+        }
+
+        impl Action {
+            /// Every action, in the order they are listed in documentation.
+            pub const ALL: &[Action] = &[$(Action::$variant,)*];
+
+            /// The name of the action as used in the config file.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $name,)*
+                }
+            }
+
+            /// Where the action's bindings are active.
+            pub fn scope(self) -> KeyScope {
+                match self {
+                    $(Action::$variant => KeyScope::$scope,)*
+                }
+            }
+
+            /// The keys bound to the action if the user does not override them.
+            pub fn default_keys(self) -> &'static [&'static str] {
+                match self {
+                    $(Action::$variant => &[$($key),*],)*
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    Quit => "quit", Global, ["q"];
+    Reset => "reset", Global, ["ctrl-r"];
+    Freeze => "freeze", Global, ["f"];
+    Help => "help", Global, ["?"];
+    Expand => "expand", Global, ["e"];
+    Search => "search", Global, ["/", "ctrl-f"];
+    SelectWidgetLeft => "select_widget_left", Global, ["H", "A", "shift-left", "ctrl-left"];
+    SelectWidgetRight => "select_widget_right", Global, ["L", "D", "shift-right", "ctrl-right"];
+    SelectWidgetUp => "select_widget_up", Global, ["K", "W", "shift-up", "ctrl-up"];
+    SelectWidgetDown => "select_widget_down", Global, ["J", "S", "shift-down", "ctrl-down"];
+    Left => "left", Global, ["left", "alt-h"];
+    Right => "right", Global, ["right", "alt-l"];
+    Up => "up", Global, ["up", "k"];
+    Down => "down", Global, ["down", "j"];
+    SkipToFirst => "skip_to_first", Global, ["g g", "home"];
+    SkipToLast => "skip_to_last", Global, ["G", "end"];
+    PageUp => "page_up", Global, ["pageup"];
+    PageDown => "page_down", Global, ["pagedown"];
+    HalfPageUp => "half_page_up", Global, ["ctrl-u"];
+    HalfPageDown => "half_page_down", Global, ["ctrl-d"];
+    ZoomIn => "zoom_in", Global, ["+"];
+    ZoomOut => "zoom_out", Global, ["-"];
+    ResetZoom => "reset_zoom", Global, ["="];
+    TogglePercentages => "toggle_percentages", Global, ["%"];
+    NextLayout => "next_layout", Global, ["]"];
+    PreviousLayout => "previous_layout", Global, ["["];
+    Layout1 => "layout_1", Global, ["1"];
+    Layout2 => "layout_2", Global, ["2"];
+    Layout3 => "layout_3", Global, ["3"];
+    Layout4 => "layout_4", Global, ["4"];
+    Layout5 => "layout_5", Global, ["5"];
+    Layout6 => "layout_6", Global, ["6"];
+    Layout7 => "layout_7", Global, ["7"];
+    Layout8 => "layout_8", Global, ["8"];
+    Layout9 => "layout_9", Global, ["9"];
+    EditLayout => "edit_layout", Global, ["E"];
+    CycleTheme => "cycle_theme", Global, ["ctrl-t"];
+    ShowEvents => "show_events", Global, ["ctrl-e"];
+    KillProcess => "kill_process", Process, ["d d", "f9", "delete"];
+    KillTree => "kill_tree", Process, ["T"];
+    ChangePriority => "change_priority", Process, ["r"];
+    ChangeAffinity => "change_affinity", Process, ["a"];
+    ToggleSortMenu => "toggle_sort_menu", Process, ["s", "f6"];
+    ToggleTreeMode => "toggle_tree_mode", Process, ["t", "f5"];
+    ToggleBranch => "toggle_branch", Process, ["space"];
+    ToggleGrouping => "toggle_grouping", Process, ["tab"];
+    CycleGroupKey => "cycle_group_key", Process, ["b"];
+    ToggleCommand => "toggle_command", Process, ["P"];
+    ToggleKernelThreads => "toggle_kernel_threads", Process, ["z"];
+    ToggleExited => "toggle_exited", Process, ["X"];
+    ToggleFollow => "toggle_follow", Process, ["F"];
+    ToggleTreeFilter => "toggle_tree_filter", Process, ["o"];
+    ChooseColumns => "choose_columns", Process, ["O"];
+    ToggleHistory => "toggle_history", Process, ["h"];
+    ToggleMark => "toggle_mark", Process, ["v"];
+    MarkRange => "mark_range", Process, ["V"];
+    MarkAll => "mark_all", Process, ["ctrl-a"];
+    ClearMarks => "clear_marks", Process, ["U"];
+    AddSortKey => "add_sort_key", Process, ["ctrl-s"];
+    InvertSort => "invert_sort", Process, ["I"];
+    SortByCpu => "sort_by_cpu", Process, ["c"];
+    SortByMemory => "sort_by_memory", Process, ["m"];
+    SortByPid => "sort_by_pid", Process, ["p"];
+    SortByName => "sort_by_name", Process, ["n"];
+    SortByGpu => "sort_by_gpu", Process, ["C"];
+    SortByGpuMemory => "sort_by_gpu_memory", Process, ["M"];
+    TempSortBySensor => "temp_sort_by_sensor", Temperature, ["s"];
+    TempSortByValue => "temp_sort_by_value", Temperature, ["t"];
+    TempAddSortKey => "temp_add_sort_key", Temperature, ["ctrl-s"];
+    DiskSortByName => "disk_sort_by_name", Disk, ["d"];
+    DiskSortByMount => "disk_sort_by_mount", Disk, ["m"];
+    DiskSortByUsed => "disk_sort_by_used", Disk, ["u"];
+    DiskSortByFree => "disk_sort_by_free", Disk, ["n"];
+    DiskSortByTotal => "disk_sort_by_total", Disk, ["t"];
+    DiskSortByUsedPercent => "disk_sort_by_used_percent", Disk, ["p"];
+    DiskSortByRead => "disk_sort_by_read", Disk, ["r"];
+    DiskSortByWrite => "disk_sort_by_write", Disk, ["w"];
+    DiskAddSortKey => "disk_add_sort_key", Disk, ["ctrl-s"];
+    ToggleInspect => "toggle_inspect", Graph, ["i"];
+    PanBack => "pan_back", Graph, [","];
+    PanForward => "pan_forward", Graph, ["."];
+    JumpToLive => "jump_to_live", Graph, ["0"];
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::ALL
+            .iter()
+            .find(|action| action.name() == s)
+            .copied()
+            .ok_or_else(|| format!("'{s}' is not a valid action"))
+    }
+}
+
+/// A single key press, along with any modifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers.intersection(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+
+        // Shift is already part of a character (e.g. `A` vs `a`), so drop it to avoid
+        // depending on whether the terminal reports it.
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+
+        Self { code, modifiers }
+    }
+
+    /// Whether this is a character typed without ctrl or alt.
+    fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(c) if c != ' ') && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(event: KeyEvent) -> Self {
+        KeyPress::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyPress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const MODIFIERS: [(&str, KeyModifiers); 4] = [
+            ("ctrl", KeyModifiers::CONTROL),
+            ("control", KeyModifiers::CONTROL),
+            ("alt", KeyModifiers::ALT),
+            ("shift", KeyModifiers::SHIFT),
+        ];
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+
+        // Strip modifier prefixes like `ctrl-` or `ctrl+`, as long as something is left over.
+        'outer: loop {
+            for (name, modifier) in MODIFIERS {
+                if let Some(prefix) = rest.get(..name.len()) {
+                    let remaining = &rest[name.len()..];
+                    if prefix.eq_ignore_ascii_case(name)
+                        && remaining.len() > 1
+                        && remaining.starts_with(['-', '+'])
+                    {
+                        modifiers |= modifier;
+                        rest = &remaining[1..];
+                        continue 'outer;
+                    }
+                }
+            }
+
+            break;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdown" | "pgdn" => KeyCode::PageDown,
+                "esc" | "escape" => KeyCode::Esc,
+                other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("'{s}' is not a valid key")),
+                },
+            },
+        };
+
+        Ok(KeyPress::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDown"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// One key press, or a chord of two key presses (e.g. `g g`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeySequence(Vec<KeyPress>);
+
+impl KeySequence {
+    /// Whether `self` is the same as, or starts with, `other`.
+    fn starts_with(&self, other: &KeySequence) -> bool {
+        self.0.starts_with(&other.0)
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<KeyPress>, _>>()?;
+
+        match keys.len() {
+            1 | 2 => Ok(KeySequence(keys)),
+            0 => Err("a binding cannot be empty".to_string()),
+            _ => Err(format!(
+                "'{s}' has too many keys, at most two are supported"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Chords of plain characters read better without spaces, e.g. `gg`.
+        let separator = if self.0.iter().all(KeyPress::is_plain_char) {
+            ""
+        } else {
+            " "
+        };
+
+        for (itx, key) in self.0.iter().enumerate() {
+            if itx > 0 {
+                write!(f, "{separator}")?;
+            }
+            write!(f, "{key}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The result of looking up a key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyLookup {
+    /// The key press completed a binding.
+    Action(Action),
+
+    /// The key press started a chord, so wait for the next key.
+    Pending,
+
+    /// Nothing is bound to the key press.
+    Unbound,
+}
+
+/// The active set of keybindings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keybindings {
+    bindings: Vec<(KeySequence, Action)>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .flat_map(|&action| {
+                action
+                    .default_keys()
+                    .iter()
+                    .filter_map(move |key| Some((key.parse().ok()?, action)))
+            })
+            .collect();
+
+        Keybindings { bindings }
+    }
+}
+
+impl Keybindings {
+    /// Create keybindings from the defaults, with the bindings of each action in `overrides`
+    /// replaced. An action with no keys is unbound.
+    ///
+    /// Returns an error if any two bindings conflict.
+    pub fn with_overrides(
+        overrides: impl IntoIterator<Item = (Action, Vec<KeySequence>)>,
+    ) -> Result<Self, String> {
+        let mut bindings = Keybindings::default().bindings;
+
+        for (action, keys) in overrides {
+            bindings.retain(|(_, bound)| *bound != action);
+            for key in keys {
+                if !bindings.contains(&(key.clone(), action)) {
+                    bindings.push((key, action));
+                }
+            }
+        }
+
+        let keybindings = Keybindings { bindings };
+        keybindings.check_conflicts()?;
+
+        Ok(keybindings)
+    }
+
+    /// Check that no binding shadows another, either by being the same or by being the
+    /// start of a chord.
+    fn check_conflicts(&self) -> Result<(), String> {
+        for (itx, (a_keys, a_action)) in self.bindings.iter().enumerate() {
+            for (b_keys, b_action) in &self.bindings[itx + 1..] {
+                if a_action.scope().overlaps(b_action.scope())
+                    && (a_keys.starts_with(b_keys) || b_keys.starts_with(a_keys))
+                {
+                    return Err(format!(
+                        "'{a_keys}' for '{}' conflicts with '{b_keys}' for '{}'",
+                        a_action.name(),
+                        b_action.name()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Look up a key press, given the first key of a chord if one is pending.
+    pub fn lookup(&self, pending: Option<KeyPress>, key: KeyPress, scope: KeyScope) -> KeyLookup {
+        let active = self
+            .bindings
+            .iter()
+            .filter(|(_, action)| action.scope() == KeyScope::Global || action.scope() == scope);
+
+        if let Some(first) = pending {
+            let chord = [first, key];
+            if let Some((_, action)) = active.clone().find(|(keys, _)| keys.0 == chord) {
+                return KeyLookup::Action(*action);
+            }
+        }
+
+        let mut lookup = KeyLookup::Unbound;
+        for (keys, action) in active {
+            match keys.0.as_slice() {
+                [only] if *only == key => return KeyLookup::Action(*action),
+                [first, _] if *first == key => lookup = KeyLookup::Pending,
+                _ => {}
+            }
+        }
+
+        lookup
+    }
+
+    /// The keys bound to an action.
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = &KeySequence> {
+        self.bindings
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(keys, _)| keys)
+    }
+
+    /// Generate the help text, using the keys from the active bindings.
+    pub fn help_text(&self) -> Vec<Vec<String>> {
+        HELP_TEXT
+            .iter()
+            .map(|section| match section {
+                HelpSection::Text(lines) => lines.iter().map(|line| line.to_string()).collect(),
+                HelpSection::Bindings(header, lines) => {
+                    let mut text = vec![header.to_string()];
+                    text.extend(lines.iter().filter_map(|line| {
+                        let keys = line
+                            .actions
+                            .iter()
+                            .flat_map(|&action| self.keys(action).map(ToString::to_string))
+                            .chain(line.fixed.iter().map(|key| key.to_string()))
+                            .collect::<Vec<_>>();
+
+                        if keys.is_empty() {
+                            None
+                        } else {
+                            Some(format!("{:<23} {}", keys.join(", "), line.description))
+                        }
+                    }));
+
+                    text
+                }
+            })
+            .collect()
+    }
+}
+
+/// A line of help text for one or more bindings.
+pub struct HelpLine {
+    /// The actions whose keys are listed.
+    pub actions: &'static [Action],
+
+    /// Keys that are not configurable, listed after the bound ones.
+    pub fixed: &'static [&'static str],
+
+    pub description: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> KeyPress {
+        s.parse().unwrap()
+    }
+
+    fn seq(s: &str) -> KeySequence {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(
+            key("q"),
+            KeyPress::new(KeyCode::Char('q'), KeyModifiers::NONE)
+        );
+        assert_eq!(key("Q"), key("shift-q"));
+        assert_eq!(
+            key("Ctrl+r"),
+            KeyPress::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            key("ctrl--"),
+            KeyPress::new(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            key("-"),
+            KeyPress::new(KeyCode::Char('-'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            key("alt-shift-left"),
+            KeyPress::new(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        );
+        assert_eq!(key("F9"), KeyPress::new(KeyCode::F(9), KeyModifiers::NONE));
+        assert_eq!(key("shift-tab"), key("backtab"));
+
+        assert!("ctrl-".parse::<KeyPress>().is_err());
+        assert!("f13".parse::<KeyPress>().is_err());
+        assert!("hello".parse::<KeyPress>().is_err());
+        assert!("a b c".parse::<KeySequence>().is_err());
+        assert!("".parse::<KeySequence>().is_err());
+    }
+
+    #[test]
+    fn display_keys() {
+        assert_eq!(seq("g g").to_string(), "gg");
+        assert_eq!(seq("ctrl-x g").to_string(), "Ctrl-x g");
+        assert_eq!(seq("shift-left").to_string(), "Shift-Left");
+        assert_eq!(seq("space").to_string(), "Space");
+        assert_eq!(seq("pagedown").to_string(), "PgDown");
+    }
+
+    #[test]
+    fn terminal_events_match_bindings() {
+        // Terminals report shifted characters with or without the shift modifier.
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(KeyPress::from(event), key("G"));
+
+        let event = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert_eq!(KeyPress::from(event), key("?"));
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        let keybindings = Keybindings::default();
+
+        for action in Action::ALL {
+            assert_eq!(
+                keybindings.keys(*action).count(),
+                action.default_keys().len(),
+                "every default key for '{}' should parse",
+                action.name()
+            );
+        }
+
+        keybindings.check_conflicts().unwrap();
+    }
+
+    #[test]
+    fn action_names_are_unique() {
+        for action in Action::ALL {
+            assert_eq!(action.name().parse::<Action>(), Ok(*action));
+        }
+    }
+
+    #[test]
+    fn lookup_chords() {
+        let keybindings = Keybindings::default();
+
+        assert_eq!(
+            keybindings.lookup(None, key("g"), KeyScope::Global),
+            KeyLookup::Pending
+        );
+        assert_eq!(
+            keybindings.lookup(Some(key("g")), key("g"), KeyScope::Global),
+            KeyLookup::Action(Action::SkipToFirst)
+        );
+
+        // A key that doesn't finish the chord is looked up on its own.
+        assert_eq!(
+            keybindings.lookup(Some(key("g")), key("G"), KeyScope::Global),
+            KeyLookup::Action(Action::SkipToLast)
+        );
+
+        // Scoped bindings only apply to their widgets.
+        assert_eq!(
+            keybindings.lookup(None, key("d"), KeyScope::Process),
+            KeyLookup::Pending
+        );
+        assert_eq!(
+            keybindings.lookup(None, key("d"), KeyScope::Disk),
+            KeyLookup::Action(Action::DiskSortByName)
+        );
+        assert_eq!(
+            keybindings.lookup(None, key("d"), KeyScope::Global),
+            KeyLookup::Unbound
+        );
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let keybindings =
+            Keybindings::with_overrides([(Action::Quit, vec![seq("ctrl-q")])]).unwrap();
+
+        assert_eq!(
+            keybindings.lookup(None, key("q"), KeyScope::Global),
+            KeyLookup::Unbound
+        );
+        assert_eq!(
+            keybindings.lookup(None, key("ctrl-q"), KeyScope::Global),
+            KeyLookup::Action(Action::Quit)
+        );
+
+        let keybindings = Keybindings::with_overrides([(Action::Help, vec![])]).unwrap();
+        assert_eq!(keybindings.keys(Action::Help).count(), 0);
+    }
+
+    #[test]
+    fn conflicting_overrides() {
+        // The same key as another global action.
+        assert!(Keybindings::with_overrides([(Action::Freeze, vec![seq("q")])]).is_err());
+
+        // The start of a chord.
+        assert!(Keybindings::with_overrides([(Action::Freeze, vec![seq("g")])]).is_err());
+
+        // A global key that is also used by a widget.
+        assert!(Keybindings::with_overrides([(Action::Freeze, vec![seq("w")])]).is_err());
+
+        // Different widgets can share keys.
+        assert!(Keybindings::with_overrides([(Action::TempSortBySensor, vec![seq("w")])]).is_ok());
+    }
+
+    #[test]
+    fn help_text_uses_bindings() {
+        let keybindings =
+            Keybindings::with_overrides([(Action::Freeze, vec![seq("ctrl-p"), seq("x")])]).unwrap();
+        let help_text = keybindings.help_text();
+
+        assert!(
+            help_text[1]
+                .iter()
+                .any(|line| line.starts_with("Ctrl-p, x ") && line.contains("Freeze"))
+        );
+    }
+}
@@ -2,7 +2,13 @@ use std::collections::BTreeMap;
 
 use tui::layout::Constraint;
 
-use crate::{constants::DEFAULT_WIDGET_ID, options::OptionError};
+use crate::{
+    constants::DEFAULT_WIDGET_ID,
+    options::{
+        OptionError,
+        config::layout::{LayoutSection, Row},
+    },
+};
 
 // Represents a start and end coordinate in some dimension.
 type LineSegment = (u16, u16);
@@ -21,6 +27,45 @@ pub struct BottomLayout {
     pub total_row_height_ratio: u16,
 }
 
+/// The set of named layouts, along with which one is currently displayed.
+#[derive(Clone, Debug, Default)]
+pub struct LayoutTabs {
+    /// The names of each layout, in the order they were defined.
+    pub names: Vec<String>,
+
+    /// The index of the currently displayed layout.
+    pub current: usize,
+
+    /// The last selected widget ID of each layout, restored when switching back.
+    pub selected_widgets: Vec<u64>,
+
+    /// The click locations of each tab in the tab bar.
+    pub tab_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+
+    /// The config section and rows of each layout, used by the layout editor.
+    /// This is empty in basic mode, which can't be edited.
+    pub sources: Vec<(LayoutSection, Vec<Row>)>,
+}
+
+impl LayoutTabs {
+    pub fn new(
+        names: Vec<String>, selected_widgets: Vec<u64>, sources: Vec<(LayoutSection, Vec<Row>)>,
+    ) -> Self {
+        Self {
+            names,
+            current: 0,
+            selected_widgets,
+            tab_click_locs: None,
+            sources,
+        }
+    }
+
+    /// Whether there is more than one layout to switch between.
+    pub fn is_enabled(&self) -> bool {
+        self.names.len() > 1
+    }
+}
+
 trait Ratio {
     fn ratio(&self) -> u16;
 }
@@ -857,6 +902,9 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection. (x, y)
     pub bottom_right_corner: Option<(u16, u16)>,
 
+    /// The name of the source to display, for custom widgets.
+    pub custom_source: Option<String>,
+
     /// TODO: REMOVE THIS LATER. This is temporary code to bridge the
     /// old layout system with a newer system later.
     ratio_override: Option<u16>,
@@ -875,6 +923,7 @@ impl BottomWidget {
             parent_reflector: None,
             top_left_corner: None,
             bottom_right_corner: None,
+            custom_source: None,
             ratio_override: None,
         }
     }
@@ -927,6 +976,11 @@ impl BottomWidget {
         self.parent_reflector = parent_reflector;
         self
     }
+
+    pub(crate) fn custom_source(mut self, custom_source: Option<String>) -> Self {
+        self.custom_source = custom_source;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
@@ -948,6 +1002,8 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Custom,
+    CpuHeatmap,
 }
 
 impl BottomWidgetType {
@@ -958,7 +1014,7 @@ impl BottomWidgetType {
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem | TempGraph)
+        matches!(self, Cpu | Net | Mem | TempGraph | Custom)
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -972,6 +1028,8 @@ impl BottomWidgetType {
             Disk => "Disks",
             Battery => "Battery",
             TempGraph => "Temperature",
+            Custom => "Custom",
+            CpuHeatmap => "CPU Heatmap",
             _ => "",
         }
     }
@@ -984,12 +1042,14 @@ impl std::str::FromStr for BottomWidgetType {
         let lower_case = s.to_lowercase();
         match lower_case.as_str() {
             "cpu" => Ok(BottomWidgetType::Cpu),
+            "cpu_heatmap" => Ok(BottomWidgetType::CpuHeatmap),
             "mem" | "memory" => Ok(BottomWidgetType::Mem),
             "net" | "network" => Ok(BottomWidgetType::Net),
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "temp_graph" | "temperature_graph" => Ok(BottomWidgetType::TempGraph),
             "disk" => Ok(BottomWidgetType::Disk),
+            "custom" => Ok(BottomWidgetType::Custom),
             "empty" => Ok(BottomWidgetType::Empty),
             #[cfg(feature = "battery")]
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
@@ -1003,6 +1063,8 @@ Supported widget names:
 +--------------------------------+
 |               cpu              |
 +--------------------------------+
+|           cpu_heatmap          |
++--------------------------------+
 |           mem, memory          |
 +--------------------------------+
 |          net, network          |
@@ -1017,6 +1079,8 @@ Supported widget names:
 +--------------------------------+
 |          batt, battery         |
 +--------------------------------+
+|             custom             |
++--------------------------------+
 |              empty             |
 +--------------------------------+
                 ",
@@ -1031,6 +1095,8 @@ Supported widget names:
 +--------------------------------+
 |               cpu              |
 +--------------------------------+
+|           cpu_heatmap          |
++--------------------------------+
 |           mem, memory          |
 +--------------------------------+
 |          net, network          |
@@ -1043,6 +1109,8 @@ Supported widget names:
 +--------------------------------+
 |              disk              |
 +--------------------------------+
+|             custom             |
++--------------------------------+
 |              empty             |
 +--------------------------------+
                 ",
@@ -1065,4 +1133,5 @@ pub struct UsedWidgets {
     pub use_temp: bool,
     pub use_temp_graph: bool,
     pub use_battery: bool,
+    pub use_custom: bool,
 }
@@ -0,0 +1,328 @@
+//! Monitoring a command launched by bottom, or processes it was attached to,
+//! so that a summary of their usage can be shown once they exit.
+
+use std::{
+    process::{Child, Command, ExitStatus, Stdio},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use itertools::Itertools;
+
+use super::data::ProcessData;
+use crate::{
+    collection::processes::{Bytes, Pid},
+    utils::{
+        data_units::{get_binary_bytes, get_decimal_bytes},
+        int_hash::IntHashMap,
+    },
+};
+
+/// The processes being monitored, along with their peak and total usage so far.
+/// A launched command that is still running when this is dropped is killed.
+#[derive(Debug)]
+pub struct Monitor {
+    /// The command bottom launched, if any. Otherwise, bottom was attached to
+    /// already running processes.
+    child: Option<Child>,
+
+    /// The processes whose trees are monitored.
+    roots: Vec<Pid>,
+
+    /// What is being monitored, as shown in the summary.
+    description: String,
+
+    start: Instant,
+    end: Option<Instant>,
+    status: Option<ExitStatus>,
+
+    /// The peak CPU usage of the whole tree, summed over its processes.
+    peak_cpu_usage_percent: f32,
+
+    /// The peak resident memory of the whole tree, summed over its processes.
+    peak_mem_usage: Bytes,
+
+    /// The most bytes read and written by each process in the tree, including
+    /// ones that have since exited.
+    io: IntHashMap<Pid, Bytes>,
+}
+
+impl Monitor {
+    fn new(child: Option<Child>, roots: Vec<Pid>, description: String) -> Self {
+        Self {
+            child,
+            roots,
+            description,
+            start: Instant::now(),
+            end: None,
+            status: None,
+            peak_cpu_usage_percent: 0.0,
+            peak_mem_usage: 0,
+            io: IntHashMap::default(),
+        }
+    }
+
+    /// Launch `command`, with its arguments. As bottom takes over the terminal,
+    /// the command's input and output are discarded.
+    pub fn launch(command: &[String]) -> anyhow::Result<Self> {
+        let (program, args) = command.split_first().context("No command was given")?;
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Could not launch '{program}'"))?;
+
+        let pid = child.id() as Pid;
+        Ok(Self::new(
+            Some(child),
+            vec![pid],
+            format!("'{}'", command.join(" ")),
+        ))
+    }
+
+    /// Monitor processes that are already running. Fails if none of them are.
+    pub fn attach(pids: Vec<Pid>) -> anyhow::Result<Self> {
+        if !pids.iter().any(|&pid| is_running(pid)) {
+            match pids.as_slice() {
+                [pid] => anyhow::bail!("No process with PID {pid} is running"),
+                _ => anyhow::bail!("None of the given PIDs are running"),
+            }
+        }
+
+        let description = match pids.as_slice() {
+            [pid] => format!("PID {pid}"),
+            pids => format!("PIDs {}", pids.iter().map(Pid::to_string).join(", ")),
+        };
+
+        Ok(Self::new(None, pids, description))
+    }
+
+    /// The processes whose trees are monitored.
+    pub fn roots(&self) -> &[Pid] {
+        &self.roots
+    }
+
+    /// Whether the monitored command or processes have exited.
+    pub fn is_finished(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Record the usage of the monitored processes and check whether they have
+    /// exited. Returns whether they have.
+    pub fn update(&mut self, process_data: &ProcessData) -> bool {
+        if self.is_finished() {
+            return true;
+        }
+
+        let pids = process_data.subtree_pids(&self.roots);
+        let mut cpu_usage_percent = 0.0;
+        let mut mem_usage = 0;
+
+        for process in pids
+            .iter()
+            .filter_map(|pid| process_data.process_harvest.get(pid))
+        {
+            cpu_usage_percent += process.cpu_usage_percent;
+            mem_usage += process.mem_usage;
+
+            let io = self.io.entry(process.pid).or_default();
+            *io = (*io).max(process.total_read + process.total_write);
+        }
+
+        self.peak_cpu_usage_percent = self.peak_cpu_usage_percent.max(cpu_usage_percent);
+        self.peak_mem_usage = self.peak_mem_usage.max(mem_usage);
+
+        let is_finished = match &mut self.child {
+            Some(child) => match child.try_wait() {
+                Ok(Some(status)) => {
+                    self.status = Some(status);
+                    true
+                }
+                Ok(None) => false,
+                Err(_) => true,
+            },
+            None => pids.is_empty(),
+        };
+        if is_finished {
+            self.end = Some(Instant::now());
+        }
+
+        is_finished
+    }
+
+    /// Kill the launched command if it is still running, and wait for it so it
+    /// is recorded as having exited. Processes that were attached to are left
+    /// alone.
+    pub fn stop(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+
+        if let Some(child) = &mut self.child {
+            if let Ok(None) = child.try_wait() {
+                let _ = child.kill();
+            }
+            self.status = child.wait().ok();
+            self.end = Some(Instant::now());
+        }
+    }
+
+    /// A summary of the usage of the monitored processes, to be shown once
+    /// bottom exits.
+    pub fn summary(&self) -> String {
+        let outcome = match (&self.end, &self.status) {
+            (Some(_), Some(status)) => format!("exited with {status}"),
+            (Some(_), None) => "exited".to_string(),
+            (None, _) => "is still running".to_string(),
+        };
+        let wall_time = self.end.unwrap_or_else(Instant::now) - self.start;
+        let wall_time = Duration::from_millis(wall_time.as_millis() as u64);
+
+        let (mem, mem_unit) = get_binary_bytes(self.peak_mem_usage);
+        let (io, io_unit) = get_decimal_bytes(self.io.values().sum());
+
+        format!(
+            "{} {outcome}\n  \
+            Wall time: {}\n  \
+            Peak CPU:  {:.1}%\n  \
+            Peak RSS:  {mem:.1}{mem_unit}\n  \
+            Total I/O: {io:.1}{io_unit}",
+            self.description,
+            humantime::format_duration(wall_time),
+            self.peak_cpu_usage_percent,
+        )
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Whether a process with the given PID is running.
+fn is_running(pid: Pid) -> bool {
+    cfg_select! {
+        unix => {
+            crate::utils::process_killer::is_process_alive(pid)
+        }
+        _ => {
+            use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+            let mut system = System::new();
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[sysinfo::Pid::from_u32(pid as u32)]),
+                true,
+                ProcessRefreshKind::nothing(),
+            ) > 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::processes::ProcessHarvest;
+
+    fn process_data(processes: &[(Pid, Option<Pid>, f32, Bytes, Bytes)]) -> ProcessData {
+        let mut data = ProcessData::default();
+
+        for &(pid, parent_pid, cpu_usage_percent, mem_usage, total_read) in processes {
+            data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    parent_pid,
+                    cpu_usage_percent,
+                    mem_usage,
+                    total_read,
+                    ..Default::default()
+                },
+            );
+            if let Some(parent_pid) = parent_pid {
+                data.process_parent_mapping
+                    .entry(parent_pid)
+                    .or_default()
+                    .push(pid);
+            }
+        }
+
+        data
+    }
+
+    #[test]
+    fn attached_monitor() {
+        let mut monitor = Monitor::new(None, vec![2], "PID 2".to_string());
+
+        let data = process_data(&[
+            (1, None, 50.0, 1000, 0),
+            (2, Some(1), 10.0, 1024, 100),
+            (3, Some(2), 20.0, 1024, 200),
+        ]);
+        assert!(!monitor.update(&data));
+
+        // Only the tree of the attached process counts, and the peaks are kept.
+        let data = process_data(&[(1, None, 50.0, 1000, 0), (2, Some(1), 5.0, 512, 150)]);
+        assert!(!monitor.update(&data));
+        assert_eq!(monitor.peak_cpu_usage_percent, 30.0);
+        assert_eq!(monitor.peak_mem_usage, 2048);
+
+        let data = process_data(&[(1, None, 50.0, 1000, 0)]);
+        assert!(monitor.update(&data));
+        assert!(monitor.is_finished());
+
+        let summary = monitor.summary();
+        assert!(summary.starts_with("PID 2 exited\n"), "{summary}");
+        assert!(summary.contains("Peak CPU:  30.0%"), "{summary}");
+        assert!(summary.contains("Peak RSS:  2.0KiB"), "{summary}");
+        assert!(summary.contains("Total I/O: 350.0B"), "{summary}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launched_monitor() {
+        let mut monitor = Monitor::launch(&["true".to_string()]).unwrap();
+        assert_eq!(monitor.roots().len(), 1);
+
+        let data = ProcessData::default();
+        let start = Instant::now();
+        while !monitor.update(&data) {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(
+            monitor
+                .summary()
+                .starts_with("'true' exited with exit status: 0")
+        );
+        assert!(Monitor::launch(&["/nonexistent/command".to_string()]).is_err());
+        assert!(Monitor::launch(&[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stopped_monitor() {
+        let mut monitor = Monitor::launch(&["sleep".to_string(), "30".to_string()]).unwrap();
+        let pid = monitor.roots()[0];
+        assert!(!monitor.update(&ProcessData::default()));
+
+        monitor.stop();
+        assert!(monitor.is_finished());
+        assert!(!is_running(pid));
+        assert!(
+            monitor
+                .summary()
+                .starts_with("'sleep 30' exited with signal")
+        );
+    }
+
+    #[test]
+    fn attach_running() {
+        assert!(Monitor::attach(vec![std::process::id() as Pid]).is_ok());
+        assert!(Monitor::attach(vec![Pid::MAX]).is_err());
+        assert!(Monitor::attach(vec![Pid::MAX, std::process::id() as Pid]).is_ok());
+    }
+}
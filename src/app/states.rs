@@ -2,16 +2,17 @@ use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
     app::layout_manager::BottomWidgetType,
-    constants,
     utils::input::InputFieldState,
     widgets::{
-        BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState, NetWidgetState,
-        ProcWidgetState, TempGraphWidgetState, TempWidgetState, query::ProcessQuery,
+        BatteryWidgetState, CpuHeatmapWidgetState, CpuWidgetState, CustomWidgetState,
+        DiskTableWidget, MemWidgetState, NetWidgetState, ProcWidgetState, TempGraphWidgetState,
+        TempWidgetState, query::ProcessQuery,
     },
 };
 
 pub struct AppWidgetStates {
     pub cpu_state: CpuState,
+    pub cpu_heatmap_state: CpuHeatmapStates,
     pub mem_state: MemState,
     pub net_state: NetState,
     pub proc_state: ProcState,
@@ -19,6 +20,7 @@ pub struct AppWidgetStates {
     pub temp_graph_state: TempGraphStates,
     pub disk_state: DiskState,
     pub battery_state: AppBatteryState,
+    pub custom_state: CustomStates,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
 }
 
@@ -35,22 +37,24 @@ pub struct AppHelpDialogState {
     pub index_shortcuts: Vec<u16>,
     is_searching: bool,
     pub search_input_state: InputFieldState,
+
+    /// The help text, with each section's first line being its header.
+    pub help_text: Vec<Vec<String>>,
 }
 
-impl Default for AppHelpDialogState {
-    fn default() -> Self {
+impl AppHelpDialogState {
+    pub fn new(help_text: Vec<Vec<String>>) -> Self {
         AppHelpDialogState {
             is_showing_help: false,
             height: 0,
             scroll_state: ParagraphScrollState::default(),
-            index_shortcuts: vec![0; constants::HELP_TEXT.len()],
+            index_shortcuts: vec![0; help_text.len()],
             is_searching: false,
             search_input_state: InputFieldState::default(),
+            help_text,
         }
     }
-}
 
-impl AppHelpDialogState {
     pub fn is_searching(&self) -> bool {
         self.is_searching
     }
@@ -192,6 +196,34 @@ impl TempGraphStates {
     }
 }
 
+pub struct CpuHeatmapStates {
+    pub widget_states: HashMap<u64, CpuHeatmapWidgetState>,
+}
+
+impl CpuHeatmapStates {
+    pub fn init(widget_states: HashMap<u64, CpuHeatmapWidgetState>) -> Self {
+        CpuHeatmapStates { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CpuHeatmapWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+}
+
+pub struct CustomStates {
+    pub widget_states: HashMap<u64, CustomWidgetState>,
+}
+
+impl CustomStates {
+    pub fn init(widget_states: HashMap<u64, CustomWidgetState>) -> Self {
+        CustomStates { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CustomWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+}
+
 pub struct DiskState {
     pub widget_states: HashMap<u64, DiskTableWidget>,
 }
@@ -13,7 +13,7 @@ use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Flex, Layout, Rect},
     symbols::Marker,
-    text::Span,
+    text::{Line, Span},
     widgets::Paragraph,
 };
 
@@ -22,7 +22,6 @@ use crate::{
         App,
         layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
     },
-    constants::*,
     options::config::style::Styles,
 };
 
@@ -36,17 +35,17 @@ pub struct Painter {
     /// Used to know whether to invalidate things.
     previous_width: u16,
 
-    /// The layout.
-    layout: BottomLayout,
+    /// The layouts, one for each named layout in [`App::layout_tabs`].
+    layouts: Vec<BottomLayout>,
 }
 
 impl Painter {
-    pub fn init(layout: BottomLayout, styling: Styles) -> anyhow::Result<Self> {
+    pub fn init(layouts: Vec<BottomLayout>, styling: Styles) -> anyhow::Result<Self> {
         let painter = Painter {
             styles: styling,
             previous_height: 0,
             previous_width: 0,
-            layout,
+            layouts,
         };
 
         Ok(painter)
@@ -70,12 +69,26 @@ impl Painter {
         }
     }
 
-    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, draw_loc: Rect) {
-        f.render_widget(
-            Paragraph::new(Span::styled(
+    fn draw_status_line(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let mut spans = Vec::with_capacity(3);
+        if app_state.data_store.is_frozen() {
+            spans.push(Span::styled(
                 "Frozen, press 'f' to unfreeze",
                 self.styles.selected_text_style,
-            )),
+            ));
+        }
+        if let Some(status_error) = &app_state.status_error {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                status_error.as_str(),
+                self.styles.invalid_query_style,
+            ));
+        }
+
+        f.render_widget(
+            Paragraph::new(Line::from(spans)),
             Layout::default()
                 .horizontal_margin(1)
                 .constraints([Constraint::Length(1)])
@@ -88,16 +101,21 @@ impl Painter {
     ) -> Result<(), B::Error> {
         use BottomWidgetType::*;
 
+        if let Some(styles) = app_state.new_styles.take() {
+            self.styles = styles;
+        }
+
         terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.data_store.is_frozen() {
-                // TODO: Remove built-in cache?
-                let split_loc = Layout::default()
-                    .constraints([Constraint::Min(0), Constraint::Length(1)])
-                    .split(f.area());
-                (split_loc[0], Some(split_loc[1]))
-            } else {
-                (f.area(), None)
-            };
+            let (terminal_size, status_draw_loc) =
+                if app_state.data_store.is_frozen() || app_state.status_error.is_some() {
+                    // TODO: Remove built-in cache?
+                    let split_loc = Layout::default()
+                        .constraints([Constraint::Min(0), Constraint::Length(1)])
+                        .split(f.area());
+                    (split_loc[0], Some(split_loc[1]))
+                } else {
+                    (f.area(), None)
+                };
             let terminal_height = terminal_size.height;
             let terminal_width = terminal_size.width;
 
@@ -126,6 +144,9 @@ impl Painter {
                 for battery_widget in app_state.states.battery_state.widget_states.values_mut() {
                     battery_widget.tab_click_locs = None;
                 }
+
+                // Reset layout tab locations...
+                app_state.layout_tabs.tab_click_locs = None;
             }
 
             // TODO: Make drawing dialog generic.
@@ -134,7 +155,8 @@ impl Painter {
                 f.buffer_mut()
                     .set_style(area, self.styles.general_widget_style);
 
-                let gen_help_len = GENERAL_HELP_TEXT.len() as u16 + 3;
+                let help_text = &app_state.help_dialog_state.help_text;
+                let gen_help_len = help_text.get(1).map_or(0, Vec::len) as u16 + 3;
                 let border_len = terminal_height.saturating_sub(gen_help_len) / 2;
                 let [_, vertical_dialog_chunk, _] = Layout::default()
                     .direction(Direction::Vertical)
@@ -146,41 +168,26 @@ impl Painter {
                     .areas(terminal_size);
 
                 // An approximate proxy for the max line length to use.
-                const MAX_TEXT_LENGTH: u16 = const {
-                    let mut max = 0;
-
-                    let mut i = 0;
-                    while i < HELP_TEXT.len() {
-                        let section = HELP_TEXT[i];
-                        let mut j = 0;
-                        while j < section.len() {
-                            let line = section[j];
-                            if line.len() > max {
-                                max = line.len();
-                            }
-
-                            j += 1;
-                        }
-
-                        i += 1;
-                    }
-
-                    max as u16
-                };
+                let max_text_length = help_text
+                    .iter()
+                    .flatten()
+                    .map(|line| line.len())
+                    .max()
+                    .unwrap_or(0) as u16;
 
                 let dialog_width = vertical_dialog_chunk.width;
-                let [middle_dialog_chunk] = if dialog_width < MAX_TEXT_LENGTH {
+                let [middle_dialog_chunk] = if dialog_width < max_text_length {
                     Layout::default()
                         .direction(Direction::Horizontal)
                         .constraints([Constraint::Percentage(100)])
                         .areas(vertical_dialog_chunk)
                 } else {
                     // We calculate this so that the margins never have to split an odd number.
-                    let len = if (dialog_width.saturating_sub(MAX_TEXT_LENGTH)) % 2 == 0 {
-                        MAX_TEXT_LENGTH
+                    let len = if (dialog_width.saturating_sub(max_text_length)) % 2 == 0 {
+                        max_text_length
                     } else {
                         // It can only be 1 if the difference is greater than 1, so this is fine.
-                        MAX_TEXT_LENGTH + 1
+                        max_text_length + 1
                     };
 
                     Layout::default()
@@ -191,7 +198,28 @@ impl Painter {
                 };
 
                 self.draw_help_dialog(f, app_state, middle_dialog_chunk);
-            } else if app_state.process_kill_dialog.is_open() {
+            } else if app_state.layout_editor.is_open() {
+                let area = f.area();
+                f.buffer_mut()
+                    .set_style(area, self.styles.general_widget_style);
+
+                app_state.layout_editor.draw(f, terminal_size, &self.styles);
+            } else if app_state.event_list.is_open() {
+                let area = f.area();
+                f.buffer_mut()
+                    .set_style(area, self.styles.general_widget_style);
+
+                app_state.event_list.draw(
+                    f,
+                    terminal_size,
+                    &app_state.data_store.get_data().events,
+                    &self.styles,
+                );
+            } else if app_state.process_kill_dialog.is_open()
+                || app_state.process_priority_dialog.is_open()
+                || app_state.process_affinity_dialog.is_open()
+                || app_state.process_columns_dialog.is_open()
+            {
                 let area = f.area();
                 f.buffer_mut()
                     .set_style(area, self.styles.general_widget_style);
@@ -219,12 +247,26 @@ impl Painter {
                     ])
                     .areas::<3>(vertical_dialog_chunk)[1];
 
-                app_state
-                    .process_kill_dialog
-                    .draw(f, dialog_draw_area, &self.styles);
+                if app_state.process_kill_dialog.is_open() {
+                    app_state
+                        .process_kill_dialog
+                        .draw(f, dialog_draw_area, &self.styles);
+                } else if app_state.process_priority_dialog.is_open() {
+                    app_state
+                        .process_priority_dialog
+                        .draw(f, dialog_draw_area, &self.styles);
+                } else if app_state.process_affinity_dialog.is_open() {
+                    app_state
+                        .process_affinity_dialog
+                        .draw(f, dialog_draw_area, &self.styles);
+                } else {
+                    app_state
+                        .process_columns_dialog
+                        .draw(f, dialog_draw_area, &self.styles);
+                }
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
@@ -281,13 +323,25 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id,
                     ),
+                    Custom => self.draw_custom_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    CpuHeatmap => self.draw_cpu_heatmap(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode. This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let data = app_state.data_store.get_data();
@@ -411,18 +465,30 @@ impl Painter {
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
+                let terminal_size = if app_state.layout_tabs.is_enabled() {
+                    let [tab_draw_loc, layout_draw_loc] =
+                        Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                            .areas(terminal_size);
+                    self.draw_layout_tabs(f, app_state, tab_draw_loc);
+
+                    layout_draw_loc
+                } else {
+                    terminal_size
+                };
+                let layout = &self.layouts[app_state.layout_tabs.current];
+
                 // A two-pass algorithm - get layouts using constraints (first pass),
                 // then pass each layout to the corresponding widget (second pass).
                 // Note that layouts are already cached in ratatui, so we don't need
                 // to do it manually!
-                let base = Layout::vertical(self.layout.rows.iter().map(|r| r.constraint))
-                    .split(terminal_size);
+                let base =
+                    Layout::vertical(layout.rows.iter().map(|r| r.constraint)).split(terminal_size);
 
-                for (br, base) in self.layout.rows.iter().zip(base.iter()) {
+                for (br, base) in layout.rows.iter().zip(base.iter()) {
                     let base =
                         Layout::horizontal(br.children.iter().map(|bc| bc.constraint)).split(*base);
 
@@ -482,6 +548,8 @@ impl Painter {
                     TempGraph => {
                         self.draw_temperature_graph(f, app_state, *draw_loc, widget.widget_id)
                     }
+                    Custom => self.draw_custom_graph(f, app_state, *draw_loc, widget.widget_id),
+                    CpuHeatmap => self.draw_cpu_heatmap(f, app_state, *draw_loc, widget.widget_id),
                     _ => {}
                 }
             }
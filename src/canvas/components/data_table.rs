@@ -144,6 +144,11 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.state.current_index
     }
 
+    /// Returns all the data in the table.
+    pub fn data(&self) -> &[DataType] {
+        &self.data
+    }
+
     /// Optionally returns the currently selected item, if there is one.
     pub fn current_item(&self) -> Option<&DataType> {
         self.data.get(self.state.current_index)
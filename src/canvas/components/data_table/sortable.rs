@@ -2,7 +2,10 @@ use std::{borrow::Cow, marker::PhantomData, num::NonZeroU16};
 
 use concat_string::concat_string;
 use itertools::Itertools;
-use tui::widgets::Row;
+use tui::{
+    style::{Modifier, Style},
+    widgets::{Cell, Row},
+};
 
 use super::{
     ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps, DataTableState,
@@ -48,6 +51,25 @@ pub struct Sortable {
 
     /// The current sorting order.
     pub order: SortOrder,
+
+    /// Further columns to sort by when rows are tied, from the highest priority
+    /// to the lowest, along with their sorting orders.
+    pub secondary: Vec<(usize, SortOrder)>,
+
+    /// The column under the header cursor, which is used to pick columns to
+    /// sort by with the keyboard. Only shown once it has been moved.
+    pub cursor: Option<usize>,
+}
+
+impl Sortable {
+    /// The most columns that can be sorted by at once.
+    pub const MAX_SORT_KEYS: usize = 3;
+
+    /// The columns to sort by and their orders, from the highest priority to
+    /// the lowest.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = (usize, SortOrder)> + '_ {
+        std::iter::once((self.sort_index, self.order)).chain(self.secondary.iter().copied())
+    }
 }
 
 /// The [`SortType`] trait is meant to be used in the typing of a [`DataTable`]
@@ -92,24 +114,40 @@ impl SortType for Sortable {
         const UP_ARROW: &str = "▲";
         const DOWN_ARROW: &str = "▼";
 
+        // The priority of each sort column is only shown if there is more than one.
+        let show_priority = !self.secondary.is_empty();
+
         Row::new(
             columns
                 .iter()
                 .zip(widths)
                 .enumerate()
                 .map(|(index, (c, &width))| {
-                    if index == self.sort_index {
-                        let arrow = match self.order {
+                    let text = if let Some((priority, (_, order))) =
+                        self.keys().find_position(|(key, _)| *key == index)
+                    {
+                        let arrow = match order {
                             SortOrder::Ascending => UP_ARROW,
                             SortOrder::Descending => DOWN_ARROW,
                         };
+                        let priority = if show_priority {
+                            Cow::Owned((priority + 1).to_string())
+                        } else {
+                            Cow::Borrowed("")
+                        };
                         // TODO: I think I can get away with removing the truncate_to_text call
                         // since I almost always bind to at least the header
                         // size... TODO: Or should we instead truncate but
                         // ALWAYS leave the arrow at the end?
-                        truncate_to_text(&concat_string!(c.header(), arrow), width.get())
+                        truncate_to_text(&concat_string!(c.header(), arrow, priority), width.get())
                     } else {
                         truncate_to_text(&c.header(), width.get())
+                    };
+
+                    if self.cursor == Some(index) {
+                        Cell::from(text).style(Style::new().add_modifier(Modifier::REVERSED))
+                    } else {
+                        Cell::from(text)
                     }
                 }),
         )
@@ -131,6 +169,10 @@ pub struct SortColumn<H> {
 
     /// The default sort order.
     pub default_order: SortOrder,
+
+    /// Whether this is one of several columns being sorted by, in which case
+    /// its header also shows its priority.
+    has_priority: bool,
 }
 
 impl<D, H> DataTableColumn<H> for SortColumn<H>
@@ -172,7 +214,8 @@ where
     }
 
     fn header_len(&self) -> usize {
-        self.header().len() + 1
+        // Leave room for the sort arrow, and the priority if it is shown.
+        self.header().len() + 1 + usize::from(self.has_priority)
     }
 }
 
@@ -187,6 +230,7 @@ where
         Self {
             column: Column::new(inner),
             default_order: SortOrder::const_default(),
+            has_priority: false,
         }
     }
 
@@ -196,6 +240,7 @@ where
         Self {
             column: Column::hard(inner, width),
             default_order: SortOrder::const_default(),
+            has_priority: false,
         }
     }
 
@@ -205,6 +250,7 @@ where
         Self {
             column: Column::soft(inner, max_percentage),
             default_order: SortOrder::const_default(),
+            has_priority: false,
         }
     }
 
@@ -247,6 +293,8 @@ where
             sort_type: Sortable {
                 sort_index: props.sort_index,
                 order: props.order,
+                secondary: Vec::new(),
+                cursor: None,
             },
             first_draw: true,
             first_index: None,
@@ -276,19 +324,30 @@ where
     /// If there was some update, the corresponding column type will be
     /// returned. If nothing happens, [`None`] is returned.
     pub fn try_select_location(&mut self, x: u16, y: u16) -> Option<usize> {
+        let index = self.header_index(x, y)?;
+        self.set_sort_index(index);
+        Some(self.sort_type.sort_index)
+    }
+
+    /// Like [`Self::try_select_location`], but the corresponding column is
+    /// added as another column to sort by, as with [`Self::add_sort_index`].
+    pub fn try_add_location(&mut self, x: u16, y: u16) -> Option<usize> {
+        let index = self.header_index(x, y)?;
+        self.add_sort_index(index);
+        Some(index)
+    }
+
+    /// The index of the column whose header is at `x` and `y`, if any.
+    fn header_index(&self, x: u16, y: u16) -> Option<usize> {
         if self.state.inner_rect.height > 1 && self.state.inner_rect.y == y {
-            if let Some(index) = self.get_range(x) {
-                self.set_sort_index(index);
-                Some(self.sort_type.sort_index)
-            } else {
-                None
-            }
+            self.get_range(x)
         } else {
             None
         }
     }
 
-    /// Updates the sort index, and sets the sort order as appropriate.
+    /// Updates the sort index, and sets the sort order as appropriate. Any
+    /// other columns being sorted by are cleared.
     ///
     /// If the index is different from the previous one, it will move to the new
     /// index and set the sort order to the prescribed default sort order.
@@ -301,14 +360,131 @@ where
         } else if let Some(col) = self.columns.get(index) {
             self.sort_type.sort_index = index;
             self.sort_type.order = col.default_order;
+            self.sort_type.secondary.clear();
+            self.update_priorities();
         }
     }
 
+    /// Adds a column to sort by when rows are tied on the columns already
+    /// being sorted by, with its default sort order.
+    ///
+    /// If the column is already being sorted by, its order is toggled instead.
+    /// If there are already [`Sortable::MAX_SORT_KEYS`] columns, the lowest
+    /// priority one is replaced.
+    pub fn add_sort_index(&mut self, index: usize) {
+        if self.sort_type.sort_index == index {
+            self.toggle_order();
+        } else if let Some((_, order)) = self
+            .sort_type
+            .secondary
+            .iter_mut()
+            .find(|(key, _)| *key == index)
+        {
+            *order = order.rev();
+        } else if let Some(col) = self.columns.get(index) {
+            if self.sort_type.secondary.len() + 1 >= Sortable::MAX_SORT_KEYS {
+                self.sort_type.secondary.pop();
+            }
+            self.sort_type.secondary.push((index, col.default_order));
+            self.update_priorities();
+        }
+    }
+
+    /// Stops sorting by a column, if it is not the main one being sorted by.
+    pub fn remove_sort_index(&mut self, index: usize) {
+        self.sort_type.secondary.retain(|(key, _)| *key != index);
+        self.update_priorities();
+    }
+
+    /// Sets every column to sort by and their orders, from the highest priority
+    /// to the lowest. Columns that don't exist are skipped, and nothing is done
+    /// if none exist.
+    pub fn set_sort_keys(&mut self, keys: impl IntoIterator<Item = (usize, SortOrder)>) {
+        let mut keys = keys
+            .into_iter()
+            .filter(|(index, _)| *index < self.columns.len())
+            .unique_by(|(index, _)| *index)
+            .take(Sortable::MAX_SORT_KEYS);
+
+        if let Some((index, order)) = keys.next() {
+            self.sort_type.sort_index = index;
+            self.sort_type.order = order;
+            self.sort_type.secondary = keys.collect();
+            self.update_priorities();
+        }
+    }
+
+    /// Marks which columns show their sort priority, which is only done when
+    /// there is more than one column being sorted by.
+    fn update_priorities(&mut self) {
+        let is_multiple = !self.sort_type.secondary.is_empty();
+        for (index, column) in self.columns.iter_mut().enumerate() {
+            column.has_priority = is_multiple && self.sort_type.keys().any(|(key, _)| key == index);
+        }
+    }
+
+    /// Moves the header cursor by `delta` shown columns, starting from the main
+    /// column being sorted by if the cursor isn't shown yet.
+    pub fn move_sort_cursor(&mut self, delta: isize) {
+        let shown = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let current = self.sort_type.cursor.unwrap_or(self.sort_type.sort_index);
+
+        let Some(position) = shown.iter().position(|&index| index == current) else {
+            self.sort_type.cursor = shown.first().copied();
+            return;
+        };
+        if self.sort_type.cursor.is_some() {
+            let position = position
+                .saturating_add_signed(delta)
+                .min(shown.len().saturating_sub(1));
+            self.sort_type.cursor = Some(shown[position]);
+        } else {
+            self.sort_type.cursor = Some(current);
+        }
+    }
+
+    /// The column under the header cursor, if it is shown.
+    pub fn sort_cursor(&self) -> Option<usize> {
+        self.sort_type.cursor
+    }
+
     /// Returns the current sort index.
     pub fn sort_index(&self) -> usize {
         self.sort_type.sort_index
     }
 
+    /// The columns to sort by and their orders, from the highest priority to
+    /// the lowest.
+    pub fn sort_keys(&self) -> Vec<(usize, SortOrder)> {
+        self.sort_type.keys().collect()
+    }
+
+    /// Sorts data by every column being sorted by.
+    pub fn sort_rows(&self, data: &mut [D]) {
+        self.sort_rows_with(data, |order| order);
+    }
+
+    /// Sorts data by every column being sorted by, in the reverse order.
+    pub fn sort_rows_reversed(&self, data: &mut [D]) {
+        self.sort_rows_with(data, |order| order.rev());
+    }
+
+    fn sort_rows_with(&self, data: &mut [D], order_fn: impl Fn(SortOrder) -> SortOrder) {
+        // Sorting is stable, so sorting by the lowest priority column first
+        // leaves rows that are tied on a higher priority one in the right order.
+        for (index, order) in self.sort_type.keys().rev() {
+            if let Some(column) = self.columns.get(index) {
+                column.sort_by(data, order_fn(order));
+            }
+        }
+    }
+
     /// Given a `needle` coordinate, select the corresponding index and value.
     fn get_range(&self, needle: u16) -> Option<usize> {
         let mut start = self.state.inner_rect.x;
@@ -334,7 +510,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::options::config::flags::TableGap;
+    use crate::{options::config::flags::TableGap, utils::general::sort_partial_fn};
 
     #[derive(Clone, PartialEq, Eq, Debug)]
     struct TestType {
@@ -375,13 +551,14 @@ mod test {
         type DataType = TestType;
 
         fn sort_data(&self, data: &mut [TestType], descending: bool) {
+            // Like the real tables, this must be stable in either order.
             match self {
-                ColumnType::Index => data.sort_by_key(|t| t.index),
-                ColumnType::Data => data.sort_by_key(|t| t.data),
-            }
-
-            if descending {
-                data.reverse();
+                ColumnType::Index => {
+                    data.sort_by(|a, b| sort_partial_fn(descending)(&a.index, &b.index))
+                }
+                ColumnType::Data => {
+                    data.sort_by(|a, b| sort_partial_fn(descending)(&a.data, &b.data))
+                }
             }
         }
     }
@@ -530,4 +707,138 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_sorting_multiple_columns() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some("test".into()),
+                table_gap: TableGap::Space,
+                left_to_right: false,
+                is_basic: false,
+                show_table_scroll_position: true,
+                show_table_scroll_bar: false,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 1,
+            order: SortOrder::Ascending,
+        };
+
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        let mut data =
+            [(0, 100), (1, 200), (2, 100), (3, 200)].map(|(index, data)| TestType { index, data });
+
+        // Adding a column again reverses it rather than adding it twice.
+        table.add_sort_index(0);
+        table.add_sort_index(0);
+        assert_eq!(
+            table.sort_keys(),
+            vec![(1, SortOrder::Ascending), (0, SortOrder::Descending)]
+        );
+
+        table.sort_rows(&mut data);
+        assert_eq!(
+            data.each_ref().map(|t| t.index),
+            [2, 0, 3, 1],
+            "ties in data should be broken by descending index"
+        );
+
+        table.sort_rows_reversed(&mut data);
+        assert_eq!(data.each_ref().map(|t| t.index), [1, 3, 0, 2]);
+
+        // The main column can only be replaced, not removed.
+        table.remove_sort_index(1);
+        table.remove_sort_index(0);
+        assert_eq!(table.sort_keys(), vec![(1, SortOrder::Ascending)]);
+
+        table.set_sort_keys([
+            (5, SortOrder::Ascending),
+            (0, SortOrder::Ascending),
+            (0, SortOrder::Descending),
+            (1, SortOrder::Descending),
+        ]);
+        assert_eq!(
+            table.sort_keys(),
+            vec![(0, SortOrder::Ascending), (1, SortOrder::Descending)]
+        );
+
+        // Sorting by a single column clears the rest.
+        table.set_sort_index(1);
+        assert_eq!(table.sort_keys(), vec![(1, SortOrder::Ascending)]);
+    }
+
+    #[test]
+    fn test_sort_header_width() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some("test".into()),
+                table_gap: TableGap::Space,
+                left_to_right: false,
+                is_basic: false,
+                show_table_scroll_position: true,
+                show_table_scroll_bar: false,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        let header_lens = |table: &SortDataTable<TestType, ColumnType>| {
+            table
+                .columns
+                .iter()
+                .map(|c| c.header_len())
+                .collect::<Vec<_>>()
+        };
+
+        // Only the arrow needs room with one column, the priority only with more.
+        assert_eq!(header_lens(&table), vec![6, 5]);
+        table.add_sort_index(1);
+        assert_eq!(header_lens(&table), vec![7, 6]);
+        table.remove_sort_index(1);
+        assert_eq!(header_lens(&table), vec![6, 5]);
+    }
+
+    #[test]
+    fn test_sort_cursor() {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some("test".into()),
+                table_gap: TableGap::Space,
+                left_to_right: false,
+                is_basic: false,
+                show_table_scroll_position: true,
+                show_table_scroll_bar: false,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 1,
+            order: SortOrder::Ascending,
+        };
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+
+        // The first move only shows the cursor on the sorted column.
+        assert_eq!(table.sort_cursor(), None);
+        table.move_sort_cursor(-1);
+        assert_eq!(table.sort_cursor(), Some(1));
+        table.move_sort_cursor(-1);
+        assert_eq!(table.sort_cursor(), Some(0));
+        table.move_sort_cursor(-1);
+        assert_eq!(table.sort_cursor(), Some(0));
+
+        table.columns[0].set_hidden(true);
+        table.move_sort_cursor(1);
+        assert_eq!(table.sort_cursor(), Some(1));
+    }
 }
@@ -0,0 +1,52 @@
+use tui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::Tabs,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{app::App, canvas::Painter};
+
+impl Painter {
+    /// Draws the tab bar showing the named layouts, with the current one highlighted.
+    pub fn draw_layout_tabs(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let [tab_draw_loc] = Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .horizontal_margin(1)
+            .areas(draw_loc);
+
+        let layout_tabs = &mut app_state.layout_tabs;
+        f.render_widget(
+            Tabs::new(
+                layout_tabs
+                    .names
+                    .iter()
+                    .map(|name| Line::from(name.as_str()))
+                    .collect::<Vec<_>>(),
+            )
+            .divider(tui::symbols::line::VERTICAL)
+            .style(self.styles.text_style)
+            .highlight_style(self.styles.selected_text_style)
+            .select(layout_tabs.current),
+            tab_draw_loc,
+        );
+
+        if should_get_widget_bounds {
+            let mut current_x = tab_draw_loc.x;
+            let current_y = tab_draw_loc.y;
+            let mut tab_click_locs: Vec<((u16, u16), (u16, u16))> = vec![];
+            for name in &layout_tabs.names {
+                // Skip the leading space before the tab label.
+                current_x += 1;
+                let width = UnicodeWidthStr::width(name.as_str()) as u16;
+                tab_click_locs.push(((current_x, current_y), (current_x + width, current_y)));
+
+                // Go past the trailing space, the divider, and to the next tab's leading space.
+                current_x += width + 2;
+            }
+            layout_tabs.tab_click_locs = Some(tab_click_locs);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Lower-level or shared drawing components used throughout bottom.
 
 pub mod data_table;
+pub mod layout_tabs;
 pub mod pipe_gauge;
 pub mod scroll_bar;
 pub mod search_input;
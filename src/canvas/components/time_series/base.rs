@@ -1,4 +1,7 @@
-use std::{borrow::Cow, time::Instant};
+use std::{
+    borrow::Cow,
+    time::{Duration, Instant},
+};
 
 use concat_string::concat_string;
 use timeless::data::ChunkedData;
@@ -22,6 +25,7 @@ pub(crate) struct GraphData<'a, F = f64> {
     values: Option<&'a ChunkedData<F>>,
     style: Style,
     name: Option<Cow<'a, str>>,
+    inspect: Option<(Cow<'a, str>, &'a dyn Fn(f64) -> String)>,
 }
 
 impl<'a, F> GraphData<'a, F> {
@@ -44,6 +48,13 @@ impl<'a, F> GraphData<'a, F> {
         self.name = Some(name);
         self
     }
+
+    /// Set the label of the series and how its values are formatted when the
+    /// graph is inspected.
+    pub fn inspect(mut self, label: Cow<'a, str>, format: &'a dyn Fn(f64) -> String) -> Self {
+        self.inspect = Some((label, format));
+        self
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -104,6 +115,25 @@ pub struct TimeGraph<'a> {
 
     /// The chart scaling.
     pub scaling: ChartScaling,
+
+    /// The position of the inspect cursor, in milliseconds before the newest
+    /// data point, if the graph is being inspected.
+    pub inspect: Option<u64>,
+
+    /// How far the graph is panned back from the newest data point, in
+    /// milliseconds.
+    pub pan: u64,
+
+    /// Whether to show the min, average, max and 95th percentile of each
+    /// series over the visible time range in the legend.
+    pub legend_stats: bool,
+
+    /// How much time each point is an average over, if the data is
+    /// downsampled. The legend stats are then marked as coming from averages.
+    pub resolution: Option<Duration>,
+
+    /// The times of events to draw markers at.
+    pub events: Vec<Instant>,
 }
 
 impl TimeGraph<'_> {
@@ -115,12 +145,12 @@ impl TimeGraph<'_> {
         if self.hide_x_labels {
             Axis::default().bounds(adjusted_x_bounds)
         } else {
-            let x_bound_left = ((-self.x_min) as u64 / 1000).to_string();
-            let x_bound_right = "0s";
+            let x_bound_left = (((-self.x_min) as u64 + self.pan) / 1000).to_string();
+            let x_bound_right = (self.pan / 1000).to_string();
 
             let x_labels = vec![
                 Span::styled(concat_string!(x_bound_left, "s"), self.graph_style),
-                Span::styled(x_bound_right, self.graph_style),
+                Span::styled(concat_string!(x_bound_right, "s"), self.graph_style),
             ];
 
             Axis::default()
@@ -143,6 +173,67 @@ impl TimeGraph<'_> {
             )
     }
 
+    /// Generates the lines shown while inspecting the graph at `offset`
+    /// milliseconds before the newest data point: the offset, then the value
+    /// of each series at that time.
+    fn generate_inspect_lines<F: Copy + Into<f64>>(
+        &self, offset: u64, graph_data: &[GraphData<'_, F>],
+    ) -> Vec<Line<'static>> {
+        let time = match offset + self.pan {
+            0 => "now".to_string(),
+            ago => format!("{:.1}s ago", ago as f64 / 1000.0),
+        };
+        let mut lines = vec![Line::styled(time, self.graph_style)];
+
+        for data in graph_data {
+            let Some((label, format)) = &data.inspect else {
+                continue;
+            };
+
+            let value = data
+                .values
+                .and_then(|values| value_at(data.time, values, offset));
+            let text = match value {
+                Some(value) => format!("{label}: {}", format(value)),
+                None => format!("{label}: N/A"),
+            };
+            lines.push(Line::styled(text, data.style));
+        }
+
+        lines
+    }
+
+    /// Adds the stats of `data` over the visible time range to its legend
+    /// entry. Series without a name are named after their inspect label.
+    fn add_legend_stats<'a, F: Copy + Into<f64>>(
+        &self, data: GraphData<'a, F>,
+    ) -> GraphData<'a, F> {
+        let (Some((label, format)), Some(values)) = (&data.inspect, data.values) else {
+            return data;
+        };
+        let Some(stats) = window_stats(data.time, values, (-self.x_min) as u64) else {
+            return data;
+        };
+
+        let name = data.name.as_ref().unwrap_or(label);
+        let mut name = format!(
+            "{name}  min {} avg {} max {} p95 {}",
+            format(stats.min),
+            format(stats.avg),
+            format(stats.max),
+            format(stats.p95)
+        );
+        if let Some(resolution) = self.resolution {
+            // Peaks are averaged away, so don't pass these off as exact.
+            name.push_str(&format!(
+                " (avg of {})",
+                humantime::format_duration(resolution)
+            ));
+        }
+
+        data.name(name.into())
+    }
+
     /// Draws a time graph at [`Rect`] location provided by `draw_loc`. A time
     /// graph is used to display data points throughout time in the x-axis.
     ///
@@ -152,14 +243,67 @@ impl TimeGraph<'_> {
     ///   graph.
     /// - Expects `graph_data`, which represents *what* data to draw, and
     ///   various details like style and optional legends.
+    ///
+    /// Returns the area the data was drawn in.
     pub fn draw<F: Copy + Default + Into<f64>>(
         &self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: Vec<GraphData<'_, F>>,
-    ) {
+    ) -> Rect {
         // TODO: (points_rework_v1) can we reduce allocations in the underlying graph by
         // saving some sort of state?
 
         let x_axis = self.generate_x_axis();
         let y_axis = self.generate_y_axis();
+
+        let position = (self.pan > 0).then(|| {
+            let history = graph_data
+                .iter()
+                .find_map(|data| {
+                    let span = data.time.last()?.duration_since(*data.time.first()?);
+                    Some(span.as_millis() as u64)
+                })
+                .unwrap_or_default();
+            let indicator = position_indicator(
+                history,
+                self.pan,
+                (-self.x_min) as u64,
+                POSITION_INDICATOR_WIDTH,
+            );
+
+            format!(" {}s ago {indicator} ", self.pan / 1000)
+        });
+        let graph_data: Vec<_> = graph_data
+            .into_iter()
+            .map(|data| {
+                let time = pan_times(data.time, self.pan);
+                let data = data.time(time);
+
+                if self.legend_stats {
+                    self.add_legend_stats(data)
+                } else {
+                    data
+                }
+            })
+            .collect();
+
+        let event_markers = graph_data
+            .iter()
+            .find_map(|data| data.time.last())
+            .map(|&newest| {
+                self.events
+                    .iter()
+                    .filter_map(|&time| newest.checked_duration_since(time))
+                    .map(|age| -(age.as_millis() as f64))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let inspect = self.inspect.map(|offset| {
+            let offset = offset.min((-self.x_min) as u64);
+            (
+                -(offset as f64),
+                self.generate_inspect_lines(offset, &graph_data),
+            )
+        });
         let data = graph_data.into_iter().map(create_dataset).collect();
 
         let block = {
@@ -176,31 +320,158 @@ impl TimeGraph<'_> {
                 b = b.title_top(Line::styled(" Esc to go back ", self.title_style).right_aligned())
             }
 
+            if let Some(position) = position {
+                b = b.title_bottom(Line::styled(position, self.title_style).right_aligned())
+            }
+
             b
         };
 
-        f.render_widget(
-            TimeChart::new(data)
-                .block(block)
-                .x_axis(x_axis)
-                .y_axis(y_axis)
-                .marker(self.marker)
-                .style(self.general_widget_style)
-                .legend_style(self.graph_style)
-                .legend_position(self.legend_position)
-                .hidden_legend_constraints({
-                    let constraints = self
-                        .legend_constraints
-                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS);
-
-                    (constraints.width, constraints.height)
-                })
-                .scaling(self.scaling),
-            draw_loc,
-        )
+        let inner = block.inner(draw_loc);
+        let chart = TimeChart::new(data)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .marker(self.marker)
+            .style(self.general_widget_style)
+            .legend_style(self.graph_style)
+            .legend_position(self.legend_position)
+            .hidden_legend_constraints({
+                // The stats make the legend a lot wider, so don't hide it for that.
+                let constraints = if self.legend_stats {
+                    LegendConstraints {
+                        width: Constraint::Percentage(100),
+                        height: Constraint::Percentage(100),
+                    }
+                } else {
+                    self.legend_constraints
+                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS)
+                };
+
+                (constraints.width, constraints.height)
+            })
+            .scaling(self.scaling)
+            .inspect(inspect)
+            .event_markers(event_markers);
+        let graph_area = chart.graph_area(inner);
+
+        f.render_widget(chart, draw_loc);
+
+        graph_area
+    }
+}
+
+/// The width of the indicator showing where a panned graph is in the history.
+const POSITION_INDICATOR_WIDTH: usize = 12;
+
+/// Return the part of `times` that is at least `pan` milliseconds older than
+/// the newest time, which is what a graph panned back by `pan` shows.
+pub(crate) fn pan_times(times: &[Instant], pan: u64) -> &[Instant] {
+    let Some(&last_time) = times.last() else {
+        return times;
+    };
+    if pan == 0 {
+        return times;
+    }
+
+    match last_time.checked_sub(Duration::from_millis(pan)) {
+        Some(edge) => &times[..times.partition_point(|&time| time <= edge)],
+        None => &[],
     }
 }
 
+/// Draw a track of `width` cells for `history` milliseconds of data, with the
+/// displayed part highlighted. The newest data is on the right.
+fn position_indicator(history: u64, pan: u64, display_time: u64, width: usize) -> String {
+    let history = history.max(pan + display_time);
+    let width = width as u64;
+
+    (0..width)
+        .map(|cell| {
+            // The times covered by the cell, in milliseconds before the newest data.
+            let newest = history * (width - 1 - cell) / width;
+            let oldest = history * (width - cell) / width;
+
+            if oldest > pan && newest < pan + display_time {
+                '━'
+            } else {
+                '─'
+            }
+        })
+        .collect()
+}
+
+/// Return the value in `values` closest to `offset` milliseconds before the
+/// newest time in `times`.
+fn value_at<F: Copy + Into<f64>>(
+    times: &[Instant], values: &ChunkedData<F>, offset: u64,
+) -> Option<f64> {
+    let last_time = *times.last()?;
+    let target = last_time
+        .checked_sub(Duration::from_millis(offset))
+        .unwrap_or(last_time);
+
+    let mut closest: Option<(Duration, f64)> = None;
+    for (&time, &value) in values.iter_along_base(times).rev() {
+        let distance = if time > target {
+            time - target
+        } else {
+            target - time
+        };
+
+        match closest {
+            // Times are in decreasing order, so once they move away from the
+            // target, the closest one has been found.
+            Some((closest_distance, _)) if distance > closest_distance => break,
+            _ => closest = Some((distance, value.into())),
+        }
+    }
+
+    closest.map(|(_, value)| value)
+}
+
+/// The min, average, max and 95th percentile of a series.
+#[derive(Debug, PartialEq)]
+struct WindowStats {
+    min: f64,
+    avg: f64,
+    max: f64,
+    p95: f64,
+}
+
+/// Return the stats of the values in `values` from the last `display_time`
+/// milliseconds of `times`, if there are any.
+fn window_stats<F: Copy + Into<f64>>(
+    times: &[Instant], values: &ChunkedData<F>, display_time: u64,
+) -> Option<WindowStats> {
+    let last_time = *times.last()?;
+    let start = last_time.checked_sub(Duration::from_millis(display_time));
+
+    let mut visible: Vec<f64> = values
+        .iter_along_base(times)
+        .rev()
+        .take_while(|(time, _)| start.is_none_or(|start| **time >= start))
+        .map(|(_, &value)| value.into())
+        .filter(|value: &f64| value.is_finite())
+        .collect();
+    if visible.is_empty() {
+        return None;
+    }
+
+    visible.sort_unstable_by(f64::total_cmp);
+    let len = visible.len();
+
+    // Uses the nearest-rank method.
+    let p95_index = ((len as f64 * 0.95).ceil() as usize).saturating_sub(1);
+
+    Some(WindowStats {
+        min: visible[0],
+        avg: visible.iter().sum::<f64>() / len as f64,
+        max: visible[len - 1],
+        p95: visible[p95_index],
+    })
+}
+
 /// Creates a new [`Dataset`].
 fn create_dataset<F: Copy + Default + Into<f64>>(data: GraphData<'_, F>) -> Dataset<'_, F> {
     let GraphData {
@@ -208,6 +479,7 @@ fn create_dataset<F: Copy + Default + Into<f64>>(data: GraphData<'_, F>) -> Data
         values,
         style,
         name,
+        inspect: _,
     } = data;
 
     let Some(values) = values else {
@@ -228,7 +500,12 @@ fn create_dataset<F: Copy + Default + Into<f64>>(data: GraphData<'_, F>) -> Data
 
 #[cfg(test)]
 mod test {
-    use std::borrow::Cow;
+    use std::{
+        borrow::Cow,
+        time::{Duration, Instant},
+    };
+
+    use timeless::data::ChunkedData;
 
     use tui::{
         style::{Color, Style},
@@ -237,7 +514,10 @@ mod test {
         widgets::BorderType,
     };
 
-    use super::{AxisBound, ChartScaling, TimeGraph};
+    use super::{
+        AxisBound, ChartScaling, GraphData, TimeGraph, WindowStats, pan_times, position_indicator,
+        value_at, window_stats,
+    };
     use crate::canvas::components::time_series::Axis;
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
@@ -264,6 +544,11 @@ mod test {
             legend_constraints: None,
             marker: Marker::Braille,
             scaling: ChartScaling::Linear,
+            inspect: None,
+            pan: 0,
+            legend_stats: false,
+            resolution: None,
+            events: Vec::new(),
         }
     }
 
@@ -301,4 +586,95 @@ mod test {
         assert_eq!(y_axis.labels, actual.labels);
         assert_eq!(y_axis.style, actual.style);
     }
+
+    #[test]
+    fn inspect_value_at() {
+        let now = Instant::now();
+        let times: Vec<Instant> = (0..4)
+            .rev()
+            .map(|i| now - Duration::from_millis(i * 1000))
+            .collect();
+        let mut values = ChunkedData::default();
+        for value in [1.0, 2.0, 3.0, 4.0] {
+            values.push(value);
+        }
+
+        assert_eq!(value_at(&times, &values, 0), Some(4.0));
+        assert_eq!(value_at(&times, &values, 1400), Some(3.0));
+        assert_eq!(value_at(&times, &values, 1600), Some(2.0));
+        assert_eq!(value_at(&times, &values, 60_000), Some(1.0));
+        assert_eq!(value_at(&[], &values, 0), None);
+    }
+
+    #[test]
+    fn pan() {
+        let now = Instant::now();
+        let times: Vec<Instant> = (0..4)
+            .rev()
+            .map(|i| now - Duration::from_millis(i * 1000))
+            .collect();
+
+        assert_eq!(pan_times(&times, 0).len(), 4);
+        assert_eq!(pan_times(&times, 1000).len(), 3);
+        assert_eq!(pan_times(&times, 1500).len(), 2);
+        assert_eq!(pan_times(&times, 60_000).len(), 0);
+
+        assert_eq!(position_indicator(120, 30, 30, 4), "──━─");
+        assert_eq!(position_indicator(120, 0, 60, 4), "──━━");
+        // The history always covers at least what is displayed.
+        assert_eq!(position_indicator(0, 10, 10, 4), "━━──");
+    }
+
+    #[test]
+    fn legend_stats() {
+        let now = Instant::now();
+        let times: Vec<Instant> = (0..20)
+            .rev()
+            .map(|i| now - Duration::from_millis(i * 1000))
+            .collect();
+        let mut values = ChunkedData::default();
+        for value in 1..=20 {
+            values.push(value as f64);
+        }
+
+        assert_eq!(
+            window_stats(&times, &values, 60_000),
+            Some(WindowStats {
+                min: 1.0,
+                avg: 10.5,
+                max: 20.0,
+                p95: 19.0,
+            })
+        );
+
+        // Only the last 5 seconds are visible, which is 6 points.
+        assert_eq!(
+            window_stats(&times, &values, 5000),
+            Some(WindowStats {
+                min: 15.0,
+                avg: 17.5,
+                max: 20.0,
+                p95: 20.0,
+            })
+        );
+
+        assert_eq!(window_stats(&[], &values, 5000), None);
+
+        // Stats from downsampled data are marked as averages.
+        let format = |value: f64| format!("{value}");
+        let name = |graph: &TimeGraph<'_>| {
+            let data = GraphData::default()
+                .time(&times)
+                .values(&values)
+                .inspect("CPU".into(), &format);
+            graph.add_legend_stats(data).name.unwrap_or_default()
+        };
+        let mut graph = create_time_series();
+        assert_eq!(name(&graph), "CPU  min 5 avg 12.5 max 20 p95 20");
+        graph.resolution = Some(Duration::from_secs(10));
+        assert_eq!(
+            name(&graph),
+            "CPU  min 5 avg 12.5 max 20 p95 20 (avg of 10s)"
+        );
+    }
 }
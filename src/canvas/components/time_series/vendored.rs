@@ -441,6 +441,11 @@ pub(super) struct TimeChart<'a, F: Copy + Default + Into<f64> = f64> {
     marker: Marker,
     /// Whether to scale the values differently.
     scaling: ChartScaling,
+    /// The x value of the inspect cursor, and the lines to show in the legend
+    /// box in place of the dataset names.
+    inspect: Option<(f64, Vec<Line<'a>>)>,
+    /// The x values of event markers.
+    event_markers: Vec<f64>,
 }
 
 impl<'a, F: Copy + Default + Into<f64>> TimeChart<'a, F> {
@@ -457,6 +462,8 @@ impl<'a, F: Copy + Default + Into<f64>> TimeChart<'a, F> {
             legend_position: Some(LegendPosition::default()),
             marker: Marker::Braille,
             scaling: ChartScaling::default(),
+            inspect: None,
+            event_markers: Vec::new(),
         }
     }
 
@@ -555,6 +562,40 @@ impl<'a, F: Copy + Default + Into<f64>> TimeChart<'a, F> {
         self
     }
 
+    /// Draws an inspect cursor at `x`, and shows `lines` in the legend box
+    /// instead of the dataset names. While inspecting, the legend box is
+    /// always shown.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn inspect(mut self, inspect: Option<(f64, Vec<Line<'a>>)>) -> TimeChart<'a, F> {
+        self.inspect = inspect;
+        self
+    }
+
+    /// Draws a dotted vertical line at each of the given x values, behind the
+    /// data.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn event_markers(mut self, event_markers: Vec<f64>) -> TimeChart<'a, F> {
+        self.event_markers = event_markers;
+        self
+    }
+
+    /// Return the area the data is drawn in, given the area inside the block.
+    pub fn graph_area(&self, area: Rect) -> Rect {
+        self.layout(area).graph_area
+    }
+
+    /// The entries of the legend box and their styles.
+    fn legend_entries(&self) -> Vec<(&Line<'a>, Style)> {
+        match &self.inspect {
+            Some((_, lines)) => lines.iter().map(|line| (line, line.style)).collect(),
+            None => self
+                .datasets
+                .iter()
+                .filter_map(|ds| Some((ds.name.as_ref()?, ds.style())))
+                .collect(),
+        }
+    }
+
     /// Compute the internal layout of the chart given the area. If the area is
     /// too small some elements may be automatically hidden
     fn layout(&self, area: Rect) -> ChartLayout {
@@ -601,21 +642,30 @@ impl<'a, F: Copy + Default + Into<f64>> TimeChart<'a, F> {
             }
         }
 
-        if let Some(legend_position) = self.legend_position {
+        let (legend_position, hidden_legend_constraints) = if self.inspect.is_some() {
+            (
+                Some(self.legend_position.unwrap_or_default()),
+                (Constraint::Percentage(100), Constraint::Percentage(100)),
+            )
+        } else {
+            (self.legend_position, self.hidden_legend_constraints)
+        };
+
+        if let Some(legend_position) = legend_position {
             let entry_widths: Vec<u16> = self
-                .datasets
+                .legend_entries()
                 .iter()
-                .filter_map(|d| Some(d.name.as_ref()?.width() as u16))
+                .map(|(line, _)| line.width() as u16)
                 .collect();
 
             let entry_count = entry_widths.len() as u16;
 
             if entry_count > 0 {
-                let [max_legend_width] = Layout::horizontal([self.hidden_legend_constraints.0])
+                let [max_legend_width] = Layout::horizontal([hidden_legend_constraints.0])
                     .flex(Flex::Start)
                     .areas(layout.graph_area);
 
-                let [max_legend_height] = Layout::vertical([self.hidden_legend_constraints.1])
+                let [max_legend_height] = Layout::vertical([hidden_legend_constraints.1])
                     .flex(Flex::Start)
                     .areas(layout.graph_area);
 
@@ -793,6 +843,20 @@ impl<'a, F: Copy + Default + Into<f64>> TimeChart<'a, F> {
     }
 }
 
+/// The symbol used for event markers.
+const EVENT_MARKER: &str = "┊";
+
+/// Return the column in `graph_area` that `x` is drawn at, if `x` is within
+/// the bounds.
+fn x_to_column(x: f64, [x_min, x_max]: [f64; 2], graph_area: Rect) -> Option<u16> {
+    if x_max <= x_min || x < x_min || x > x_max || graph_area.width == 0 {
+        return None;
+    }
+
+    let ratio = (x - x_min) / (x_max - x_min);
+    Some(graph_area.left() + (ratio * f64::from(graph_area.width - 1)).round() as u16)
+}
+
 impl<F: Copy + Default + Into<f64>> Widget for TimeChart<'_, F> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         buf.set_style(area, self.style);
@@ -860,6 +924,34 @@ impl<F: Copy + Default + Into<f64>> Widget for TimeChart<'_, F> {
             })
             .render(graph_area, buf);
 
+        for x in &self.event_markers {
+            let Some(column) = x_to_column(*x, x_bounds, graph_area) else {
+                continue;
+            };
+
+            for y in graph_area.top()..graph_area.bottom() {
+                if let Some(cell) = buf.cell_mut((column, y)) {
+                    // Only draw over empty cells, so the data stays visible.
+                    if matches!(cell.symbol(), " " | "\u{2800}") {
+                        cell.set_symbol(EVENT_MARKER).set_style(self.x_axis.style);
+                    }
+                }
+            }
+        }
+
+        if let Some(column) = self
+            .inspect
+            .as_ref()
+            .and_then(|(x, _)| x_to_column(*x, x_bounds, graph_area))
+        {
+            for y in graph_area.top()..graph_area.bottom() {
+                if let Some(cell) = buf.cell_mut((column, y)) {
+                    cell.set_symbol(symbols::line::VERTICAL)
+                        .set_style(self.legend_style);
+                }
+            }
+        }
+
         if let Some((x, y)) = layout.title_x {
             if let Some(title) = self.x_axis.title.as_ref() {
                 let width = graph_area
@@ -916,9 +1008,8 @@ impl<F: Copy + Default + Into<f64>> Widget for TimeChart<'_, F> {
             let max_width = legend_area.width - 2;
 
             for (i, (dataset_name, dataset_style)) in self
-                .datasets
-                .iter()
-                .filter_map(|ds| Some((ds.name.as_ref()?, ds.style())))
+                .legend_entries()
+                .into_iter()
                 .enumerate()
                 .take((legend_area.height - 2) as usize)
             {
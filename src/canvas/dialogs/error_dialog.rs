@@ -0,0 +1,50 @@
+//! Drawing for the error state shared by the process dialogs.
+
+use tui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::{Line, Text},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::{
+    canvas::drawing_utils::dialog_block, collection::processes::Pid, options::config::style::Styles,
+};
+
+/// Draw a dialog reporting that `action` failed on a process, shrunk to the
+/// height of its text.
+pub fn draw_error_dialog(
+    f: &mut Frame<'_>, draw_area: Rect, styles: &Styles, action: &str, process_name: &str,
+    pid: Option<Pid>, err: &str,
+) {
+    let text = Text::from(vec![
+        if let Some(pid) = pid {
+            format!("Failed to {action} process {process_name} ({pid}):").into()
+        } else {
+            format!("Failed to {action} process '{process_name}':").into()
+        },
+        err.to_owned().into(),
+        "Please press ENTER or ESC to close this dialog.".into(),
+    ]);
+    let text = Paragraph::new(text)
+        .style(styles.text_style)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    let block = dialog_block(styles.border_type, styles.border_style)
+        .title_top(Line::styled(" Error ", styles.widget_title_style))
+        .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+    let num_lines = text.line_count(block.inner(draw_area).width) as u16;
+
+    // Note the +2 is for the margin, and another +2 for border.
+    let [draw_area] = Layout::vertical([Constraint::Max(num_lines + 2 + 2)])
+        .flex(Flex::Center)
+        .areas(draw_area);
+    let [text_draw_area] = Layout::vertical([Constraint::Length(num_lines)])
+        .flex(Flex::Center)
+        .areas(block.inner(draw_area));
+
+    f.render_widget(block, draw_area);
+    f.render_widget(text, text_draw_area);
+}
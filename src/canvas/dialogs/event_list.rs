@@ -0,0 +1,108 @@
+//! A dialog listing recorded events, newest first.
+
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::data::EventLog, canvas::drawing_utils::dialog_block, options::config::style::Styles,
+    utils::general::format_age,
+};
+
+/// The event list dialog.
+#[derive(Debug, Default)]
+pub(crate) struct EventListDialog {
+    /// The scroll offset, if the dialog is open.
+    scroll: Option<usize>,
+    /// How many events fit in the dialog when it was last drawn.
+    page_height: usize,
+}
+
+impl EventListDialog {
+    pub fn open(&mut self) {
+        self.scroll = Some(0);
+    }
+
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.scroll.is_some()
+    }
+
+    /// Handle a key press, given the number of recorded events.
+    pub fn on_key_event(&mut self, event: KeyEvent, num_events: usize) {
+        let Some(scroll) = &mut self.scroll else {
+            return;
+        };
+        let last = num_events.saturating_sub(1);
+        let page = self.page_height.max(1);
+
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.scroll = None,
+            KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(last),
+            KeyCode::PageUp => *scroll = scroll.saturating_sub(page),
+            KeyCode::PageDown => *scroll = (*scroll + page).min(last),
+            KeyCode::Home | KeyCode::Char('g') => *scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => *scroll = last,
+            _ => {}
+        }
+    }
+
+    pub fn draw(&mut self, f: &mut Frame<'_>, draw_area: Rect, events: &EventLog, styles: &Styles) {
+        let Some(scroll) = &mut self.scroll else {
+            return;
+        };
+
+        let block = dialog_block(styles.border_type, styles.border_style)
+            .title_top(Line::styled(
+                format!(" Events ({}) ", events.events().len()),
+                styles.widget_title_style,
+            ))
+            .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+        let inner = block.inner(draw_area);
+        f.render_widget(block, draw_area);
+
+        self.page_height = usize::from(inner.height);
+        *scroll = (*scroll).min(events.events().len().saturating_sub(1));
+
+        let now = Instant::now();
+        let lines: Vec<Line<'_>> = if events.events().len() == 0 {
+            vec![Line::styled(
+                "No events have been recorded yet.",
+                styles.text_style,
+            )]
+        } else {
+            events
+                .events()
+                .rev()
+                .skip(*scroll)
+                .take(self.page_height)
+                .map(|event| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!(
+                                "{:>12}  ",
+                                format_age(now.saturating_duration_since(event.time))
+                            ),
+                            styles.text_style,
+                        ),
+                        Span::styled(
+                            format!("{:<10}", event.kind.as_str()),
+                            styles.table_header_style,
+                        ),
+                        Span::styled(event.description.as_str(), styles.text_style),
+                    ])
+                })
+                .collect()
+        };
+
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+}
@@ -19,7 +19,6 @@ use crate::{
         },
         drawing_utils::dialog_block,
     },
-    constants::{self, HELP_TEXT},
 };
 
 /// Append a highlighted match to `lines`, and return whether a match was found.
@@ -44,15 +43,10 @@ fn add_highlight_match<'a>(
 
 // TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
 impl Painter {
-    fn help_text_lines(&self, app_state: &App) -> Vec<Line<'_>> {
-        let mut lines: Vec<Line<'_>> = Vec::new();
+    fn help_text_lines<'a>(&self, help_text: &'a [Vec<String>], query: &str) -> Vec<Line<'a>> {
+        let mut lines: Vec<Line<'a>> = Vec::new();
 
-        let query = app_state
-            .help_dialog_state
-            .search_input_state
-            .current_query()
-            .trim()
-            .to_lowercase();
+        let query = query.trim().to_lowercase();
 
         let is_filtering = !query.is_empty();
         if is_filtering {
@@ -61,16 +55,16 @@ impl Painter {
                 .selected_text_style
                 .add_modifier(self.styles.table_header_style.add_modifier);
 
-            HELP_TEXT.iter().enumerate().for_each(|(itx, section)| {
+            help_text.iter().enumerate().for_each(|(itx, section)| {
                 let mut iter = section.iter();
 
                 if itx == 0 {
                     return;
                 }
 
-                let header_str = iter.next().copied();
+                let header_str = iter.next().map(String::as_str);
 
-                let mut header_line: Vec<Line<'_>> = Vec::new();
+                let mut header_line: Vec<Line<'a>> = Vec::new();
                 let header_matches = if let Some(h) = header_str {
                     add_highlight_match(
                         &query,
@@ -83,8 +77,8 @@ impl Painter {
                     false
                 };
 
-                let mut matched_body: Vec<Line<'_>> = Vec::new();
-                for &text in iter {
+                let mut matched_body: Vec<Line<'a>> = Vec::new();
+                for text in iter {
                     add_highlight_match(
                         &query,
                         text,
@@ -115,27 +109,27 @@ impl Painter {
                 }
             });
         } else {
-            HELP_TEXT.iter().enumerate().for_each(|(itx, section)| {
+            help_text.iter().enumerate().for_each(|(itx, section)| {
                 let mut iter = section.iter();
 
                 if itx == 0 {
-                    for &text in iter {
-                        lines.push(Line::from(Span::styled(text, self.styles.text_style)));
+                    for text in iter {
+                        lines.push(Line::from(Span::styled(
+                            text.as_str(),
+                            self.styles.text_style,
+                        )));
                     }
                     return;
                 }
 
-                let header_opt = iter.next();
-                let header_str = header_opt.copied();
-
-                if let Some(header) = header_str {
+                if let Some(header) = iter.next() {
                     lines.push(Line::from(Span::default()));
                     lines.push(Line::from(Span::styled(
-                        header,
+                        header.as_str(),
                         self.styles.table_header_style,
                     )));
                 }
-                for &text in iter {
+                for text in iter {
                     lines.push(Line::from(Span::styled(text, self.styles.text_style)));
                 }
             });
@@ -146,7 +140,14 @@ impl Painter {
 
     pub fn draw_help_dialog(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
         // TODO: We can reduce lines processed based on the known height.
-        let styled_help_text = self.help_text_lines(app_state);
+        let help_text = app_state.help_dialog_state.help_text.clone();
+        let styled_help_text = self.help_text_lines(
+            &help_text,
+            app_state
+                .help_dialog_state
+                .search_input_state
+                .current_query(),
+        );
 
         // Reserve one column on the right for the scroll bar.
         let block = dialog_block(self.styles.border_type, self.styles.border_style)
@@ -184,28 +185,26 @@ impl Painter {
                 .current_query()
                 .is_empty()
             {
-                constants::HELP_TEXT
-                    .iter()
-                    .enumerate()
-                    .for_each(|(itx, section)| {
-                        let mut buffer = 0;
-
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width as u16;
-                        });
-
-                        if itx == 0 {
-                            app_state.help_dialog_state.index_shortcuts[itx] = 0;
-                        } else {
-                            app_state.help_dialog_state.index_shortcuts[itx] =
-                                app_state.help_dialog_state.index_shortcuts[itx - 1]
-                                    + 1
-                                    + prev_section_len;
-                        }
-                        prev_section_len = section.len() as u16 + buffer;
-                        overflow_buffer += buffer;
+                help_text.iter().enumerate().for_each(|(itx, section)| {
+                    let mut buffer = 0;
+
+                    section.iter().for_each(|text_line| {
+                        buffer += UnicodeWidthStr::width(text_line.as_str()).saturating_sub(1)
+                            as u16
+                            / paragraph_width as u16;
                     });
+
+                    if itx == 0 {
+                        app_state.help_dialog_state.index_shortcuts[itx] = 0;
+                    } else {
+                        app_state.help_dialog_state.index_shortcuts[itx] =
+                            app_state.help_dialog_state.index_shortcuts[itx - 1]
+                                + 1
+                                + prev_section_len;
+                    }
+                    prev_section_len = section.len() as u16 + buffer;
+                    overflow_buffer += buffer;
+                });
             } else {
                 for line in &styled_help_text {
                     let width: usize = line
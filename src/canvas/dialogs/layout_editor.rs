@@ -0,0 +1,1034 @@
+//! A dialog to interactively edit a layout and save it back to the config file.
+
+use std::{fs, io, path::Path};
+
+use anyhow::Context;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use toml_edit::{DocumentMut, Item, Table};
+use tui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::layout_manager::{BottomWidgetType, WidgetDirection},
+    canvas::drawing_utils::dialog_block,
+    options::config::{
+        layout::{FinalWidget, LayoutSection, Row, RowChildren, update_rows},
+        style::Styles,
+    },
+};
+
+/// The widget types that can be cycled through, in order.
+const WIDGET_TYPES: &[&str] = &[
+    "cpu",
+    "mem",
+    "net",
+    "proc",
+    "temp",
+    "temp_graph",
+    "disk",
+    #[cfg(feature = "battery")]
+    "batt",
+    "cpu_heatmap",
+    "custom",
+    "empty",
+];
+
+const HELP_TEXT: &str = "Arrows/hjkl select  v/s split right/below  d delete  x swap  t/T change type  \
+                         H/L width  K/J height  w save";
+
+/// The position of a widget in the layout, as indices into the row, the row's child,
+/// and the widget within that child if it is a column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct WidgetPosition {
+    row: usize,
+    child: usize,
+    widget: usize,
+}
+
+/// A boundary between two siblings that can be dragged to resize them. Each
+/// boundary is between the given index and the one after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Boundary {
+    Row(usize),
+    Child(usize, usize),
+    Widget(usize, usize, usize),
+}
+
+/// Where each part of the layout was last drawn, for mouse handling.
+#[derive(Debug, Default)]
+struct DrawLocs {
+    rows: Vec<Rect>,
+    children: Vec<Vec<Rect>>,
+    widgets: Vec<Vec<Vec<Rect>>>,
+}
+
+#[derive(Debug)]
+struct LayoutEditorState {
+    layout_index: usize,
+    section: LayoutSection,
+    rows: Vec<Row>,
+    selected: WidgetPosition,
+    marked: Option<WidgetPosition>,
+    drag: Option<Boundary>,
+    is_modified: bool,
+    is_confirming_discard: bool,
+    status: Option<String>,
+    locs: DrawLocs,
+}
+
+fn new_widget() -> FinalWidget {
+    FinalWidget {
+        ratio: None,
+        widget_type: "empty".to_string(),
+        default: None,
+        name: None,
+    }
+}
+
+fn column_len(child: &RowChildren) -> usize {
+    match child {
+        RowChildren::Widget(_) => 1,
+        RowChildren::Col { child, .. } => child.len(),
+    }
+}
+
+fn adjust_ratio(ratio: &mut Option<u16>, delta: i16) {
+    *ratio = Some(ratio.unwrap_or(1).saturating_add_signed(delta).max(1));
+}
+
+/// Resize the sibling at `index` to `new_size`, taking the space from the sibling after it.
+/// The current draw sizes are used as the new ratios so the other siblings keep their size.
+fn resize_siblings(sizes: &[u16], index: usize, new_size: u16) -> Vec<u16> {
+    fn gcd(a: u16, b: u16) -> u16 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    let mut sizes: Vec<u16> = sizes.iter().map(|size| (*size).max(1)).collect();
+    let total = sizes[index] + sizes[index + 1];
+    sizes[index] = new_size.clamp(1, total - 1);
+    sizes[index + 1] = total - sizes[index];
+
+    let divisor = sizes.iter().copied().fold(0, gcd).max(1);
+    sizes.iter().map(|size| size / divisor).collect()
+}
+
+impl LayoutEditorState {
+    fn children(&self, row: usize) -> &[RowChildren] {
+        self.rows
+            .get(row)
+            .and_then(|row| row.child.as_deref())
+            .unwrap_or_default()
+    }
+
+    fn widget(&self, pos: WidgetPosition) -> Option<&FinalWidget> {
+        match self.children(pos.row).get(pos.child)? {
+            RowChildren::Widget(widget) => Some(widget),
+            RowChildren::Col { child, .. } => child.get(pos.widget),
+        }
+    }
+
+    fn widget_mut(&mut self, pos: WidgetPosition) -> Option<&mut FinalWidget> {
+        match self
+            .rows
+            .get_mut(pos.row)?
+            .child
+            .as_mut()?
+            .get_mut(pos.child)?
+        {
+            RowChildren::Widget(widget) => Some(widget),
+            RowChildren::Col { child, .. } => child.get_mut(pos.widget),
+        }
+    }
+
+    fn widget_count(&self) -> usize {
+        (0..self.rows.len())
+            .flat_map(|row| self.children(row))
+            .map(column_len)
+            .sum()
+    }
+
+    fn set_modified(&mut self) {
+        self.is_modified = true;
+        self.marked = None;
+        self.clamp_selection();
+    }
+
+    fn clamp_selection(&mut self) {
+        let pos = &mut self.selected;
+        pos.row = pos.row.min(self.rows.len().saturating_sub(1));
+
+        let children = self
+            .rows
+            .get(pos.row)
+            .and_then(|row| row.child.as_deref())
+            .unwrap_or_default();
+        pos.child = pos.child.min(children.len().saturating_sub(1));
+        pos.widget = pos.widget.min(
+            children
+                .get(pos.child)
+                .map_or(0, column_len)
+                .saturating_sub(1),
+        );
+    }
+
+    fn move_selection(&mut self, direction: WidgetDirection) {
+        let pos = self.selected;
+        let column_len = self.children(pos.row).get(pos.child).map_or(0, column_len);
+
+        match direction {
+            WidgetDirection::Left => {
+                if pos.child > 0 {
+                    self.selected.child -= 1;
+                    self.selected.widget = 0;
+                }
+            }
+            WidgetDirection::Right => {
+                if pos.child + 1 < self.children(pos.row).len() {
+                    self.selected.child += 1;
+                    self.selected.widget = 0;
+                }
+            }
+            WidgetDirection::Up => {
+                if pos.widget > 0 {
+                    self.selected.widget -= 1;
+                } else if pos.row > 0 {
+                    self.selected.row -= 1;
+                    self.selected.widget = usize::MAX;
+                }
+            }
+            WidgetDirection::Down => {
+                if pos.widget + 1 < column_len {
+                    self.selected.widget += 1;
+                } else if pos.row + 1 < self.rows.len() {
+                    self.selected.row += 1;
+                    self.selected.widget = 0;
+                }
+            }
+        }
+
+        self.clamp_selection();
+    }
+
+    /// Add a new widget to the right of the selected one.
+    fn split_right(&mut self) {
+        let pos = self.selected;
+        let Some(row) = self.rows.get_mut(pos.row) else {
+            return;
+        };
+
+        let children = row.child.get_or_insert_with(Vec::new);
+        let index = if children.is_empty() {
+            0
+        } else {
+            pos.child + 1
+        };
+        children.insert(index, RowChildren::Widget(new_widget()));
+
+        self.selected = WidgetPosition {
+            row: pos.row,
+            child: index,
+            widget: 0,
+        };
+        self.set_modified();
+    }
+
+    /// Add a new widget below the selected one, turning it into a column if needed.
+    fn split_below(&mut self) {
+        let pos = self.selected;
+        let Some(target) = self
+            .rows
+            .get_mut(pos.row)
+            .and_then(|row| row.child.as_mut())
+            .and_then(|children| children.get_mut(pos.child))
+        else {
+            return;
+        };
+
+        match target {
+            RowChildren::Widget(widget) => {
+                let mut widget = widget.clone();
+                let ratio = widget.ratio.take();
+                *target = RowChildren::Col {
+                    ratio,
+                    child: vec![widget, new_widget()],
+                };
+                self.selected.widget = 1;
+            }
+            RowChildren::Col { child, .. } => {
+                child.insert(pos.widget + 1, new_widget());
+                self.selected.widget = pos.widget + 1;
+            }
+        }
+
+        self.set_modified();
+    }
+
+    fn remove(&mut self) {
+        if self.widget(self.selected).is_none() {
+            return;
+        }
+
+        if self.widget_count() <= 1 {
+            self.status = Some("A layout needs at least one widget.".to_string());
+            return;
+        }
+
+        let pos = self.selected;
+        let Some(children) = self.rows[pos.row].child.as_mut() else {
+            return;
+        };
+
+        match &mut children[pos.child] {
+            RowChildren::Widget(_) => {
+                children.remove(pos.child);
+            }
+            RowChildren::Col { ratio, child } => {
+                child.remove(pos.widget);
+
+                // A column with only one widget left is turned back into that widget.
+                if child.len() == 1 {
+                    let mut widget = child.remove(0);
+                    widget.ratio = *ratio;
+                    children[pos.child] = RowChildren::Widget(widget);
+                } else if child.is_empty() {
+                    children.remove(pos.child);
+                }
+            }
+        }
+
+        if children.is_empty() {
+            self.rows.remove(pos.row);
+        }
+
+        self.set_modified();
+    }
+
+    /// Mark the selected widget, or swap it with the marked widget if there is one.
+    fn mark_or_swap(&mut self) {
+        let selected = self.selected;
+        match self.marked {
+            None => {
+                if self.widget(selected).is_some() {
+                    self.marked = Some(selected);
+                }
+            }
+            Some(marked) if marked == selected => self.marked = None,
+            Some(marked) => {
+                if let (Some(a), Some(b)) =
+                    (self.widget(marked).cloned(), self.widget(selected).cloned())
+                {
+                    // Widgets swap places, but the space they take up stays the same.
+                    for (pos, other) in [(marked, b), (selected, a)] {
+                        if let Some(widget) = self.widget_mut(pos) {
+                            *widget = FinalWidget {
+                                ratio: widget.ratio,
+                                ..other
+                            };
+                        }
+                    }
+                    self.set_modified();
+                }
+            }
+        }
+    }
+
+    fn cycle_type(&mut self, forward: bool) {
+        let Some(widget) = self.widget_mut(self.selected) else {
+            return;
+        };
+
+        let current = widget.widget_type.parse::<BottomWidgetType>().ok();
+        let index = WIDGET_TYPES
+            .iter()
+            .position(|t| t.parse::<BottomWidgetType>().ok() == current);
+        let next = match index {
+            Some(index) if forward => (index + 1) % WIDGET_TYPES.len(),
+            Some(index) => (index + WIDGET_TYPES.len() - 1) % WIDGET_TYPES.len(),
+            None => 0,
+        };
+
+        widget.widget_type = WIDGET_TYPES[next].to_string();
+        if widget.widget_type != "custom" {
+            widget.name = None;
+        }
+
+        self.is_modified = true;
+    }
+
+    fn resize_width(&mut self, delta: i16) {
+        let pos = self.selected;
+        let Some(target) = self
+            .rows
+            .get_mut(pos.row)
+            .and_then(|row| row.child.as_mut())
+            .and_then(|children| children.get_mut(pos.child))
+        else {
+            return;
+        };
+
+        match target {
+            RowChildren::Widget(widget) => adjust_ratio(&mut widget.ratio, delta),
+            RowChildren::Col { ratio, .. } => adjust_ratio(ratio, delta),
+        }
+        self.is_modified = true;
+    }
+
+    fn resize_height(&mut self, delta: i16) {
+        let pos = self.selected;
+        let in_column = self
+            .children(pos.row)
+            .get(pos.child)
+            .is_some_and(|child| column_len(child) > 1);
+
+        if in_column {
+            if let Some(widget) = self.widget_mut(pos) {
+                adjust_ratio(&mut widget.ratio, delta);
+            }
+        } else if let Some(row) = self.rows.get_mut(pos.row) {
+            adjust_ratio(&mut row.ratio, delta);
+        }
+        self.is_modified = true;
+    }
+
+    /// Find the boundary at the given location, if any.
+    fn boundary_at(&self, x: u16, y: u16) -> Option<Boundary> {
+        fn on_edge(start: u16, end: u16, pos: u16) -> bool {
+            pos + 1 == end || pos == start
+        }
+
+        let locs = &self.locs;
+        for (row, pair) in locs.rows.windows(2).enumerate() {
+            if (pair[0].x..pair[0].right()).contains(&x) && on_edge(pair[1].y, pair[0].bottom(), y)
+            {
+                return Some(Boundary::Row(row));
+            }
+        }
+
+        for (row, children) in locs.children.iter().enumerate() {
+            for (child, pair) in children.windows(2).enumerate() {
+                if (pair[0].y..pair[0].bottom()).contains(&y)
+                    && on_edge(pair[1].x, pair[0].right(), x)
+                {
+                    return Some(Boundary::Child(row, child));
+                }
+            }
+        }
+
+        for (row, children) in locs.widgets.iter().enumerate() {
+            for (child, widgets) in children.iter().enumerate() {
+                for (widget, pair) in widgets.windows(2).enumerate() {
+                    if (pair[0].x..pair[0].right()).contains(&x)
+                        && on_edge(pair[1].y, pair[0].bottom(), y)
+                    {
+                        return Some(Boundary::Widget(row, child, widget));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn drag_boundary(&mut self, boundary: Boundary, x: u16, y: u16) {
+        match boundary {
+            Boundary::Row(index) => {
+                let Some(start) = self.locs.rows.get(index) else {
+                    return;
+                };
+                let sizes: Vec<u16> = self.locs.rows.iter().map(|loc| loc.height).collect();
+                let ratios = resize_siblings(&sizes, index, (y + 1).saturating_sub(start.y));
+                for (row, ratio) in self.rows.iter_mut().zip(ratios) {
+                    row.ratio = Some(ratio);
+                }
+            }
+            Boundary::Child(row, index) => {
+                let Some(locs) = self.locs.children.get(row) else {
+                    return;
+                };
+                let sizes: Vec<u16> = locs.iter().map(|loc| loc.width).collect();
+                let ratios = resize_siblings(&sizes, index, (x + 1).saturating_sub(locs[index].x));
+                let children = self.rows[row].child.iter_mut().flatten();
+                for (child, ratio) in children.zip(ratios) {
+                    match child {
+                        RowChildren::Widget(widget) => widget.ratio = Some(ratio),
+                        RowChildren::Col {
+                            ratio: col_ratio, ..
+                        } => *col_ratio = Some(ratio),
+                    }
+                }
+            }
+            Boundary::Widget(row, child, index) => {
+                let Some(locs) = self.locs.widgets.get(row).and_then(|c| c.get(child)) else {
+                    return;
+                };
+                let sizes: Vec<u16> = locs.iter().map(|loc| loc.height).collect();
+                let ratios = resize_siblings(&sizes, index, (y + 1).saturating_sub(locs[index].y));
+                if let Some(RowChildren::Col { child: widgets, .. }) = self.rows[row]
+                    .child
+                    .as_mut()
+                    .and_then(|children| children.get_mut(child))
+                {
+                    for (widget, ratio) in widgets.iter_mut().zip(ratios) {
+                        widget.ratio = Some(ratio);
+                    }
+                }
+            }
+        }
+
+        self.is_modified = true;
+    }
+
+    fn widget_at(&self, x: u16, y: u16) -> Option<WidgetPosition> {
+        let pos = tui::layout::Position::new(x, y);
+        for (row, children) in self.locs.widgets.iter().enumerate() {
+            for (child, widgets) in children.iter().enumerate() {
+                for (widget, loc) in widgets.iter().enumerate() {
+                    if loc.contains(pos) {
+                        return Some(WidgetPosition { row, child, widget });
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Write the rows of a layout to the config file at `path`, keeping everything else
+/// in the file as it is.
+fn write_layout(path: &Path, section: &LayoutSection, rows: &[Row]) -> anyhow::Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let mut document: DocumentMut = contents.parse()?;
+
+    let table = match section {
+        LayoutSection::Row => document.as_table_mut(),
+        LayoutSection::Named(name) => {
+            let mut implicit_table = Table::new();
+            implicit_table.set_implicit(true);
+
+            document
+                .entry("layout")
+                .or_insert_with(|| Item::Table(implicit_table.clone()))
+                .as_table_mut()
+                .context("'layout' is not a table")?
+                .entry(name)
+                .or_insert_with(|| Item::Table(implicit_table))
+                .as_table_mut()
+                .with_context(|| format!("'layout.{name}' is not a table"))?
+        }
+    };
+
+    // Update the old rows in place, so their comments and formatting are kept.
+    update_rows(table, rows);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, document.to_string())?;
+
+    Ok(())
+}
+
+/// The layout editor dialog.
+#[derive(Debug, Default)]
+pub(crate) struct LayoutEditor {
+    state: Option<LayoutEditorState>,
+}
+
+impl LayoutEditor {
+    /// Open the editor for the layout at `layout_index`.
+    pub fn open(&mut self, layout_index: usize, section: LayoutSection, rows: Vec<Row>) {
+        self.state = Some(LayoutEditorState {
+            layout_index,
+            section,
+            rows,
+            selected: WidgetPosition::default(),
+            marked: None,
+            drag: None,
+            is_modified: false,
+            is_confirming_discard: false,
+            status: None,
+            locs: DrawLocs::default(),
+        });
+    }
+
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Handle a key press. If the layout was saved, this returns the index of the
+    /// layout along with its new rows.
+    pub fn on_key_event(
+        &mut self, event: KeyEvent, config_path: Option<&Path>,
+    ) -> Option<(usize, Vec<Row>)> {
+        let state = self.state.as_mut()?;
+        let is_confirming_discard = std::mem::take(&mut state.is_confirming_discard);
+        state.status = None;
+
+        if event.modifiers == KeyModifiers::CONTROL {
+            if event.code == KeyCode::Char('s') {
+                return self.save(config_path);
+            }
+            return None;
+        }
+
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if state.is_modified && !is_confirming_discard {
+                    state.is_confirming_discard = true;
+                    state.status =
+                        Some("There are unsaved changes, press Esc again to discard them.".into());
+                } else {
+                    self.state = None;
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => state.move_selection(WidgetDirection::Left),
+            KeyCode::Right | KeyCode::Char('l') => state.move_selection(WidgetDirection::Right),
+            KeyCode::Up | KeyCode::Char('k') => state.move_selection(WidgetDirection::Up),
+            KeyCode::Down | KeyCode::Char('j') => state.move_selection(WidgetDirection::Down),
+            KeyCode::Char('v') => state.split_right(),
+            KeyCode::Char('s') => state.split_below(),
+            KeyCode::Char('d') | KeyCode::Delete => state.remove(),
+            KeyCode::Char('x') => state.mark_or_swap(),
+            KeyCode::Char('t') => state.cycle_type(true),
+            KeyCode::Char('T') => state.cycle_type(false),
+            KeyCode::Char('H') => state.resize_width(-1),
+            KeyCode::Char('L') => state.resize_width(1),
+            KeyCode::Char('K') => state.resize_height(-1),
+            KeyCode::Char('J') => state.resize_height(1),
+            KeyCode::Char('w') => return self.save(config_path),
+            _ => {}
+        }
+
+        None
+    }
+
+    fn save(&mut self, config_path: Option<&Path>) -> Option<(usize, Vec<Row>)> {
+        let state = self.state.as_mut()?;
+        let Some(path) = config_path else {
+            state.status = Some("There is no config file to save the layout to.".into());
+            return None;
+        };
+
+        match write_layout(path, &state.section, &state.rows) {
+            Ok(()) => {
+                state.is_modified = false;
+                state.status = Some(format!("Saved the layout to '{}'.", path.display()));
+                Some((state.layout_index, state.rows.clone()))
+            }
+            Err(err) => {
+                state.status = Some(format!("Couldn't save the layout: {err}"));
+                None
+            }
+        }
+    }
+
+    /// Handle a mouse press, either starting to drag a boundary or selecting a widget.
+    pub fn on_mouse_down(&mut self, x: u16, y: u16) {
+        let Some(state) = &mut self.state else {
+            return;
+        };
+
+        state.drag = state.boundary_at(x, y);
+        if state.drag.is_none() {
+            if let Some(pos) = state.widget_at(x, y) {
+                state.selected = pos;
+            }
+        }
+    }
+
+    pub fn on_mouse_drag(&mut self, x: u16, y: u16) {
+        if let Some(state) = &mut self.state {
+            if let Some(boundary) = state.drag {
+                state.drag_boundary(boundary, x, y);
+            }
+        }
+    }
+
+    pub fn on_mouse_up(&mut self) {
+        if let Some(state) = &mut self.state {
+            state.drag = None;
+        }
+    }
+
+    pub fn draw(&mut self, f: &mut Frame<'_>, draw_area: Rect, styles: &Styles) {
+        let Some(state) = &mut self.state else {
+            return;
+        };
+
+        let modified = if state.is_modified { " (modified)" } else { "" };
+        let block = dialog_block(styles.border_type, styles.border_style)
+            .title_top(Line::styled(
+                format!(" Layout editor: {}{modified} ", state.section.name()),
+                styles.widget_title_style,
+            ))
+            .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+        let inner = block.inner(draw_area);
+        f.render_widget(block, draw_area);
+
+        let [preview_area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(inner);
+
+        let mut locs = DrawLocs::default();
+        let row_locs = Layout::vertical(
+            state
+                .rows
+                .iter()
+                .map(|row| Constraint::Fill(row.ratio.unwrap_or(1))),
+        )
+        .split(preview_area);
+
+        for (row_index, (row, row_loc)) in state.rows.iter().zip(row_locs.iter()).enumerate() {
+            let children = row.child.as_deref().unwrap_or_default();
+            let child_locs = Layout::horizontal(children.iter().map(|child| match child {
+                RowChildren::Widget(widget) => Constraint::Fill(widget.ratio.unwrap_or(1)),
+                RowChildren::Col { ratio, .. } => Constraint::Fill(ratio.unwrap_or(1)),
+            }))
+            .split(*row_loc);
+
+            let mut row_widget_locs = Vec::with_capacity(children.len());
+            for (child_index, (child, child_loc)) in
+                children.iter().zip(child_locs.iter()).enumerate()
+            {
+                let widgets = match child {
+                    RowChildren::Widget(widget) => std::slice::from_ref(widget),
+                    RowChildren::Col { child, .. } => child.as_slice(),
+                };
+                let widget_locs = if let RowChildren::Col { .. } = child {
+                    Layout::vertical(
+                        widgets
+                            .iter()
+                            .map(|widget| Constraint::Fill(widget.ratio.unwrap_or(1))),
+                    )
+                    .split(*child_loc)
+                    .to_vec()
+                } else {
+                    vec![*child_loc]
+                };
+
+                for (widget_index, (widget, widget_loc)) in
+                    widgets.iter().zip(widget_locs.iter()).enumerate()
+                {
+                    let pos = WidgetPosition {
+                        row: row_index,
+                        child: child_index,
+                        widget: widget_index,
+                    };
+                    let is_selected = pos == state.selected;
+                    let is_marked = Some(pos) == state.marked;
+
+                    let title_style = if is_marked {
+                        styles.selected_text_style
+                    } else {
+                        styles.widget_title_style
+                    };
+                    let title = if is_marked {
+                        format!(" {} (swap) ", widget.widget_type)
+                    } else {
+                        format!(" {} ", widget.widget_type)
+                    };
+                    let border_style = if is_selected {
+                        styles.highlighted_border_style
+                    } else {
+                        styles.border_style
+                    };
+
+                    let description = match &widget.name {
+                        Some(name) => name.clone(),
+                        None => widget
+                            .widget_type
+                            .parse::<BottomWidgetType>()
+                            .map(|widget_type| widget_type.get_pretty_name().to_string())
+                            .unwrap_or_default(),
+                    };
+
+                    f.render_widget(
+                        Paragraph::new(Span::styled(description, styles.text_style))
+                            .alignment(Alignment::Center)
+                            .block(
+                                dialog_block(styles.border_type, border_style)
+                                    .title_top(Line::styled(title, title_style)),
+                            ),
+                        *widget_loc,
+                    );
+                }
+
+                row_widget_locs.push(widget_locs);
+            }
+
+            locs.rows.push(*row_loc);
+            locs.children.push(child_locs.to_vec());
+            locs.widgets.push(row_widget_locs);
+        }
+
+        state.locs = locs;
+
+        let status = state
+            .status
+            .as_deref()
+            .unwrap_or("Drag the borders between widgets with the mouse to resize them.");
+        f.render_widget(
+            Paragraph::new(vec![
+                Line::styled(HELP_TEXT, styles.text_style),
+                Line::styled(status, styles.selected_text_style),
+            ]),
+            help_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rows(layout: &str) -> Vec<Row> {
+        toml_edit::de::from_str::<crate::options::Config>(layout)
+            .unwrap()
+            .row
+            .unwrap()
+    }
+
+    fn state(layout: &str) -> LayoutEditorState {
+        let mut editor = LayoutEditor::default();
+        editor.open(0, LayoutSection::Row, rows(layout));
+        editor.state.unwrap()
+    }
+
+    fn widget_types(state: &LayoutEditorState) -> Vec<Vec<Vec<String>>> {
+        state
+            .rows
+            .iter()
+            .map(|row| {
+                row.child
+                    .iter()
+                    .flatten()
+                    .map(|child| match child {
+                        RowChildren::Widget(widget) => vec![widget.widget_type.clone()],
+                        RowChildren::Col { child, .. } => {
+                            child.iter().map(|w| w.widget_type.clone()).collect()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    const LAYOUT: &str = r#"
+        [[row]]
+          [[row.child]]
+            type="cpu"
+        [[row]]
+          [[row.child]]
+            type="mem"
+          [[row.child]]
+            type="proc"
+    "#;
+
+    #[test]
+    fn split_and_remove() {
+        let mut state = state(LAYOUT);
+
+        state.split_right();
+        assert_eq!(state.selected.child, 1);
+        state.split_below();
+        assert_eq!(
+            widget_types(&state)[0],
+            vec![
+                vec!["cpu".to_string()],
+                vec!["empty".into(), "empty".into()]
+            ]
+        );
+
+        // Removing a widget from a column of two turns it back into a widget.
+        state.remove();
+        assert_eq!(
+            widget_types(&state)[0],
+            vec![vec!["cpu".to_string()], vec!["empty".into()]]
+        );
+
+        state.remove();
+        state.remove();
+        assert_eq!(state.rows.len(), 1);
+        assert!(state.is_modified);
+    }
+
+    #[test]
+    fn keeps_last_widget() {
+        let mut state = state("[[row]]\n[[row.child]]\ntype=\"cpu\"");
+        state.remove();
+        assert_eq!(state.widget_count(), 1);
+        assert!(state.status.is_some());
+    }
+
+    #[test]
+    fn move_and_swap() {
+        let mut state = state(LAYOUT);
+
+        state.mark_or_swap();
+        state.move_selection(WidgetDirection::Down);
+        state.move_selection(WidgetDirection::Right);
+        assert_eq!(
+            state.selected,
+            WidgetPosition {
+                row: 1,
+                child: 1,
+                widget: 0
+            }
+        );
+
+        state.mark_or_swap();
+        assert_eq!(
+            widget_types(&state),
+            vec![
+                vec![vec!["proc".to_string()]],
+                vec![vec!["mem".to_string()], vec!["cpu".to_string()]]
+            ]
+        );
+        assert!(state.marked.is_none());
+    }
+
+    #[test]
+    fn cycle_type() {
+        let mut state = state(LAYOUT);
+        state.cycle_type(true);
+        assert_eq!(widget_types(&state)[0][0][0], "mem");
+
+        state.cycle_type(false);
+        state.cycle_type(false);
+        assert_eq!(widget_types(&state)[0][0][0], "empty");
+    }
+
+    #[test]
+    fn resize() {
+        let mut state = state(LAYOUT);
+        state.resize_height(2);
+        assert_eq!(state.rows[0].ratio, Some(3));
+
+        state.resize_height(-5);
+        assert_eq!(state.rows[0].ratio, Some(1));
+
+        assert_eq!(resize_siblings(&[10, 10, 20], 0, 5), vec![1, 3, 4]);
+        assert_eq!(resize_siblings(&[10, 10], 0, 30), vec![19, 1]);
+    }
+
+    #[test]
+    fn write_keeps_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(
+            &path,
+            "# My config.\n[flags]\n# Keep this.\nrate = \"1s\"\n\n# My layout.\n[[row]]\n[[row.child]]\ntype=\"cpu\"\n",
+        )
+        .unwrap();
+
+        write_layout(&path, &LayoutSection::Row, &rows(LAYOUT)).unwrap();
+        write_layout(
+            &path,
+            &LayoutSection::Named("other".to_string()),
+            &rows(LAYOUT),
+        )
+        .unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+
+        assert!(written.starts_with("# My config.\n[flags]\n# Keep this.\nrate = \"1s\"\n"));
+        assert!(written.contains("# My layout.\n[[row]]"));
+        assert!(written.contains("[[layout.other.row]]"));
+
+        let config: crate::options::Config = toml_edit::de::from_str(&written).unwrap();
+        assert_eq!(config.row.unwrap().len(), 2);
+        assert_eq!(
+            config.layout.unwrap()["other"].row.as_ref().unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn write_keeps_nested_comments() {
+        const COMMENTED_LAYOUT: &str = r#"# My layout.
+[[row]]
+  ratio = 2 # Tall.
+  [[row.child]]
+    # The CPU graph.
+    type = "cpu"
+
+# The bottom row.
+[[row]]
+  [[row.child]]
+    ratio = 1
+    # A column.
+    [[row.child.child]]
+      # Memory.
+      type = "mem"
+    [[row.child.child]]
+      type = "proc" # Processes.
+  [[row.child]]
+    type = "net"
+
+# Other settings.
+[flags]
+rate = "1s"
+"#;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(&path, COMMENTED_LAYOUT).unwrap();
+
+        let mut rows = rows(COMMENTED_LAYOUT);
+        let Some(RowChildren::Col { child, .. }) = rows[1]
+            .child
+            .as_mut()
+            .and_then(|children| children.first_mut())
+        else {
+            panic!("the second row should start with a column");
+        };
+        child[1].widget_type = "temp".to_string();
+        rows[1].child.as_mut().unwrap().pop();
+        rows.push(rows[0].clone());
+
+        write_layout(&path, &LayoutSection::Row, &rows).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(
+            written,
+            r#"# My layout.
+[[row]]
+  ratio = 2 # Tall.
+  [[row.child]]
+    # The CPU graph.
+    type = "cpu"
+
+# The bottom row.
+[[row]]
+  [[row.child]]
+    ratio = 1
+    # A column.
+    [[row.child.child]]
+      # Memory.
+      type = "mem"
+    [[row.child.child]]
+      type = "temp" # Processes.
+
+[[row]]
+ratio = 2
+
+[[row.child]]
+type = "cpu"
+
+# Other settings.
+[flags]
+rate = "1s"
+"#
+        );
+    }
+}
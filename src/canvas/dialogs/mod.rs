@@ -1,2 +1,8 @@
+pub mod error_dialog;
+pub mod event_list;
 pub mod help_dialog;
+pub mod layout_editor;
+pub mod process_affinity_dialog;
+pub mod process_columns_dialog;
 pub mod process_kill_dialog;
+pub mod process_priority_dialog;
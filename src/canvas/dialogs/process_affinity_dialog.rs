@@ -0,0 +1,335 @@
+//! A dialog box to change the CPU affinity of processes.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::{Line, Span, Text},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::{
+    canvas::{dialogs::error_dialog::draw_error_dialog, drawing_utils::dialog_block},
+    collection::processes::Pid,
+    options::config::style::Styles,
+    widgets::{ProcWidgetData, ProcessTargets},
+};
+
+#[cfg(target_os = "linux")]
+const HELP_TEXT: &str = "Arrows move  Space toggle  a all/none  Enter apply";
+
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+struct EditingState {
+    targets: ProcessTargets,
+    /// Whether each CPU is allowed.
+    cpus: Vec<bool>,
+    /// The CPU under the cursor.
+    cursor: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl EditingState {
+    /// Move the cursor by `delta` CPUs, if that stays within the grid.
+    fn move_cursor(&mut self, delta: isize) {
+        if let Some(cursor) = self.cursor.checked_add_signed(delta) {
+            if cursor < self.cpus.len() {
+                self.cursor = cursor;
+            }
+        }
+    }
+
+    /// Allow every CPU, or none if they already all are.
+    fn toggle_all(&mut self) {
+        let all = !self.cpus.iter().all(|allowed| *allowed);
+        self.cpus.fill(all);
+    }
+
+    fn selected_cpus(&self) -> Vec<usize> {
+        self.cpus
+            .iter()
+            .enumerate()
+            .filter_map(|(cpu, allowed)| allowed.then_some(cpu))
+            .collect()
+    }
+}
+
+/// The current state of the process affinity dialog.
+#[derive(Default, Debug)]
+enum ProcessAffinityDialogState {
+    #[default]
+    NotEnabled,
+    #[cfg(target_os = "linux")]
+    Editing(EditingState),
+    Error {
+        process_name: String,
+        pid: Option<Pid>,
+        err: String,
+    },
+}
+
+/// Process CPU affinity dialog.
+#[derive(Default, Debug)]
+pub(crate) struct ProcessAffinityDialog {
+    state: ProcessAffinityDialogState,
+    /// How many CPUs fit in a row when the dialog was last drawn.
+    columns: usize,
+}
+
+impl ProcessAffinityDialog {
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        !(matches!(self.state, ProcessAffinityDialogState::NotEnabled))
+    }
+
+    /// Open the dialog for the given processes, where `process` is the entry
+    /// of the first one. The current affinity is read from the first process,
+    /// as it is only collected while the affinity column is shown.
+    pub fn open(&mut self, process: &ProcWidgetData, targets: ProcessTargets) {
+        if targets.processes.is_empty() {
+            self.state = ProcessAffinityDialogState::Error {
+                process_name: process.id.to_string(),
+                pid: None,
+                err: "No PIDs found for the given process name.".into(),
+            };
+            return;
+        }
+
+        cfg_select! {
+            target_os = "linux" => {
+                let num_cpus = crate::utils::process_priority::num_cpus();
+                let affinity = targets
+                    .processes
+                    .first()
+                    .and_then(|(_, pid)| crate::utils::process_priority::get_affinity(*pid));
+                let cpus = match &affinity {
+                    Some(allowed) => {
+                        let len = allowed.iter().map(|cpu| cpu + 1).fold(num_cpus, usize::max);
+                        let mut cpus = vec![false; len];
+                        for &cpu in allowed {
+                            cpus[cpu] = true;
+                        }
+                        cpus
+                    }
+                    None => vec![true; num_cpus],
+                };
+
+                self.state = ProcessAffinityDialogState::Editing(EditingState {
+                    targets,
+                    cpus,
+                    cursor: 0,
+                });
+            }
+            _ => {
+                self.state = ProcessAffinityDialogState::Error {
+                    process_name: targets.name().to_string(),
+                    pid: None,
+                    err: "Changing the CPU affinity of processes is not supported on this platform."
+                        .into(),
+                };
+            }
+        }
+    }
+
+    /// Handle a key press.
+    pub fn on_key_event(&mut self, event: KeyEvent) {
+        match &mut self.state {
+            ProcessAffinityDialogState::NotEnabled => {}
+            #[cfg(target_os = "linux")]
+            ProcessAffinityDialogState::Editing(state) => {
+                let columns = self.columns.max(1) as isize;
+
+                match event.code {
+                    KeyCode::Esc | KeyCode::Char('q') => self.state = Default::default(),
+                    KeyCode::Left | KeyCode::Char('h') => state.move_cursor(-1),
+                    KeyCode::Right | KeyCode::Char('l') => state.move_cursor(1),
+                    KeyCode::Up | KeyCode::Char('k') => state.move_cursor(-columns),
+                    KeyCode::Down | KeyCode::Char('j') => state.move_cursor(columns),
+                    KeyCode::Char(' ') => state.cpus[state.cursor] = !state.cpus[state.cursor],
+                    KeyCode::Char('a') => state.toggle_all(),
+                    KeyCode::Enter => self.apply(),
+                    _ => {}
+                }
+            }
+            ProcessAffinityDialogState::Error { .. } => {
+                if matches!(
+                    event.code,
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')
+                ) {
+                    self.state = Default::default();
+                }
+            }
+        }
+    }
+
+    /// Apply the affinity to every process, stopping at the first error. A
+    /// process must be allowed to run on at least one CPU, so nothing is done
+    /// if none are selected.
+    #[cfg(target_os = "linux")]
+    fn apply(&mut self) {
+        let ProcessAffinityDialogState::Editing(state) = &self.state else {
+            return;
+        };
+
+        let cpus = state.selected_cpus();
+        if cpus.is_empty() {
+            return;
+        }
+
+        let ProcessAffinityDialogState::Editing(state) = std::mem::take(&mut self.state) else {
+            return;
+        };
+
+        for pid in state.targets.pids() {
+            if let Err(err) = crate::utils::process_priority::set_affinity(pid, &cpus) {
+                self.state = ProcessAffinityDialogState::Error {
+                    process_name: state.targets.name_of(pid).to_string(),
+                    pid: Some(pid),
+                    err: err.to_string(),
+                };
+                return;
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn draw_editing(
+        f: &mut Frame<'_>, draw_area: Rect, styles: &Styles, state: &EditingState,
+        columns: &mut usize,
+    ) {
+        const MAX_PROCESS_NAME_WIDTH: usize = 20;
+
+        let num_selected = state.cpus.iter().filter(|allowed| **allowed).count();
+        let mut lines = vec![Line::from(format!(
+            "Change the CPU affinity of {}.",
+            state.targets.describe(MAX_PROCESS_NAME_WIDTH)
+        ))];
+        if let Some(list) = state.targets.list() {
+            lines.push(Line::from(list));
+        }
+        lines.push(Line::from(format!(
+            "{num_selected} of {} CPUs allowed.",
+            state.cpus.len()
+        )));
+        let text = Text::from(lines).alignment(Alignment::Center);
+        let text = Paragraph::new(text)
+            .style(styles.text_style)
+            .wrap(Wrap { trim: true });
+
+        let block = dialog_block(styles.border_type, styles.border_style)
+            .title_top(Line::styled(" Change Affinity ", styles.widget_title_style))
+            .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+        // Each cell looks like "[x] 12 ".
+        let label_width = (state.cpus.len().saturating_sub(1)).to_string().len();
+        let cell_width = label_width + 5;
+        let inner_width = usize::from(block.inner(draw_area).width);
+        *columns = (inner_width / cell_width).clamp(1, state.cpus.len().max(1));
+
+        let grid: Vec<Line<'_>> = state
+            .cpus
+            .chunks(*columns)
+            .enumerate()
+            .map(|(row, cpus)| {
+                Line::from(
+                    cpus.iter()
+                        .enumerate()
+                        .map(|(column, allowed)| {
+                            let cpu = row * *columns + column;
+                            let mark = if *allowed { 'x' } else { ' ' };
+                            let style = if cpu == state.cursor {
+                                styles.selected_text_style
+                            } else {
+                                styles.text_style
+                            };
+
+                            Span::styled(format!("[{mark}] {cpu:<label_width$} "), style)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        let num_lines = text.line_count(block.inner(draw_area).width) as u16;
+        let num_rows = grid.len() as u16;
+
+        // The text, the grid, and the help text, with a space between each and
+        // 2 for the block.
+        let [draw_area] = Layout::vertical([Constraint::Max(num_lines + num_rows + 1 + 2 + 2)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+
+        let [text_area, grid_area, help_area] = Layout::vertical([
+            Constraint::Max(num_lines),
+            Constraint::Length(num_rows),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceEvenly)
+        .areas(block.inner(draw_area));
+
+        let [grid_area] = Layout::horizontal([Constraint::Length((*columns * cell_width) as u16)])
+            .flex(Flex::Center)
+            .areas(grid_area);
+
+        f.render_widget(block, draw_area);
+        f.render_widget(text, text_area);
+        f.render_widget(Paragraph::new(grid), grid_area);
+        f.render_widget(
+            Paragraph::new(HELP_TEXT)
+                .style(styles.text_style)
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    /// Draw the [`ProcessAffinityDialog`].
+    pub fn draw(&mut self, f: &mut Frame<'_>, draw_area: Rect, styles: &Styles) {
+        const MAX_DIALOG_WIDTH: u16 = 100;
+        let [draw_area] = Layout::horizontal([Constraint::Max(MAX_DIALOG_WIDTH)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+
+        match &self.state {
+            ProcessAffinityDialogState::NotEnabled => {}
+            #[cfg(target_os = "linux")]
+            ProcessAffinityDialogState::Editing(state) => {
+                Self::draw_editing(f, draw_area, styles, state, &mut self.columns);
+            }
+            ProcessAffinityDialogState::Error {
+                process_name,
+                pid,
+                err,
+            } => {
+                draw_error_dialog(f, draw_area, styles, "change", process_name, *pid, err);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edit_cpus() {
+        let mut state = EditingState {
+            targets: ProcessTargets::default(),
+            cpus: vec![true, false, true, false, true],
+            cursor: 0,
+        };
+
+        // The cursor stays within the grid.
+        state.move_cursor(-1);
+        assert_eq!(state.cursor, 0);
+        state.move_cursor(4);
+        assert_eq!(state.cursor, 4);
+        state.move_cursor(2);
+        assert_eq!(state.cursor, 4);
+
+        assert_eq!(state.selected_cpus(), vec![0, 2, 4]);
+        state.toggle_all();
+        assert_eq!(state.selected_cpus(), vec![0, 1, 2, 3, 4]);
+        state.toggle_all();
+        assert!(state.selected_cpus().is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+//! A dialog to choose and reorder the columns of the process widget, and save
+//! them to the config file.
+
+use std::{fs, io, path::Path, time::SystemTime};
+
+use anyhow::Context;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use toml_edit::{Array, DocumentMut, Item, Table, value};
+use tui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    canvas::drawing_utils::dialog_block,
+    options::{config::style::Styles, config_modified_time},
+    widgets::ProcWidgetColumn,
+};
+
+const HELP_TEXT: &str = "Arrows move  Space show/hide  J/K reorder  Enter apply  w save";
+
+#[derive(Debug)]
+struct ColumnsState {
+    /// Every column, in order, and whether it is shown.
+    columns: Vec<(ProcWidgetColumn, bool)>,
+    /// The column under the cursor.
+    cursor: usize,
+    /// The result of the last save, or why the columns can't be applied.
+    status: Option<String>,
+}
+
+impl ColumnsState {
+    /// The shown columns come first, in their current order, followed by the
+    /// rest in their default order.
+    fn new(shown: &[ProcWidgetColumn]) -> Self {
+        let columns = shown
+            .iter()
+            .map(|column| (*column, true))
+            .chain(
+                ProcWidgetColumn::CHOOSABLE
+                    .iter()
+                    .filter(|column| !shown.contains(column))
+                    .map(|column| (*column, false)),
+            )
+            .collect();
+
+        Self {
+            columns,
+            cursor: 0,
+            status: None,
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if let Some(cursor) = self.cursor.checked_add_signed(delta) {
+            if cursor < self.columns.len() {
+                self.cursor = cursor;
+            }
+        }
+    }
+
+    /// Move the column under the cursor by `delta` places, keeping it under
+    /// the cursor.
+    fn move_column(&mut self, delta: isize) {
+        if let Some(target) = self.cursor.checked_add_signed(delta) {
+            if target < self.columns.len() {
+                self.columns.swap(self.cursor, target);
+                self.cursor = target;
+            }
+        }
+    }
+
+    fn toggle_shown(&mut self) {
+        if let Some((_, shown)) = self.columns.get_mut(self.cursor) {
+            *shown = !*shown;
+        }
+    }
+
+    /// The shown columns, in order. At least one column must be shown.
+    fn shown_columns(&mut self) -> Option<Vec<ProcWidgetColumn>> {
+        let shown = self
+            .columns
+            .iter()
+            .filter_map(|(column, shown)| shown.then_some(*column))
+            .collect::<Vec<_>>();
+
+        if shown.is_empty() {
+            self.status = Some("At least one column must be shown.".into());
+            None
+        } else {
+            Some(shown)
+        }
+    }
+}
+
+/// Write the columns to the `processes.columns` setting of the config file at
+/// `path`, keeping everything else in the file as it is.
+fn write_columns(path: &Path, columns: &[ProcWidgetColumn]) -> anyhow::Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let mut document: DocumentMut = contents.parse()?;
+
+    let table = document
+        .entry("processes")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .context("'processes' is not a table")?;
+
+    let columns = columns
+        .iter()
+        .map(ProcWidgetColumn::config_name)
+        .collect::<Array>();
+    match table.get_mut("columns") {
+        // Keep any comments around the old value.
+        Some(item) => {
+            let decor = item.as_value().map(|old| old.decor().clone());
+            *item = value(columns);
+            if let (Some(decor), Some(new)) = (decor, item.as_value_mut()) {
+                *new.decor_mut() = decor;
+            }
+        }
+        None => {
+            table.insert("columns", value(columns));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, document.to_string())?;
+
+    Ok(())
+}
+
+/// The process column chooser dialog.
+#[derive(Debug, Default)]
+pub(crate) struct ProcessColumnsDialog {
+    state: Option<ColumnsState>,
+    /// When the config file was modified by the last save, if it hasn't been
+    /// taken yet.
+    saved_at: Option<SystemTime>,
+}
+
+impl ProcessColumnsDialog {
+    /// Open the dialog with the columns currently shown by a process widget.
+    pub fn open(&mut self, shown: &[ProcWidgetColumn]) {
+        self.state = Some(ColumnsState::new(shown));
+    }
+
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Take when the config file was modified by the last save, if the
+    /// columns were saved since this was last called.
+    pub fn take_saved_at(&mut self) -> Option<SystemTime> {
+        self.saved_at.take()
+    }
+
+    /// Handle a key press. If the columns were applied or saved, this returns
+    /// the columns to show.
+    pub fn on_key_event(
+        &mut self, event: KeyEvent, config_path: Option<&Path>,
+    ) -> Option<Vec<ProcWidgetColumn>> {
+        let state = self.state.as_mut()?;
+        state.status = None;
+
+        if event.modifiers == KeyModifiers::CONTROL {
+            if event.code == KeyCode::Char('s') {
+                return self.save(config_path);
+            }
+            return None;
+        }
+
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = None,
+            KeyCode::Up | KeyCode::Char('k') => state.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => state.move_cursor(1),
+            KeyCode::Char('K') => state.move_column(-1),
+            KeyCode::Char('J') => state.move_column(1),
+            KeyCode::Char(' ') => state.toggle_shown(),
+            KeyCode::Enter => {
+                let columns = state.shown_columns()?;
+                self.state = None;
+                return Some(columns);
+            }
+            KeyCode::Char('w') => return self.save(config_path),
+            _ => {}
+        }
+
+        None
+    }
+
+    fn save(&mut self, config_path: Option<&Path>) -> Option<Vec<ProcWidgetColumn>> {
+        let state = self.state.as_mut()?;
+        let columns = state.shown_columns()?;
+        let Some(path) = config_path else {
+            state.status = Some("There is no config file to save the columns to.".into());
+            return None;
+        };
+
+        match write_columns(path, &columns) {
+            Ok(()) => {
+                state.status = Some(format!("Saved the columns to '{}'.", path.display()));
+                self.saved_at = config_modified_time(path);
+                Some(columns)
+            }
+            Err(err) => {
+                state.status = Some(format!("Couldn't save the columns: {err}"));
+                None
+            }
+        }
+    }
+
+    /// Draw the [`ProcessColumnsDialog`].
+    pub fn draw(&mut self, f: &mut Frame<'_>, draw_area: Rect, styles: &Styles) {
+        const MAX_DIALOG_WIDTH: u16 = 70;
+
+        let Some(state) = &self.state else {
+            return;
+        };
+
+        let block = dialog_block(styles.border_type, styles.border_style)
+            .title_top(Line::styled(" Columns ", styles.widget_title_style))
+            .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+        let list: Vec<Line<'_>> = state
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, (column, shown))| {
+                let mark = if *shown { 'x' } else { ' ' };
+                let style = if index == state.cursor {
+                    styles.selected_text_style
+                } else {
+                    styles.text_style
+                };
+
+                Line::from(Span::styled(format!("[{mark}] {}", column.name()), style))
+            })
+            .collect();
+
+        // The list, a space, the status and help text, and 2 for the block.
+        let num_rows = list.len() as u16;
+        let [draw_area] = Layout::horizontal([Constraint::Max(MAX_DIALOG_WIDTH)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+        let [draw_area] = Layout::vertical([Constraint::Max(num_rows + 1 + 2 + 2)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+
+        let [list_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(draw_area));
+
+        // Keep the cursor visible if the list doesn't fit.
+        let scroll = (state.cursor + 1).saturating_sub(usize::from(list_area.height)) as u16;
+        let list_width = list.iter().map(Line::width).max().unwrap_or_default() as u16;
+        let [list_area] = Layout::horizontal([Constraint::Length(list_width)])
+            .flex(Flex::Center)
+            .areas(list_area);
+
+        f.render_widget(block, draw_area);
+        f.render_widget(Paragraph::new(list).scroll((scroll, 0)), list_area);
+        if let Some(status) = &state.status {
+            f.render_widget(
+                Paragraph::new(status.as_str())
+                    .style(styles.text_style)
+                    .alignment(Alignment::Center),
+                status_area,
+            );
+        }
+        f.render_widget(
+            Paragraph::new(HELP_TEXT)
+                .style(styles.text_style)
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn choose_columns() {
+        let mut state = ColumnsState::new(&[ProcWidgetColumn::Cpu, ProcWidgetColumn::PidOrCount]);
+        assert_eq!(state.columns.len(), ProcWidgetColumn::CHOOSABLE.len());
+        assert_eq!(
+            state.columns[..3],
+            [
+                (ProcWidgetColumn::Cpu, true),
+                (ProcWidgetColumn::PidOrCount, true),
+                (ProcWidgetColumn::ProcNameOrCommand, false),
+            ]
+        );
+
+        // The moved column stays under the cursor, and can't go past the ends.
+        state.move_column(-1);
+        assert_eq!(state.cursor, 0);
+        state.move_column(1);
+        assert_eq!(state.cursor, 1);
+        state.move_cursor(1);
+        state.toggle_shown();
+        assert_eq!(
+            state.shown_columns(),
+            Some(vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::Cpu,
+                ProcWidgetColumn::ProcNameOrCommand,
+            ])
+        );
+
+        for (_, shown) in &mut state.columns {
+            *shown = false;
+        }
+        assert_eq!(state.shown_columns(), None);
+        assert!(state.status.is_some());
+    }
+
+    #[test]
+    fn write_keeps_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(
+            &path,
+            "# My config.\n[processes]\n# My columns.\ncolumns = [\"cpu%\"] # Just CPU.\nregex = true\n",
+        )
+        .unwrap();
+
+        write_columns(
+            &path,
+            &[ProcWidgetColumn::PidOrCount, ProcWidgetColumn::State],
+        )
+        .unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+
+        fs::write(&path, "[flags]\nrate = \"1s\"\n").unwrap();
+        write_columns(&path, &[ProcWidgetColumn::Mem]).unwrap();
+        let added = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(
+            written,
+            "# My config.\n[processes]\n# My columns.\ncolumns = [\"pid\", \"state\"] # Just CPU.\nregex = true\n"
+        );
+        let config: crate::options::Config = toml_edit::de::from_str(&written).unwrap();
+        let columns = config.processes.unwrap().columns;
+        assert_eq!(
+            columns
+                .iter()
+                .map(ProcWidgetColumn::from)
+                .collect::<Vec<_>>(),
+            vec![ProcWidgetColumn::PidOrCount, ProcWidgetColumn::State]
+        );
+
+        assert_eq!(
+            added,
+            "[flags]\nrate = \"1s\"\n\n[processes]\ncolumns = [\"mem\"]\n"
+        );
+    }
+}
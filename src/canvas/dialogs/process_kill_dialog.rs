@@ -1,6 +1,6 @@
 //! A dialog box to handle killing processes.
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
 use tui::widgets::ListState;
@@ -16,7 +16,11 @@ use crate::canvas::components::scroll_bar::{
     ScrollBarArgs, dialog_scroll_bar_area, draw_scroll_bar,
 };
 use crate::{
-    canvas::drawing_utils::dialog_block, collection::processes::Pid, options::config::style::Styles,
+    app::data::ProcessData,
+    canvas::{dialogs::error_dialog::draw_error_dialog, drawing_utils::dialog_block},
+    collection::processes::Pid,
+    options::config::style::Styles,
+    widgets::ProcessTargets,
 };
 
 // Configure signal text based on the target OS.
@@ -188,11 +192,183 @@ pub(crate) enum ButtonState {
     },
 }
 
+/// Which processes a signal is sent to.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum KillScope {
+    /// Just the chosen processes.
+    #[default]
+    Processes,
+    /// The process groups of the chosen processes.
+    ProcessGroup,
+    /// The sessions of the chosen processes, by signalling each process group
+    /// in them other than the session leader's.
+    Session,
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+impl KillScope {
+    fn next(self) -> Self {
+        match self {
+            KillScope::Processes => KillScope::ProcessGroup,
+            KillScope::ProcessGroup => KillScope::Session,
+            KillScope::Session => KillScope::Processes,
+        }
+    }
+
+    /// Return the string representation.
+    fn as_str(&self) -> &'static str {
+        match self {
+            KillScope::Processes => "processes",
+            KillScope::ProcessGroup => "process groups",
+            KillScope::Session => "sessions",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct ProcessKillSelectingInner {
-    process_name: String,
-    pids: Vec<Pid>,
+    targets: ProcessTargets,
     button_state: ButtonState,
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    scope: KillScope,
+    /// Whether to send SIGKILL to any processes still alive after
+    /// `escalation_timeout`.
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    escalate: bool,
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    escalation_timeout: Duration,
+}
+
+/// A signal that was sent, and the processes that have yet to exit.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+#[derive(Debug)]
+struct KillProgress {
+    signal_name: &'static str,
+    /// The process groups that are signalled, if signalling whole groups.
+    groups: Vec<Pid>,
+    /// How many processes were signalled.
+    total: usize,
+    /// The processes that have not exited yet.
+    remaining: Vec<Pid>,
+    /// When to send SIGKILL to any processes that have not exited, if
+    /// escalating.
+    escalate_at: Option<Instant>,
+    /// How many processes were sent SIGKILL, if escalated.
+    escalated: Option<usize>,
+    /// The processes or process groups that could not be signalled, and why.
+    errors: Vec<(Pid, String)>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+impl KillProgress {
+    /// Send a signal to the remaining processes, or their process groups.
+    /// Processes that could not be signalled are no longer waited on.
+    fn send(&mut self, signal: usize) {
+        use crate::utils::process_killer;
+
+        if self.groups.is_empty() {
+            let mut errors = Vec::new();
+            self.remaining.retain(|&pid| {
+                match process_killer::kill_process_given_pid(pid, signal) {
+                    Ok(()) => true,
+                    Err(err) => {
+                        // If it is already gone, there is nothing to report.
+                        if process_killer::is_process_alive(pid) {
+                            errors.push((pid, err.to_string()));
+                        }
+                        false
+                    }
+                }
+            });
+            self.errors.extend(errors);
+        } else {
+            for &pgid in &self.groups {
+                if let Err(err) = process_killer::kill_process_group(pgid, signal) {
+                    let has_members = self
+                        .remaining
+                        .iter()
+                        .any(|&pid| process_killer::get_process_group(pid) == Some(pgid));
+                    if has_members {
+                        self.errors.push((pgid, err.to_string()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drop any processes that have exited, and send SIGKILL to the rest if it
+    /// is time to escalate.
+    fn poll(&mut self) {
+        use crate::utils::process_killer;
+
+        self.remaining
+            .retain(|&pid| process_killer::is_process_alive(pid));
+
+        if let Some(escalate_at) = self.escalate_at {
+            if self.remaining.is_empty() {
+                self.escalate_at = None;
+            } else if Instant::now() >= escalate_at {
+                self.escalate_at = None;
+                self.escalated = Some(self.remaining.len());
+                self.send(libc::SIGKILL as usize);
+            }
+        }
+    }
+}
+
+/// The process groups to signal for `scope`, given the chosen processes, every
+/// known process, bottom's own PID, and how to look up a process's group and
+/// session. Returns `None` if the chosen processes share bottom's session.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn groups_to_signal(
+    scope: KillScope, targets: &[Pid], known: &[Pid], own_pid: Pid,
+    group_of: impl Fn(Pid) -> Option<Pid>, session_of: impl Fn(Pid) -> Option<Pid>,
+) -> Option<Vec<Pid>> {
+    use itertools::Itertools;
+
+    // Never signal our own process group, as that would kill bottom too.
+    let own_group = group_of(own_pid);
+    let groups_of = |pids: &mut dyn Iterator<Item = Pid>| -> Vec<Pid> {
+        pids.filter_map(&group_of)
+            .filter(|pgid| Some(*pgid) != own_group)
+            .unique()
+            .collect()
+    };
+
+    match scope {
+        KillScope::Processes => Some(Vec::new()),
+        KillScope::ProcessGroup => Some(groups_of(&mut targets.iter().copied())),
+        KillScope::Session => {
+            let sessions: Vec<Pid> = targets
+                .iter()
+                .copied()
+                .filter_map(&session_of)
+                .unique()
+                .collect();
+
+            // Bottom's own session is usually the terminal it runs in, along
+            // with everything else started from there.
+            if session_of(own_pid).is_some_and(|sid| sessions.contains(&sid)) {
+                return None;
+            }
+
+            // The session leader's group has the same ID as the session, and
+            // is usually the user's shell, so leave it alone.
+            let groups = groups_of(
+                &mut known
+                    .iter()
+                    .copied()
+                    .filter(|pid| session_of(*pid).is_some_and(|sid| sessions.contains(&sid))),
+            );
+            Some(
+                groups
+                    .into_iter()
+                    .filter(|pgid| !sessions.contains(pgid))
+                    .collect(),
+            )
+        }
+    }
 }
 
 /// The current state of the process kill dialog.
@@ -201,6 +377,8 @@ enum ProcessKillDialogState {
     #[default]
     NotEnabled,
     Selecting(ProcessKillSelectingInner),
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    Killing(KillProgress),
     Error {
         process_name: String,
         pid: Option<Pid>,
@@ -229,76 +407,104 @@ impl ProcessKillDialog {
         self.reset();
     }
 
-    pub fn on_enter(&mut self) {
+    /// Send the selected signal, if any. Returns a description of what was sent
+    /// if it was sent to at least one process.
+    ///
+    /// Signals sent to process trees, process groups, or sessions, or that
+    /// escalate, keep the dialog open to show which processes have exited.
+    pub fn on_enter(
+        &mut self,
+        #[cfg_attr(
+            not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")),
+            expect(unused_variables)
+        )]
+        process_data: &ProcessData,
+    ) -> Option<String> {
         // We do this to get around borrow issues.
         let mut current = ProcessKillDialogState::NotEnabled;
         std::mem::swap(&mut self.state, &mut current);
 
+        let mut sent = None;
+
         if let ProcessKillDialogState::Selecting(state) = current {
-            let process_name = state.process_name;
-            let button_state = state.button_state;
-            let pids = state.pids;
+            let targets = &state.targets;
+            let pids = targets.pids();
+            let process_name = |pid: Pid| targets.name_of(pid).to_string();
+            let description = |signal: &str, count: usize| match (count, pids.first()) {
+                (1, Some(&pid)) => format!("Sent {signal} to {} ({pid})", process_name(pid)),
+                _ => format!("Sent {signal} to {} ({count} processes)", targets.name()),
+            };
 
-            match button_state {
-                #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
-                ButtonState::Signals { state, .. } => {
+            cfg_select! {
+                any(target_os = "linux", target_os = "macos", target_os = "freebsd") => {
                     use crate::utils::process_killer;
 
-                    if let Some(selected) = state.selected() {
-                        if selected != 0 {
-                            // On Linux, we need to skip 32 and 33.
-                            let signal = if cfg!(target_os = "linux")
-                                && (selected == 32 || selected == 33)
-                            {
-                                selected + 2
-                            } else {
-                                selected
-                            };
+                    let signal = match &state.button_state {
+                        ButtonState::Signals { state, .. } => {
+                            state.selected().filter(|selected| *selected != 0).map(|selected| {
+                                // On Linux, we need to skip 32 and 33.
+                                let signal = if cfg!(target_os = "linux")
+                                    && (selected == 32 || selected == 33)
+                                {
+                                    selected + 2
+                                } else {
+                                    selected
+                                };
+
+                                let signal_name = SIGNAL_TEXT[selected]
+                                    .split_once(": ")
+                                    .map_or(SIGNAL_TEXT[selected], |(_, name)| name);
+
+                                (signal, signal_name)
+                            })
+                        }
+                        // Send a SIGTERM by default.
+                        ButtonState::Simple { yes, .. } => yes.then_some((DEFAULT_KILL_SIGNAL, "TERM")),
+                    };
 
-                            for pid in pids {
+                    if let Some((signal, signal_name)) = signal {
+                        if state.scope != KillScope::Processes
+                            || state.escalate
+                            || targets.tree_roots.is_some()
+                        {
+                            sent = self.start_progress(&state, signal, signal_name, process_data);
+                        } else {
+                            let mut count = 0;
+                            for &pid in &pids {
                                 if let Err(err) =
                                     process_killer::kill_process_given_pid(pid, signal)
                                 {
                                     self.state = ProcessKillDialogState::Error {
-                                        process_name,
+                                        process_name: process_name(pid),
                                         pid: Some(pid),
                                         err: err.to_string(),
                                     };
-                                    return;
+                                    break;
                                 }
+                                count += 1;
                             }
+                            sent = (count > 0).then(|| description(signal_name, count));
                         }
                     }
                 }
-                ButtonState::Simple { yes, .. } => {
-                    if yes {
-                        cfg_select! {
-                            target_os = "windows" => {
-                                use crate::utils::process_killer;
-
-                                for pid in pids {
-                                    if let Err(err) = process_killer::kill_process_given_pid(pid) {
-                                        self.state = ProcessKillDialogState::Error { process_name, pid: Some(pid), err: err.to_string() };
-                                        break;
-                                    }
-                                }
-                            }
-                            any(target_os = "linux", target_os = "macos", target_os = "freebsd") => {
-                                use crate::utils::process_killer;
-
-                                for pid in pids {
-                                    // Send a SIGTERM by default.
-                                    if let Err(err) = process_killer::kill_process_given_pid(pid, DEFAULT_KILL_SIGNAL) {
-                                        self.state = ProcessKillDialogState::Error { process_name, pid: Some(pid), err: err.to_string() };
-                                        break;
-                                    }
-                                }
-                            }
-                            _ => {
-                                self.state = ProcessKillDialogState::Error { process_name, pid: None, err: "Killing processes is not supported on this platform.".into() };
+                target_os = "windows" => {
+                    use crate::utils::process_killer;
 
+                    if let ButtonState::Simple { yes: true, .. } = state.button_state {
+                        let mut count = 0;
+                        for &pid in &pids {
+                            if let Err(err) = process_killer::kill_process_given_pid(pid) {
+                                self.state = ProcessKillDialogState::Error { process_name: process_name(pid), pid: Some(pid), err: err.to_string() };
+                                break;
                             }
+                            count += 1;
                         }
+                        sent = (count > 0).then(|| description("kill", count));
+                    }
+                }
+                _ => {
+                    if let ButtonState::Simple { yes: true, .. } = state.button_state {
+                        self.state = ProcessKillDialogState::Error { process_name: targets.name().to_string(), pid: None, err: "Killing processes is not supported on this platform.".into() };
                     }
                 }
             }
@@ -306,6 +512,117 @@ impl ProcessKillDialog {
 
         // Fall through behaviour is just to close the dialog.
         self.last_char = None;
+
+        sent
+    }
+
+    /// Send a signal to the processes, process groups, or sessions chosen in
+    /// `state`, and keep the dialog open to show its progress. Returns a
+    /// description of what was sent if anything was signalled.
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    fn start_progress(
+        &mut self, state: &ProcessKillSelectingInner, signal: usize, signal_name: &'static str,
+        process_data: &ProcessData,
+    ) -> Option<String> {
+        use crate::utils::process_killer::{get_process_group, get_session};
+
+        let targets = &state.targets;
+        let known: Vec<Pid> = process_data.process_harvest.keys().copied().collect();
+
+        let Some(groups) = groups_to_signal(
+            state.scope,
+            &targets.pids(),
+            &known,
+            std::process::id() as Pid,
+            get_process_group,
+            get_session,
+        ) else {
+            self.state = ProcessKillDialogState::Error {
+                process_name: targets.name().to_string(),
+                pid: None,
+                err: "Refusing to signal the session bottom is running in.".into(),
+            };
+            return None;
+        };
+
+        let members = if state.scope == KillScope::Processes {
+            targets.pids()
+        } else if groups.is_empty() {
+            self.state = ProcessKillDialogState::Error {
+                process_name: targets.name().to_string(),
+                pid: None,
+                err: match state.scope {
+                    KillScope::Session => "No sessions to signal, as bottom never signals its own \
+                        process group or a session leader's."
+                        .into(),
+                    _ => format!(
+                        "No {} to signal, as bottom never signals its own process group.",
+                        state.scope.as_str()
+                    ),
+                },
+            };
+            return None;
+        } else {
+            known
+                .into_iter()
+                .filter(|pid| get_process_group(*pid).is_some_and(|pgid| groups.contains(&pgid)))
+                .collect()
+        };
+
+        let signalled = if groups.is_empty() {
+            members.len()
+        } else {
+            groups.len()
+        };
+
+        let mut progress = KillProgress {
+            signal_name,
+            groups,
+            total: members.len(),
+            remaining: members,
+            escalate_at: state
+                .escalate
+                .then(|| Instant::now() + state.escalation_timeout),
+            escalated: None,
+            errors: Vec::new(),
+        };
+        progress.send(signal);
+
+        let what = match (state.scope, targets.tree_roots) {
+            (KillScope::Processes, Some(_)) => "the process tree of ",
+            (KillScope::Processes, None) => "",
+            (KillScope::ProcessGroup, _) => "the process group of ",
+            (KillScope::Session, _) => "the session of ",
+        };
+        let sent = (progress.errors.len() < signalled).then(|| {
+            format!(
+                "Sent {signal_name} to {what}{} ({} processes)",
+                targets.name(),
+                progress.total
+            )
+        });
+
+        self.state = ProcessKillDialogState::Killing(progress);
+        sent
+    }
+
+    /// Check on a signal that was sent, sending SIGKILL to any processes that
+    /// have not exited if it is time to escalate. Returns whether the progress
+    /// is being shown.
+    pub fn update_progress(&mut self) -> bool {
+        cfg_select! {
+            any(target_os = "linux", target_os = "macos", target_os = "freebsd") => {
+                if let ProcessKillDialogState::Killing(progress) = &mut self.state {
+                    progress.poll();
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => {
+                false
+            }
+        }
     }
 
     pub fn on_char(&mut self, c: char) {
@@ -387,6 +704,20 @@ impl ProcessKillDialog {
                 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
                 self.go_to_last();
             }
+            'p' =>
+            {
+                #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+                if let ProcessKillDialogState::Selecting(state) = &mut self.state {
+                    state.scope = state.scope.next();
+                }
+            }
+            'e' =>
+            {
+                #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+                if let ProcessKillDialogState::Selecting(state) = &mut self.state {
+                    state.escalate = !state.escalate;
+                }
+            }
             _ => {}
         }
 
@@ -579,9 +910,15 @@ impl ProcessKillDialog {
         }
     }
 
-    /// Enable the process kill process.
+    /// Enable the process kill process. If escalation is turned on, any
+    /// processes still alive after `escalation_timeout` are sent SIGKILL.
     pub fn start_process_kill(
-        &mut self, process_name: String, pids: Vec<Pid>, use_simple_selection: bool,
+        &mut self, targets: ProcessTargets, use_simple_selection: bool,
+        #[cfg_attr(
+            not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")),
+            expect(unused_variables)
+        )]
+        escalation_timeout: Duration,
     ) {
         let button_state = if use_simple_selection {
             ButtonState::Simple {
@@ -600,9 +937,9 @@ impl ProcessKillDialog {
             }
         };
 
-        if pids.is_empty() {
+        if targets.processes.is_empty() {
             self.state = ProcessKillDialogState::Error {
-                process_name,
+                process_name: targets.name().to_string(),
                 pid: None,
                 err: "No PIDs found for the given process name.".into(),
             };
@@ -610,9 +947,14 @@ impl ProcessKillDialog {
         }
 
         self.state = ProcessKillDialogState::Selecting(ProcessKillSelectingInner {
-            process_name,
-            pids,
+            targets,
             button_state,
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            scope: KillScope::default(),
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            escalate: false,
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            escalation_timeout,
         });
     }
 
@@ -638,8 +980,7 @@ impl ProcessKillDialog {
         f: &mut Frame<'_>, draw_area: Rect, styles: &Styles, state: &mut ProcessKillSelectingInner,
     ) {
         let ProcessKillSelectingInner {
-            process_name,
-            pids,
+            targets,
             button_state,
             ..
         } = state;
@@ -648,23 +989,23 @@ impl ProcessKillDialog {
         let text = {
             const MAX_PROCESS_NAME_WIDTH: usize = 20;
 
-            if let Some(first_pid) = pids.first() {
-                let truncated_process_name =
-                    unicode_ellipsis::truncate_str(process_name, MAX_PROCESS_NAME_WIDTH);
-
-                let text = if pids.len() > 1 {
-                    Line::from(format!(
-                        "Kill {} processes with the name '{}'? Press ENTER to confirm.",
-                        pids.len(),
-                        truncated_process_name
-                    ))
-                } else {
-                    Line::from(format!(
-                        "Kill process '{truncated_process_name}' with PID {first_pid}? Press ENTER to confirm."
-                    ))
-                };
-
-                Text::from(vec![text])
+            if !targets.processes.is_empty() {
+                let mut lines = vec![Line::from(format!(
+                    "Kill {}? Press ENTER to confirm.",
+                    targets.describe(MAX_PROCESS_NAME_WIDTH)
+                ))];
+                if let Some(list) = targets.list() {
+                    lines.push(Line::from(list));
+                }
+                #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+                lines.push(Line::from(format!(
+                    "Send to: {} (p)  KILL survivors after {}: {} (e)",
+                    state.scope.as_str(),
+                    humantime::format_duration(state.escalation_timeout),
+                    if state.escalate { "on" } else { "off" },
+                )));
+
+                Text::from(lines)
             } else {
                 Text::from(vec![
                     "Could not find process to kill.".into(),
@@ -824,6 +1165,53 @@ impl ProcessKillDialog {
         }
     }
 
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    fn progress_text(progress: &KillProgress) -> Text<'static> {
+        let exited = progress.total - progress.remaining.len();
+        let mut lines: Vec<Line<'static>> = vec![
+            format!(
+                "Sent {} to {} processes, {exited} of which have exited.",
+                progress.signal_name, progress.total
+            )
+            .into(),
+        ];
+
+        if let Some(escalate_at) = progress.escalate_at {
+            let left = escalate_at.saturating_duration_since(Instant::now());
+            lines.push(
+                format!(
+                    "Sending KILL to the rest in {}s.",
+                    left.as_secs_f64().ceil() as u64
+                )
+                .into(),
+            );
+        }
+        if let Some(escalated) = progress.escalated {
+            lines.push(format!("Sent KILL to {escalated} processes that had not exited.").into());
+        }
+        if let Some((pid, err)) = progress.errors.first() {
+            lines.push(
+                format!(
+                    "Failed to signal {} processes or groups, such as {pid}: {err}",
+                    progress.errors.len()
+                )
+                .into(),
+            );
+        }
+
+        lines.push(
+            if progress.remaining.is_empty() && progress.errors.is_empty() {
+                "All processes have exited. Please press ENTER or ESC to close this dialog.".into()
+            } else if progress.escalate_at.is_some() {
+                "Closing this dialog with ENTER or ESC cancels sending KILL.".into()
+            } else {
+                "Please press ENTER or ESC to close this dialog.".into()
+            },
+        );
+
+        Text::from(lines)
+    }
+
     #[inline]
     fn draw_no_button_dialog(
         &self, f: &mut Frame<'_>, draw_area: Rect, styles: &Styles, text: Text<'_>, title: Line<'_>,
@@ -876,25 +1264,127 @@ impl ProcessKillDialog {
                 // Draw a text box. If buttons are yes/no, fit it, otherwise, use max space.
                 Self::draw_selecting(f, draw_area, styles, state);
             }
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            ProcessKillDialogState::Killing(progress) => {
+                let text = Self::progress_text(progress);
+                let title = Line::styled(" Killing Processes ", styles.widget_title_style);
+
+                self.draw_no_button_dialog(f, draw_area, styles, text, title);
+            }
             ProcessKillDialogState::Error {
                 process_name,
                 pid,
                 err,
             } => {
-                let text = Text::from(vec![
-                    if let Some(pid) = pid {
-                        format!("Failed to kill process {process_name} ({pid}):").into()
-                    } else {
-                        format!("Failed to kill process '{process_name}':").into()
-                    },
-                    err.to_owned().into(),
-                    "Please press ENTER or ESC to close this dialog.".into(),
-                ])
-                .alignment(Alignment::Center);
-                let title = Line::styled(" Error ", styles.widget_title_style);
-
-                self.draw_no_button_dialog(f, draw_area, styles, text, title);
+                draw_error_dialog(f, draw_area, styles, "kill", process_name, *pid, err);
             }
         }
     }
 }
+
+#[cfg(all(
+    test,
+    any(target_os = "linux", target_os = "macos", target_os = "freebsd")
+))]
+mod test {
+    use std::{
+        io::{BufRead, BufReader},
+        os::unix::process::ExitStatusExt,
+        process::{Command, Stdio},
+    };
+
+    use super::*;
+
+    /// Bottom is PID 10 in group 10 and session 1. Group 20 is led by the
+    /// session leader 20 of session 20, which also holds group 30. Group 40 is
+    /// in session 40.
+    fn group_of(pid: Pid) -> Option<Pid> {
+        match pid {
+            10 | 11 => Some(10),
+            20 | 21 => Some(20),
+            30 | 31 => Some(30),
+            40 | 41 => Some(40),
+            _ => None,
+        }
+    }
+
+    fn session_of(pid: Pid) -> Option<Pid> {
+        match pid {
+            10 | 11 => Some(1),
+            20 | 21 | 30 | 31 => Some(20),
+            40 | 41 => Some(40),
+            _ => None,
+        }
+    }
+
+    const KNOWN: [Pid; 8] = [10, 11, 20, 21, 30, 31, 40, 41];
+
+    fn groups(scope: KillScope, targets: &[Pid]) -> Option<Vec<Pid>> {
+        groups_to_signal(scope, targets, &KNOWN, 10, group_of, session_of)
+    }
+
+    #[test]
+    fn signal_processes() {
+        assert_eq!(groups(KillScope::Processes, &[11, 21]), Some(vec![]));
+    }
+
+    #[test]
+    fn skip_own_process_group() {
+        assert_eq!(
+            groups(KillScope::ProcessGroup, &[11, 31, 30, 41]),
+            Some(vec![30, 40])
+        );
+        assert_eq!(groups(KillScope::ProcessGroup, &[10, 11]), Some(vec![]));
+        assert_eq!(groups(KillScope::ProcessGroup, &[99]), Some(vec![]));
+    }
+
+    #[test]
+    fn refuse_own_session() {
+        assert_eq!(groups(KillScope::Session, &[11]), None);
+        assert_eq!(groups(KillScope::Session, &[31, 11]), None);
+    }
+
+    #[test]
+    fn skip_session_leader_group() {
+        assert_eq!(groups(KillScope::Session, &[31]), Some(vec![30]));
+        assert_eq!(groups(KillScope::Session, &[21]), Some(vec![30]));
+        assert_eq!(groups(KillScope::Session, &[41, 31]), Some(vec![30]));
+    }
+
+    #[test]
+    fn escalate_to_kill() {
+        // Ignore TERM, and only hand over to `sleep` once the trap is set.
+        let mut child = Command::new("sh")
+            .args(["-c", "trap '' TERM; echo ready; exec sleep 30"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        assert_eq!(line, "ready\n");
+
+        let pid = child.id() as Pid;
+        let mut progress = KillProgress {
+            signal_name: "TERM",
+            groups: Vec::new(),
+            total: 1,
+            remaining: vec![pid],
+            escalate_at: Some(Instant::now()),
+            escalated: None,
+            errors: Vec::new(),
+        };
+
+        progress.send(libc::SIGTERM as usize);
+        progress.poll();
+        assert_eq!(progress.escalated, Some(1));
+        assert!(progress.errors.is_empty());
+
+        let status = child.wait().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+
+        progress.poll();
+        assert!(progress.remaining.is_empty());
+    }
+}
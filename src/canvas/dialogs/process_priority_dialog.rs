@@ -0,0 +1,460 @@
+//! A dialog box to change the priority and scheduling of processes.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+#[cfg(target_os = "linux")]
+use crate::utils::process_priority::{IoClass, SchedPolicy};
+use crate::{
+    canvas::{dialogs::error_dialog::draw_error_dialog, drawing_utils::dialog_block},
+    collection::processes::Pid,
+    options::config::style::Styles,
+    widgets::{ProcWidgetData, ProcessTargets},
+};
+
+const HELP_TEXT: &str = "Up/Down select  Left/Right change  Enter apply";
+
+/// A setting that can be changed in a [`ProcessPriorityDialog`].
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Nice,
+    #[cfg(target_os = "linux")]
+    IoClass,
+    #[cfg(target_os = "linux")]
+    IoLevel,
+    #[cfg(target_os = "linux")]
+    Policy,
+    #[cfg(target_os = "linux")]
+    RtPriority,
+}
+
+#[cfg(unix)]
+const FIELDS: &[Field] = &[
+    Field::Nice,
+    #[cfg(target_os = "linux")]
+    Field::IoClass,
+    #[cfg(target_os = "linux")]
+    Field::IoLevel,
+    #[cfg(target_os = "linux")]
+    Field::Policy,
+    #[cfg(target_os = "linux")]
+    Field::RtPriority,
+];
+
+/// The values of each setting.
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Settings {
+    nice: i32,
+    #[cfg(target_os = "linux")]
+    io_class: IoClass,
+    #[cfg(target_os = "linux")]
+    io_level: u8,
+    #[cfg(target_os = "linux")]
+    policy: SchedPolicy,
+    #[cfg(target_os = "linux")]
+    rt_priority: i32,
+}
+
+#[cfg(unix)]
+impl Settings {
+    /// Read the current settings of a process.
+    fn read(pid: Pid, nice: i32) -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            use crate::utils::process_priority::{get_io_priority, get_scheduler};
+
+            let (io_class, io_level) = get_io_priority(pid).unwrap_or((IoClass::None, 4));
+            let (policy, rt_priority) = get_scheduler(pid).unwrap_or((SchedPolicy::Other, 0));
+
+            Settings {
+                nice,
+                io_class,
+                io_level,
+                policy,
+                rt_priority,
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            Settings { nice }
+        }
+    }
+
+    /// Whether the field is used with the other current settings.
+    fn is_used(&self, field: Field) -> bool {
+        match field {
+            #[cfg(target_os = "linux")]
+            Field::IoLevel => matches!(self.io_class, IoClass::Realtime | IoClass::BestEffort),
+            #[cfg(target_os = "linux")]
+            Field::RtPriority => self.policy.is_realtime(),
+            _ => true,
+        }
+    }
+
+    fn value_text(&self, field: Field) -> String {
+        if !self.is_used(field) {
+            return "-".into();
+        }
+
+        match field {
+            Field::Nice => self.nice.to_string(),
+            #[cfg(target_os = "linux")]
+            Field::IoClass => self.io_class.as_str().into(),
+            #[cfg(target_os = "linux")]
+            Field::IoLevel => self.io_level.to_string(),
+            #[cfg(target_os = "linux")]
+            Field::Policy => self.policy.as_str().into(),
+            #[cfg(target_os = "linux")]
+            Field::RtPriority => self.rt_priority.to_string(),
+        }
+    }
+
+    /// Step the field to its next (or previous) value, stopping at the ends.
+    fn step(&mut self, field: Field, forward: bool) {
+        fn step_in<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+            let index = values.iter().position(|v| *v == current).unwrap_or(0);
+            let index = if forward {
+                (index + 1).min(values.len() - 1)
+            } else {
+                index.saturating_sub(1)
+            };
+            values[index]
+        }
+
+        let delta = if forward { 1 } else { -1 };
+
+        match field {
+            Field::Nice => self.nice = (self.nice + delta).clamp(-20, 19),
+            #[cfg(target_os = "linux")]
+            Field::IoClass => self.io_class = step_in(&IoClass::ALL, self.io_class, forward),
+            #[cfg(target_os = "linux")]
+            Field::IoLevel => {
+                self.io_level = self.io_level.saturating_add_signed(delta as i8).min(7)
+            }
+            #[cfg(target_os = "linux")]
+            Field::Policy => {
+                self.policy = step_in(&SchedPolicy::ALL, self.policy, forward);
+                self.rt_priority = if self.policy.is_realtime() {
+                    self.rt_priority.max(1)
+                } else {
+                    0
+                };
+            }
+            #[cfg(target_os = "linux")]
+            Field::RtPriority => self.rt_priority = (self.rt_priority + delta).clamp(1, 99),
+        }
+    }
+
+    /// Apply any settings that differ from `initial` to a process.
+    fn apply(&self, initial: &Settings, pid: Pid) -> anyhow::Result<()> {
+        use crate::utils::process_priority;
+
+        if self.nice != initial.nice {
+            process_priority::set_nice(pid, self.nice)?;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            if (self.io_class, self.io_level) != (initial.io_class, initial.io_level) {
+                let level = if self.is_used(Field::IoLevel) {
+                    self.io_level
+                } else {
+                    0
+                };
+                process_priority::set_io_priority(pid, self.io_class, level)?;
+            }
+
+            if (self.policy, self.rt_priority) != (initial.policy, initial.rt_priority) {
+                process_priority::set_scheduler(pid, self.policy, self.rt_priority)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug)]
+struct EditingState {
+    targets: ProcessTargets,
+    initial: Settings,
+    current: Settings,
+    selected: usize,
+}
+
+/// The current state of the process priority dialog.
+#[derive(Default, Debug)]
+enum ProcessPriorityDialogState {
+    #[default]
+    NotEnabled,
+    #[cfg(unix)]
+    Editing(EditingState),
+    Error {
+        process_name: String,
+        pid: Option<Pid>,
+        err: String,
+    },
+}
+
+/// Process priority dialog.
+#[derive(Default, Debug)]
+pub(crate) struct ProcessPriorityDialog {
+    state: ProcessPriorityDialogState,
+}
+
+impl ProcessPriorityDialog {
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        !(matches!(self.state, ProcessPriorityDialogState::NotEnabled))
+    }
+
+    /// Open the dialog for the given processes, where `process` is the entry
+    /// of the first one. The current settings are read from the first one.
+    pub fn open(&mut self, process: &ProcWidgetData, targets: ProcessTargets) {
+        let Some(&(_, first_pid)) = targets.processes.first() else {
+            self.state = ProcessPriorityDialogState::Error {
+                process_name: process.id.to_string(),
+                pid: None,
+                err: "No PIDs found for the given process name.".into(),
+            };
+            return;
+        };
+
+        cfg_select! {
+            unix => {
+                let initial = Settings::read(first_pid, process.nice);
+                self.state = ProcessPriorityDialogState::Editing(EditingState {
+                    targets,
+                    initial,
+                    current: initial,
+                    selected: 0,
+                });
+            }
+            _ => {
+                let _ = first_pid;
+                self.state = ProcessPriorityDialogState::Error {
+                    process_name: targets.name().to_string(),
+                    pid: None,
+                    err: "Changing the priority of processes is not supported on this platform."
+                        .into(),
+                };
+            }
+        }
+    }
+
+    /// Handle a key press.
+    pub fn on_key_event(&mut self, event: KeyEvent) {
+        match &mut self.state {
+            ProcessPriorityDialogState::NotEnabled => {}
+            #[cfg(unix)]
+            ProcessPriorityDialogState::Editing(state) => match event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.state = Default::default(),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    state.selected = state.selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    state.selected = (state.selected + 1).min(FIELDS.len() - 1);
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    let field = FIELDS[state.selected];
+                    if state.current.is_used(field) {
+                        state.current.step(field, false);
+                    }
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    let field = FIELDS[state.selected];
+                    if state.current.is_used(field) {
+                        state.current.step(field, true);
+                    }
+                }
+                KeyCode::Enter => self.apply(),
+                _ => {}
+            },
+            ProcessPriorityDialogState::Error { .. } => {
+                if matches!(
+                    event.code,
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')
+                ) {
+                    self.state = Default::default();
+                }
+            }
+        }
+    }
+
+    /// Apply the changed settings to every process, stopping at the first
+    /// error.
+    #[cfg(unix)]
+    fn apply(&mut self) {
+        let ProcessPriorityDialogState::Editing(state) = std::mem::take(&mut self.state) else {
+            return;
+        };
+
+        for pid in state.targets.pids() {
+            if let Err(err) = state.current.apply(&state.initial, pid) {
+                self.state = ProcessPriorityDialogState::Error {
+                    process_name: state.targets.name_of(pid).to_string(),
+                    pid: Some(pid),
+                    err: err.to_string(),
+                };
+                return;
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn draw_editing(f: &mut Frame<'_>, draw_area: Rect, styles: &Styles, state: &EditingState) {
+        const MAX_PROCESS_NAME_WIDTH: usize = 20;
+        const LABEL_WIDTH: usize = 14;
+        const VALUE_WIDTH: usize = 13;
+
+        let mut lines = vec![Line::from(format!(
+            "Change the priority of {}.",
+            state.targets.describe(MAX_PROCESS_NAME_WIDTH)
+        ))];
+        if let Some(list) = state.targets.list() {
+            lines.push(Line::from(list));
+        }
+        let text = Paragraph::new(lines)
+            .style(styles.text_style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        let rows: Vec<Line<'_>> = FIELDS
+            .iter()
+            .enumerate()
+            .map(|(index, &field)| {
+                let label = match field {
+                    Field::Nice => "Nice",
+                    #[cfg(target_os = "linux")]
+                    Field::IoClass => "I/O class",
+                    #[cfg(target_os = "linux")]
+                    Field::IoLevel => "I/O level",
+                    #[cfg(target_os = "linux")]
+                    Field::Policy => "Policy",
+                    #[cfg(target_os = "linux")]
+                    Field::RtPriority => "RT priority",
+                };
+                let value = state.current.value_text(field);
+                let value = if index == state.selected && state.current.is_used(field) {
+                    format!("< {value:^VALUE_WIDTH$} >")
+                } else {
+                    format!("  {value:^VALUE_WIDTH$}  ")
+                };
+                let style = if index == state.selected {
+                    styles.selected_text_style
+                } else if state.current.is_used(field) {
+                    styles.text_style
+                } else {
+                    styles.disabled_text_style
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{label:<LABEL_WIDTH$}"), styles.text_style),
+                    Span::styled(value, style),
+                ])
+            })
+            .collect();
+
+        let block = dialog_block(styles.border_type, styles.border_style)
+            .title_top(Line::styled(" Change Priority ", styles.widget_title_style))
+            .title_top(Line::styled(" Esc to close ", styles.widget_title_style).right_aligned());
+
+        let num_lines = text.line_count(block.inner(draw_area).width) as u16;
+        let num_rows = rows.len() as u16;
+
+        // The text, the rows, and the help text, with a space between each and
+        // 2 for the block.
+        let [draw_area] = Layout::vertical([Constraint::Max(num_lines + num_rows + 1 + 2 + 2)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+
+        let [text_area, rows_area, help_area] = Layout::vertical([
+            Constraint::Max(num_lines),
+            Constraint::Length(num_rows),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceEvenly)
+        .areas(block.inner(draw_area));
+
+        let [rows_area] =
+            Layout::horizontal([Constraint::Length((LABEL_WIDTH + VALUE_WIDTH + 4) as u16)])
+                .flex(Flex::Center)
+                .areas(rows_area);
+
+        f.render_widget(block, draw_area);
+        f.render_widget(text, text_area);
+        f.render_widget(Paragraph::new(rows), rows_area);
+        f.render_widget(
+            Paragraph::new(HELP_TEXT)
+                .style(styles.text_style)
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    /// Draw the [`ProcessPriorityDialog`].
+    pub fn draw(&self, f: &mut Frame<'_>, draw_area: Rect, styles: &Styles) {
+        const MAX_DIALOG_WIDTH: u16 = 100;
+        let [draw_area] = Layout::horizontal([Constraint::Max(MAX_DIALOG_WIDTH)])
+            .flex(Flex::Center)
+            .areas(draw_area);
+
+        match &self.state {
+            ProcessPriorityDialogState::NotEnabled => {}
+            #[cfg(unix)]
+            ProcessPriorityDialogState::Editing(state) => {
+                Self::draw_editing(f, draw_area, styles, state);
+            }
+            ProcessPriorityDialogState::Error {
+                process_name,
+                pid,
+                err,
+            } => {
+                draw_error_dialog(f, draw_area, styles, "change", process_name, *pid, err);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn step_settings() {
+        let mut settings = Settings {
+            nice: 18,
+            io_class: IoClass::None,
+            io_level: 4,
+            policy: SchedPolicy::Other,
+            rt_priority: 0,
+        };
+
+        settings.step(Field::Nice, true);
+        settings.step(Field::Nice, true);
+        assert_eq!(settings.nice, 19);
+
+        assert!(!settings.is_used(Field::IoLevel));
+        settings.step(Field::IoClass, true);
+        assert_eq!(settings.io_class, IoClass::Realtime);
+        assert!(settings.is_used(Field::IoLevel));
+
+        // Switching to a real-time policy needs a real-time priority.
+        settings.step(Field::Policy, true);
+        settings.step(Field::Policy, true);
+        settings.step(Field::Policy, true);
+        assert_eq!(settings.policy, SchedPolicy::Fifo);
+        assert_eq!(settings.rt_priority, 1);
+
+        settings.step(Field::Policy, false);
+        assert_eq!(settings.rt_priority, 0);
+    }
+}
@@ -50,6 +50,22 @@ pub fn widget_block(
     }
 }
 
+/// Draw values as a line of bar characters, scaled between `min` and `max`.
+pub fn sparkline(values: impl Iterator<Item = f64>, min: f64, max: f64) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    values
+        .map(|value| {
+            let ratio = if max > min {
+                ((value - min) / (max - min)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            BARS[(ratio * (BARS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
 /// Return a dialog block.
 pub fn dialog_block(border_type: BorderType, border_style: Style) -> Block<'static> {
     Block::default()
@@ -97,6 +113,16 @@ mod test {
         assert!(over_timer.is_none());
     }
 
+    #[test]
+    fn test_sparkline() {
+        let values = [0.0, 25.0, 50.0, 100.0, 150.0];
+        assert_eq!(sparkline(values.into_iter(), 0.0, 100.0), "▁▃▅██");
+        assert_eq!(sparkline(values.into_iter(), 50.0, 100.0), "▁▁▁██");
+
+        // A flat line is drawn at the bottom.
+        assert_eq!(sparkline([3.0, 3.0].into_iter(), 3.0, 3.0), "▁▁");
+    }
+
     /// This test exists because previously, [`SIDE_BORDERS`] was set
     /// incorrectly after I moved from tui-rs to ratatui.
     #[test]
@@ -147,7 +147,9 @@ impl Painter {
                     Cell::from("Charge").style(self.styles.text_style)
                 ]));
                 battery_charge_rows.push(Row::new([Cell::from(bars).style(
-                    if charge_percent < 10.0 {
+                    if let Some(style) = self.styles.value_colours.battery_style(charge_percent) {
+                        style
+                    } else if charge_percent < 10.0 {
                         self.styles.low_battery
                     } else if charge_percent < 50.0 {
                         self.styles.medium_battery
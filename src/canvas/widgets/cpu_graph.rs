@@ -4,18 +4,21 @@ use tui::{
 };
 
 use crate::{
-    app::{App, data::StoredData, layout_manager::WidgetDirection},
+    app::{
+        App,
+        data::{StoredData, TimeSeriesData},
+        layout_manager::WidgetDirection,
+    },
     canvas::{
         Painter,
         components::{
             data_table::{DrawInfo, SelectionState},
-            time_series::GraphData,
+            time_series::{GraphData, LegendPosition},
         },
         drawing_utils::should_hide_x_label,
     },
-    collection::cpu::CpuData,
+    collection::cpu::{CpuData, CpuDataType},
     components::time_series::GraphDrawCtx,
-    widgets::CpuWidgetState,
 };
 
 const AVG_POSITION: usize = 1;
@@ -118,13 +121,19 @@ impl Painter {
     }
 
     fn generate_points<'a>(
-        &self, cpu_widget_state: &'a CpuWidgetState, data: &'a StoredData, show_avg_cpu: bool,
+        &self, current_scroll_position: usize, data: &'a StoredData,
+        time_series: &'a TimeSeriesData, show_avg_cpu: bool,
     ) -> Vec<GraphData<'a>> {
         let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
-        let current_scroll_position = cpu_widget_state.table.state.current_index;
+        let format_usage: &dyn Fn(f64) -> String = &|usage| format!("{usage:.1}%");
         let cpu_entries = &data.cpu_harvest;
-        let cpu_points = &data.time_series_data.cpu;
-        let time = &data.time_series_data.time;
+        let cpu_points = &time_series.cpu;
+        let time = &time_series.time;
+        let label = |index: usize| match cpu_entries.get(index).map(|entry| entry.data_type) {
+            Some(CpuDataType::Avg) => "AVG".into(),
+            Some(CpuDataType::Cpu(cpu)) => format!("CPU{cpu}").into(),
+            None => "".into(),
+        };
 
         if current_scroll_position == ALL_POSITION {
             // This case ensures the other cases cannot have the position be equal to 0.
@@ -140,7 +149,11 @@ impl Painter {
                             [(itx - show_avg_offset) % self.styles.cpu_colour_styles.len()]
                     };
 
-                    GraphData::default().style(style).time(time).values(values)
+                    GraphData::default()
+                        .style(style)
+                        .time(time)
+                        .values(values)
+                        .inspect(label(itx), format_usage)
                 })
                 .rev()
                 .collect()
@@ -161,7 +174,8 @@ impl Painter {
                 GraphData::default()
                     .style(style)
                     .time(time)
-                    .values(&cpu_points[current_scroll_position - 1]),
+                    .values(&cpu_points[current_scroll_position - 1])
+                    .inspect(label(current_scroll_position - 1), format_usage),
             ]
         } else {
             vec![]
@@ -182,9 +196,11 @@ impl Painter {
                 draw_loc,
             );
 
+            let time_series = data.time_series(cpu_widget_state.graph.state().shown_span());
             let graph_data = self.generate_points(
-                cpu_widget_state,
+                cpu_widget_state.table.state.current_index,
                 data,
+                time_series,
                 app_state.app_config_fields.show_average_cpu,
             );
 
@@ -209,6 +225,11 @@ impl Painter {
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let marker = self.get_marker(app_state.app_config_fields.use_dot);
 
+            // There is no legend in the graph itself, so only show one with the stats of the
+            // selected entry, as there are too many entries to show them all.
+            let legend_stats = app_state.app_config_fields.cpu_legend_stats
+                && cpu_widget_state.table.state.current_index != ALL_POSITION;
+
             cpu_widget_state.graph.draw(
                 f,
                 draw_loc,
@@ -223,8 +244,11 @@ impl Painter {
                     hide_x_labels,
                     is_selected: app_state.current_widget.widget_id == widget_id,
                     is_expanded: app_state.is_expanded,
-                    legend_position: None,
+                    legend_position: legend_stats.then(LegendPosition::default),
                     legend_constraints: None,
+                    legend_stats,
+                    resolution: time_series.resolution,
+                    events: app_state.data_store.get_data().events.times(),
                 },
                 graph_data,
             );
@@ -0,0 +1,225 @@
+use tui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::App,
+    canvas::{Painter, drawing_utils::widget_block},
+    collection::cpu::CpuDataType,
+    widgets::{CoreGroup, HeatmapCell},
+};
+
+/// The cell sizes to try, from largest to smallest, as the width of a cell and
+/// the gap after it. Usage values are only written in cells that fit them.
+const CELL_SIZES: [(u16, u16); 3] = [(3, 1), (1, 1), (1, 0)];
+
+/// Pick the largest cell size where every group fits in `area`, returning the
+/// cell size and the number of columns.
+fn cell_layout(groups: &[CoreGroup], area: Rect) -> ((u16, u16), usize) {
+    let mut layout = ((1, 0), 1);
+    for (width, gap) in CELL_SIZES {
+        let columns = usize::from((area.width + gap) / (width + gap)).max(1);
+        let rows: usize = groups
+            .iter()
+            .map(|group| usize::from(group.label.is_some()) + group.cores.len().div_ceil(columns))
+            .sum();
+
+        layout = ((width, gap), columns);
+        if rows <= usize::from(area.height) {
+            break;
+        }
+    }
+
+    layout
+}
+
+/// Return a readable text colour on top of `background`.
+fn text_colour(background: Color) -> Color {
+    match background {
+        Color::Rgb(r, g, b) => {
+            let luminance = 0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b);
+            if luminance > 140.0 {
+                Color::Black
+            } else {
+                Color::White
+            }
+        }
+        Color::Black | Color::Red | Color::Blue | Color::Magenta | Color::DarkGray => Color::White,
+        Color::Reset => Color::Reset,
+        _ => Color::Black,
+    }
+}
+
+impl Painter {
+    pub fn draw_cpu_heatmap(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_selected = widget_id == app_state.current_widget.widget_id;
+
+        if let Some(widget_state) = app_state
+            .states
+            .cpu_heatmap_state
+            .get_mut_widget_state(widget_id)
+        {
+            let mut usages = Vec::new();
+            for cpu in &app_state.data_store.get_data().cpu_harvest {
+                if let CpuDataType::Cpu(index) = cpu.data_type {
+                    if usages.len() <= index {
+                        usages.resize(index + 1, 0.0);
+                    }
+                    usages[index] = cpu.usage;
+                }
+            }
+
+            let groups = widget_state.groups(0..usages.len());
+
+            let mut block = widget_block(
+                app_state.app_config_fields.use_basic_mode,
+                is_selected,
+                self.styles.border_type,
+                self.styles.general_widget_style,
+            )
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title_top(Line::styled(
+                " CPU Heatmap ",
+                self.styles.widget_title_style,
+            ));
+
+            // Show the exact usage of the hovered or selected core in the title.
+            if let Some((core, usage)) = widget_state
+                .focused_core()
+                .and_then(|core| usages.get(core).map(|usage| (core, usage)))
+            {
+                let group = groups
+                    .iter()
+                    .find(|group| group.cores.contains(&core))
+                    .and_then(|group| group.label.as_deref());
+                let details = match group {
+                    Some(group) => format!(" CPU {core} ({group}): {usage:.1}% "),
+                    None => format!(" CPU {core}: {usage:.1}% "),
+                };
+                let style =
+                    self.styles.cpu_colour_styles[core % self.styles.cpu_colour_styles.len()];
+
+                block = block.title_top(Line::styled(details, style));
+            }
+
+            if app_state.is_expanded {
+                block = block.title_top(
+                    Line::styled(" Esc to go back ", self.styles.widget_title_style)
+                        .right_aligned(),
+                );
+            }
+
+            let inner = block.inner(draw_loc);
+            let ((cell_width, gap), columns) = cell_layout(&groups, inner);
+
+            let mut lines = Vec::new();
+            let mut cells = Vec::with_capacity(usages.len());
+            let mut row = 0;
+            for group in &groups {
+                if let Some(label) = &group.label {
+                    lines.push(Line::styled(label.clone(), self.styles.text_style));
+                }
+
+                for row_cores in group.cores.chunks(columns) {
+                    let y = inner.y + lines.len() as u16;
+                    let mut spans = Vec::with_capacity(row_cores.len() * 2);
+
+                    for (column, &core) in row_cores.iter().enumerate() {
+                        let usage = usages[core];
+                        let background = self
+                            .styles
+                            .value_colours
+                            .cpu_heatmap_colour(f64::from(usage));
+                        let mut style = Style::new().bg(background).fg(text_colour(background));
+                        if widget_state.selected == Some(core) {
+                            style = style.add_modifier(Modifier::REVERSED);
+                        }
+                        if widget_state.hovered == Some(core) {
+                            style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                        }
+
+                        let text = if cell_width >= 3 {
+                            format!("{usage:>3.0}")
+                        } else {
+                            " ".repeat(usize::from(cell_width))
+                        };
+                        spans.push(Span::styled(text, style));
+                        if gap > 0 {
+                            spans.push(Span::raw(" ".repeat(usize::from(gap))));
+                        }
+
+                        cells.push(HeatmapCell {
+                            core,
+                            row,
+                            column,
+                            area: Rect::new(
+                                inner.x + column as u16 * (cell_width + gap),
+                                y,
+                                cell_width,
+                                1,
+                            ),
+                        });
+                    }
+
+                    lines.push(Line::from(spans));
+                    row += 1;
+                }
+            }
+
+            // Cells that didn't fit aren't drawn, so they can't be selected either.
+            cells.retain(|cell| cell.area.y < inner.bottom());
+            widget_state.cells = cells;
+
+            f.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .style(self.styles.text_style),
+                draw_loc,
+            );
+        }
+
+        // Update draw loc in widget map.
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::layout::Rect;
+
+    use super::cell_layout;
+    use crate::widgets::CoreGroup;
+
+    #[test]
+    fn cells_shrink_to_fit() {
+        let groups = vec![
+            CoreGroup {
+                label: Some("Socket 0".to_string()),
+                cores: (0..16).collect(),
+            },
+            CoreGroup {
+                label: Some("Socket 1".to_string()),
+                cores: (16..32).collect(),
+            },
+        ];
+
+        // 8 columns of usage values, in two rows per socket.
+        assert_eq!(cell_layout(&groups, Rect::new(0, 0, 32, 6)), ((3, 1), 8));
+        // Too short for usage values, so use 16 narrow columns.
+        assert_eq!(cell_layout(&groups, Rect::new(0, 0, 32, 4)), ((1, 1), 16));
+        // Still too short, so drop the gaps.
+        assert_eq!(cell_layout(&groups, Rect::new(0, 0, 16, 4)), ((1, 0), 16));
+    }
+}
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+
+use tui::{
+    Frame,
+    layout::{Constraint, Rect},
+};
+
+use crate::{
+    app::App,
+    canvas::{
+        Painter,
+        components::time_series::{AxisBound, ChartScaling, GraphData, LegendConstraints},
+        drawing_utils::should_hide_x_label,
+    },
+    components::time_series::GraphDrawCtx,
+};
+
+impl Painter {
+    pub fn draw_custom_graph(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(widget_state) = app_state
+            .states
+            .custom_state
+            .get_mut_widget_state(widget_id)
+        {
+            let shared_data = app_state.data_store.get_data();
+            let time_series = shared_data.time_series(widget_state.graph.state().shown_span());
+            let times = &(time_series.time);
+            let source = time_series.custom.get(&widget_state.source);
+
+            // Pair each displayed series with its stored values, in display order.
+            let points: Vec<_> = match source {
+                Some(source) if widget_state.series.is_empty() => source
+                    .iter()
+                    .map(|(key, values)| (key.as_str(), "", None, values))
+                    .collect(),
+                Some(source) => widget_state
+                    .series
+                    .iter()
+                    .filter_map(|series| {
+                        source.get(&series.key).map(|values| {
+                            (
+                                series.name.as_str(),
+                                series.unit.as_str(),
+                                series.style,
+                                values,
+                            )
+                        })
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                widget_state.graph.state_mut().autohide_timer_mut(),
+                draw_loc,
+            );
+
+            let y_max = match widget_state.max {
+                Some(max) => max,
+                None => widget_state
+                    .graph
+                    .y_max(points.iter().map(|(_, _, _, values)| *values), times),
+            };
+
+            // Only label the axis with a unit if every series shares it.
+            let unit = match points.first() {
+                Some((_, unit, _, _)) if points.iter().all(|(_, u, _, _)| u == unit) => *unit,
+                _ => "",
+            };
+            let (adjusted_y_max, y_labels) = adjust_custom_data_point(y_max, unit);
+            let y_bounds = AxisBound::Max(adjusted_y_max);
+
+            // Hide the legend if the width is 90% of the total widget width
+            // or the height is greater than 50% of the total widget height.
+            let legend_constraints = LegendConstraints {
+                width: Constraint::Ratio(9, 10),
+                height: Constraint::Ratio(1, 2),
+            };
+
+            let formats: Vec<_> = points
+                .iter()
+                .map(|(_, unit, _, _)| move |value: f64| format!("{}{unit}", format_value(value)))
+                .collect();
+            let graph_data: Vec<GraphData<'_, f64>> = points
+                .iter()
+                .enumerate()
+                .map(|(itx, (name, unit, style, values))| {
+                    let legend = match values.last() {
+                        Some(latest) => format!("{name}: {}{unit}", format_value(*latest)).into(),
+                        None => Cow::from(*name),
+                    };
+                    let style = style.unwrap_or(
+                        self.styles.cpu_colour_styles[itx % self.styles.cpu_colour_styles.len()],
+                    );
+
+                    GraphData::default()
+                        .name(legend)
+                        .style(style)
+                        .time(times)
+                        .values(values)
+                        .inspect(Cow::from(*name), &formats[itx])
+                })
+                .collect();
+
+            let marker = self.get_marker(app_state.app_config_fields.use_dot);
+            let y_labels: Vec<Cow<'_, str>> = y_labels.into_iter().map(Into::into).collect();
+            let title = format!(" {} ", widget_state.title);
+
+            widget_state.graph.draw(
+                f,
+                draw_loc,
+                GraphDrawCtx {
+                    title: title.into(),
+                    border_style,
+                    title_style: self.styles.widget_title_style,
+                    graph_style: self.styles.graph_style,
+                    general_widget_style: self.styles.general_widget_style,
+                    border_type: self.styles.border_type,
+                    marker,
+                    hide_x_labels,
+                    is_selected: app_state.current_widget.widget_id == widget_id,
+                    is_expanded: app_state.is_expanded,
+                    legend_position: widget_state.legend_position,
+                    legend_stats: false,
+                    resolution: time_series.resolution,
+                    legend_constraints: Some(legend_constraints),
+                    events: app_state.data_store.get_data().events.times(),
+                },
+                y_bounds,
+                &y_labels,
+                ChartScaling::Linear,
+                graph_data,
+            );
+        }
+
+        // Update draw loc in widget map.
+        if app_state.should_get_widget_bounds() {
+            if let Some(custom_widget) = app_state.widget_map.get_mut(&widget_id) {
+                custom_widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                custom_widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+/// Format a value to a reasonable number of decimal places.
+fn format_value(value: f64) -> String {
+    if value.abs() >= 100.0 || value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// Returns the adjusted upper bound and the required labels.
+fn adjust_custom_data_point(max_entry: f64, unit: &str) -> (f64, [String; 3]) {
+    // Avoid a zero-height graph.
+    let max_entry = if max_entry > 0.0 { max_entry } else { 1.0 };
+
+    let labels = [
+        format!("0{unit}"),
+        format!("{}{unit}", format_value(max_entry / 2.0)),
+        format!("{}{unit}", format_value(max_entry)),
+    ];
+
+    (max_entry, labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_values() {
+        assert_eq!(format_value(5.0), "5");
+        assert_eq!(format_value(0.126), "0.13");
+        assert_eq!(format_value(1234.4), "1234");
+    }
+
+    #[test]
+    fn zero_max_is_raised() {
+        let (max, labels) = adjust_custom_data_point(0.0, "ms");
+        assert_eq!(max, 1.0);
+        assert_eq!(labels, ["0ms", "0.50ms", "1ms"]);
+    }
+}
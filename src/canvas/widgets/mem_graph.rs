@@ -48,7 +48,8 @@ fn graph_data<'a>(
                 .name(label)
                 .time(time)
                 .values(values)
-                .style(style),
+                .style(style)
+                .inspect(name.to_string().into(), &|usage| format!("{usage:.1}%")),
         );
     }
 }
@@ -86,7 +87,7 @@ impl Painter {
                 }
 
                 let mut points = Vec::with_capacity(size);
-                let time_series = &data.time_series_data;
+                let time_series = data.time_series(mem_state.graph.state().shown_span());
                 let time = &time_series.time;
 
                 // TODO: Add a "no data" option here/to time graph if there is no entries
@@ -138,7 +139,7 @@ impl Painter {
                     let gpu_styles = &self.styles.gpu_colours;
 
                     for (name, harvest) in &data.gpu_harvest {
-                        if let Some(gpu_data) = data.time_series_data.gpu_mem.get(name) {
+                        if let Some(gpu_data) = time_series.gpu_mem.get(name) {
                             let style = {
                                 if gpu_styles.is_empty() {
                                     Style::default()
@@ -167,6 +168,11 @@ impl Painter {
 
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let marker = self.get_marker(app_state.app_config_fields.use_dot);
+            let resolution = app_state
+                .data_store
+                .get_data()
+                .time_series(mem_state.graph.state().shown_span())
+                .resolution;
 
             mem_state.graph.draw(
                 f,
@@ -187,6 +193,9 @@ impl Painter {
                         width: Constraint::Ratio(3, 4),
                         height: Constraint::Ratio(3, 4),
                     }),
+                    legend_stats: app_state.app_config_fields.memory_legend_stats,
+                    resolution,
+                    events: app_state.data_store.get_data().events.times(),
                 },
                 graph_data,
             );
@@ -2,6 +2,8 @@ use crate::{collection::network::NetworkHarvest, utils::data_units::convert_byte
 
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod cpu_heatmap;
+pub mod custom;
 pub mod disk_table;
 pub mod mem_basic;
 pub mod mem_graph;
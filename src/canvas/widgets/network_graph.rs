@@ -63,9 +63,11 @@ impl Painter {
         {
             let shared_data = app_state.data_store.get_data();
             let network_latest_data = &(shared_data.network_harvest);
-            let rx_points = &(shared_data.time_series_data.rx);
-            let tx_points = &(shared_data.time_series_data.tx);
-            let times = &(shared_data.time_series_data.time);
+            let time_series =
+                shared_data.time_series(network_widget_state.graph.state().shown_span());
+            let rx_points = &(time_series.rx);
+            let tx_points = &(time_series.tx);
+            let times = &(time_series.time);
 
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let hide_x_labels = should_hide_x_label(
@@ -111,17 +113,23 @@ impl Painter {
             let tx = get_unit_prefix(network_latest_data.tx, use_binary_prefix);
             let total_rx = convert_bits(network_latest_data.total_rx, use_binary_prefix);
             let total_tx = convert_bits(network_latest_data.total_tx, use_binary_prefix);
+            let format_rate = |rate: f64| {
+                let (value, prefix) = get_unit_prefix(rate as u64, use_binary_prefix);
+                format!("{value:.1}{prefix}{unit}")
+            };
 
             let graph_data = if use_old_network_legend {
                 let mut graph_data = vec![
                     GraphData::default()
                         .time(times)
                         .values(rx_points)
-                        .style(self.styles.rx_style),
+                        .style(self.styles.rx_style)
+                        .inspect("RX".into(), &format_rate),
                     GraphData::default()
                         .time(times)
                         .values(tx_points)
-                        .style(self.styles.tx_style),
+                        .style(self.styles.tx_style)
+                        .inspect("TX".into(), &format_rate),
                 ];
 
                 graph_data.extend([
@@ -163,12 +171,14 @@ impl Painter {
                                 .name(format!("RX: {rx_label:<10} All: {total_rx_label:<8} Packets: {rx_packet_rate:>8}pkt/s Avg: {avg_rx_packet_size_label}").into())
                                 .time(times)
                                 .values(rx_points)
-                                .style(self.styles.rx_style),
+                                .style(self.styles.rx_style)
+                                .inspect("RX".into(), &format_rate),
                             GraphData::default()
                                 .name(format!("TX: {tx_label:<10} All: {total_tx_label:<8} Packets: {tx_packet_rate:>8}pkt/s Avg: {avg_tx_packet_size_label}").into())
                                 .time(times)
                                 .values(tx_points)
-                                .style(self.styles.tx_style),
+                                .style(self.styles.tx_style)
+                                .inspect("TX".into(), &format_rate),
                         ]
                 } else {
                     vec![
@@ -176,12 +186,14 @@ impl Painter {
                             .name(format!("RX: {rx_label:<10} All: {total_rx_label}").into())
                             .time(times)
                             .values(rx_points)
-                            .style(self.styles.rx_style),
+                            .style(self.styles.rx_style)
+                            .inspect("RX".into(), &format_rate),
                         GraphData::default()
                             .name(format!("TX: {tx_label:<10} All: {total_tx_label}").into())
                             .time(times)
                             .values(tx_points)
-                            .style(self.styles.tx_style),
+                            .style(self.styles.tx_style)
+                            .inspect("TX".into(), &format_rate),
                     ]
                 }
             };
@@ -217,7 +229,10 @@ impl Painter {
                     is_selected: app_state.current_widget.widget_id == widget_id,
                     is_expanded: app_state.is_expanded,
                     legend_position: app_state.app_config_fields.network_legend_position,
+                    legend_stats: app_state.app_config_fields.network_legend_stats,
+                    resolution: time_series.resolution,
                     legend_constraints: Some(legend_constraints),
+                    events: app_state.data_store.get_data().events.times(),
                 },
                 y_bounds,
                 &y_labels,
@@ -6,19 +6,27 @@ use tui::{
 };
 
 use crate::{
-    app::App,
+    app::{
+        App,
+        data::{PROCESS_HISTORY_LEN, ProcessSample},
+    },
     canvas::{
         Painter,
         components::{
             data_table::{DrawInfo, SelectionState},
             search_input::build_query_spans,
         },
-        drawing_utils::widget_block,
+        drawing_utils::{sparkline, widget_block},
     },
+    dec_bytes_per_second_string,
+    widgets::MemUsage,
 };
 
 const SORT_MENU_WIDTH: u16 = 7;
 
+/// The most processes to show the usage history of at once.
+const MAX_HISTORY_ROWS: usize = 6;
+
 impl Painter {
     /// Draws and handles all process-related drawing.  Use this.
     /// - `widget_id` here represents the widget ID of the process widget
@@ -30,6 +38,17 @@ impl Painter {
             let is_basic = app_state.app_config_fields.use_basic_mode;
             let search_height = if !is_basic { 5 } else { 3 };
             let is_sort_open = proc_widget_state.is_sort_open;
+            let history_height = proc_widget_state.show_history.then(|| {
+                let num_processes = proc_widget_state
+                    .history_processes(
+                        &app_state.data_store.get_data().process_data,
+                        &app_state.app_config_fields.process_watch_list,
+                    )
+                    .len();
+                let borders = if is_basic { 0 } else { 2 };
+
+                num_processes.clamp(1, MAX_HISTORY_ROWS) as u16 + borders
+            });
 
             let mut proc_draw_loc = draw_loc;
             if proc_widget_state.is_search_enabled() {
@@ -42,6 +61,16 @@ impl Painter {
                 self.draw_search_field(f, app_state, processes_chunk[1], widget_id + 1);
             }
 
+            if let Some(history_height) = history_height {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(history_height)])
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_process_history(f, app_state, processes_chunk[1], widget_id);
+            }
+
             if is_sort_open {
                 let processes_chunk = Layout::default()
                     .direction(Direction::Horizontal)
@@ -68,6 +97,99 @@ impl Painter {
         }
     }
 
+    /// Draws sparklines of the usage history of the selected and watched
+    /// processes.
+    /// - `widget_id` represents the widget ID of the process widget itself.
+    fn draw_process_history(
+        &self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64,
+    ) {
+        /// The width of the name and PID of each process.
+        const LABEL_WIDTH: usize = 24;
+        /// The width of each metric, besides its sparkline.
+        const METRIC_WIDTH: usize = 14;
+
+        let Some(proc_widget_state) = app_state.states.proc_state.widget_states.get(&widget_id)
+        else {
+            return;
+        };
+
+        let is_basic = app_state.app_config_fields.use_basic_mode;
+        let process_data = &app_state.data_store.get_data().process_data;
+        let processes = proc_widget_state.history_processes(
+            process_data,
+            &app_state.app_config_fields.process_watch_list,
+        );
+
+        let mut block = widget_block(
+            is_basic,
+            false,
+            self.styles.border_type,
+            self.styles.general_widget_style,
+        )
+        .border_style(self.styles.border_style);
+        if !is_basic {
+            block = block.title_top(Line::styled(" History ", self.styles.widget_title_style));
+        }
+
+        let inner_width = usize::from(block.inner(draw_loc).width);
+        let spark_width = (inner_width.saturating_sub(LABEL_WIDTH + 3 * METRIC_WIDTH) / 3)
+            .clamp(1, PROCESS_HISTORY_LEN);
+
+        let lines: Vec<Line<'_>> = if processes.is_empty() {
+            vec![Line::styled(
+                "No process is selected or watched.",
+                self.styles.text_style,
+            )]
+        } else {
+            processes
+                .iter()
+                .take(MAX_HISTORY_ROWS)
+                .map(|process| {
+                    let samples: Vec<ProcessSample> = process_data
+                        .history
+                        .get(&process.pid)
+                        .map(|history| {
+                            let skip = history.len().saturating_sub(spark_width);
+                            history.iter().skip(skip).copied().collect()
+                        })
+                        .unwrap_or_default();
+
+                    // Memory is scaled to its own range, so slow growth stands out.
+                    let cpu = samples
+                        .iter()
+                        .map(|sample| f64::from(sample.cpu_usage_percent));
+                    let mem = samples.iter().map(|sample| sample.mem_usage as f64);
+                    let io = samples.iter().map(|sample| sample.io_per_sec as f64);
+                    let cpu_line = sparkline(cpu.clone(), 0.0, cpu.fold(0.0, f64::max));
+                    let mem_line = sparkline(
+                        mem.clone(),
+                        mem.clone().fold(f64::INFINITY, f64::min),
+                        mem.fold(0.0, f64::max),
+                    );
+                    let io_line = sparkline(io.clone(), 0.0, io.fold(0.0, f64::max));
+
+                    let label = unicode_ellipsis::truncate_str(
+                        &format!("{} ({})", process.name, process.pid),
+                        LABEL_WIDTH - 1,
+                    )
+                    .to_string();
+                    let text = format!(
+                        "{label:<LABEL_WIDTH$}CPU {cpu_line:>spark_width$} {:>6.1}%  \
+                         Mem {mem_line:>spark_width$} {:>7}  \
+                         I/O {io_line:>spark_width$} {:>9}",
+                        process.cpu_usage_percent,
+                        MemUsage::Bytes(process.mem_usage).to_string(),
+                        dec_bytes_per_second_string(process.read_per_sec + process.write_per_sec),
+                    );
+
+                    Line::styled(text, self.styles.text_style)
+                })
+                .collect()
+        };
+
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+
     /// Draws the process sort box.
     /// - `widget_id` represents the widget ID of the process widget itself.
     fn draw_processes_table(
@@ -25,8 +25,9 @@ impl Painter {
             .get_mut_widget_state(widget_id)
         {
             let shared_data = app_state.data_store.get_data();
-            let points = &(shared_data.time_series_data.temperature);
-            let times = &(shared_data.time_series_data.time);
+            let time_series = shared_data.time_series(widget_state.graph.state().shown_span());
+            let points = &(time_series.temperature);
+            let times = &(time_series.time);
 
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let hide_x_labels = should_hide_x_label(
@@ -49,6 +50,7 @@ impl Painter {
             };
 
             let unit = app_state.app_config_fields.temperature_type.unit();
+            let format_temp = |temp: f64| format!("{temp:.1}{unit}");
             let graph_data: Vec<GraphData<'_, f32>> = points
                 .iter()
                 .enumerate()
@@ -66,6 +68,7 @@ impl Painter {
                         )
                         .time(times)
                         .values(values)
+                        .inspect(source.as_str().into(), &format_temp)
                 })
                 .collect();
 
@@ -88,7 +91,10 @@ impl Painter {
                     is_selected: app_state.current_widget.widget_id == widget_id,
                     is_expanded: app_state.is_expanded,
                     legend_position: app_state.app_config_fields.temperature_legend_position,
+                    legend_stats: app_state.app_config_fields.temperature_legend_stats,
+                    resolution: time_series.resolution,
                     legend_constraints: Some(legend_constraints),
+                    events: app_state.data_store.get_data().events.times(),
                 },
                 y_bounds,
                 &y_labels,
@@ -17,6 +17,7 @@ mod linux {
 #[cfg(feature = "battery")]
 pub mod batteries;
 pub mod cpu;
+pub mod custom;
 pub mod disks;
 pub mod error;
 pub mod memory;
@@ -59,6 +60,7 @@ pub struct Data {
     pub arc: Option<memory::MemData>,
     #[cfg(feature = "gpu")]
     pub gpu: Option<Vec<(String, memory::MemData)>>,
+    pub custom: Option<Vec<custom::CustomHarvest>>,
 }
 
 impl Default for Data {
@@ -82,6 +84,7 @@ impl Default for Data {
             arc: None,
             #[cfg(feature = "gpu")]
             gpu: None,
+            custom: None,
         }
     }
 }
@@ -96,6 +99,7 @@ impl Data {
         self.swap = None;
         self.cpu = None;
         self.load_avg = None;
+        self.custom = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -166,6 +170,7 @@ pub struct DataCollector {
     use_current_cpu_total: bool,
     show_average_cpu: bool,
     get_process_threads: bool,
+    get_process_affinity: bool,
 
     last_list_collection_time: Instant,
     should_run_less_routine_tasks: bool,
@@ -194,6 +199,8 @@ pub struct DataCollector {
 
     #[cfg(target_os = "linux")]
     cgroup_memory_data: CgroupMemCollector,
+
+    custom: custom::CustomCollector,
 }
 
 const LESS_ROUTINE_TASK_TIME: Duration = Duration::from_secs(60);
@@ -216,6 +223,7 @@ impl DataCollector {
             use_current_cpu_total: false,
             unnormalized_cpu: false,
             get_process_threads: false,
+            get_process_affinity: false,
             last_collection_time,
             total_rx: 0,
             total_tx: 0,
@@ -240,6 +248,7 @@ impl DataCollector {
             should_run_less_routine_tasks: true,
             #[cfg(target_os = "linux")]
             cgroup_memory_data: CgroupMemCollector::default(),
+            custom: custom::CustomCollector::default(),
         }
     }
 
@@ -265,6 +274,10 @@ impl DataCollector {
         }
     }
 
+    pub fn set_filters(&mut self, filters: DataFilters) {
+        self.filters = filters;
+    }
+
     pub fn set_collection(&mut self, used_widgets: UsedWidgets) {
         self.widgets_to_harvest = used_widgets;
     }
@@ -285,6 +298,17 @@ impl DataCollector {
         self.get_process_threads = get_process_threads;
     }
 
+    pub fn set_get_process_affinity(&mut self, get_process_affinity: bool) {
+        self.get_process_affinity = get_process_affinity;
+    }
+
+    pub fn set_custom_sources(&mut self, sources: Vec<custom::CustomSource>) {
+        // Keep the last values and run times if nothing changed.
+        if !self.custom.has_sources(&sources) {
+            self.custom = custom::CustomCollector::new(sources);
+        }
+    }
+
     #[cfg(feature = "zfs")]
     pub fn set_free_arc_mem(&mut self, free_mem: bool) {
         self.free_arc_mem = free_mem;
@@ -385,6 +409,7 @@ impl DataCollector {
         self.update_processes();
         self.update_network_usage();
         self.update_disks();
+        self.update_custom();
 
         // Make sure to run this to refresh the setting.
         self.should_run_less_routine_tasks = false;
@@ -491,6 +516,13 @@ impl DataCollector {
         }
     }
 
+    #[inline]
+    fn update_custom(&mut self) {
+        if self.widgets_to_harvest.use_custom {
+            self.data.custom = Some(self.custom.harvest(self.data.collection_time));
+        }
+    }
+
     #[inline]
     fn update_memory_usage(&mut self) {
         if self.widgets_to_harvest.use_mem {
@@ -1,6 +1,7 @@
 //! Data collection for CPU usage and load average.
 
 pub mod sysinfo;
+pub mod topology;
 pub use self::sysinfo::*;
 
 pub type LoadAvgHarvest = [f32; 3];
@@ -0,0 +1,111 @@
+//! The socket and NUMA node of each CPU, used to group CPUs together.
+//!
+//! This is only read on Linux, through sysfs. On other platforms, no CPU has a
+//! known location.
+
+/// Where a CPU is located.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuLocation {
+    /// The physical package (socket) the CPU is on.
+    pub socket: Option<usize>,
+
+    /// The NUMA node the CPU belongs to.
+    pub numa_node: Option<usize>,
+}
+
+/// Return the location of each CPU, indexed by the CPU's number. CPUs whose
+/// location is unknown are given an empty [`CpuLocation`].
+pub fn get_cpu_locations() -> Vec<CpuLocation> {
+    #[cfg(target_os = "linux")]
+    {
+        read_cpu_locations(std::path::Path::new("/sys/devices/system/cpu"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Read the CPU locations from a sysfs CPU directory, such as
+/// `/sys/devices/system/cpu`.
+#[cfg(target_os = "linux")]
+fn read_cpu_locations(cpu_dir: &std::path::Path) -> Vec<CpuLocation> {
+    use std::fs;
+
+    /// Parse the number after `prefix` in a file name like `cpu12`.
+    fn numbered(name: &std::ffi::OsStr, prefix: &str) -> Option<usize> {
+        name.to_str()?.strip_prefix(prefix)?.parse().ok()
+    }
+
+    let Ok(entries) = fs::read_dir(cpu_dir) else {
+        return Vec::new();
+    };
+
+    let mut locations = Vec::new();
+    for entry in entries.flatten() {
+        let Some(cpu) = numbered(&entry.file_name(), "cpu") else {
+            continue;
+        };
+        let path = entry.path();
+
+        let socket = fs::read_to_string(path.join("topology/physical_package_id"))
+            .ok()
+            .and_then(|id| id.trim().parse().ok());
+
+        // Each CPU directory has a `nodeN` link to the NUMA node it belongs to.
+        let numa_node = fs::read_dir(&path).ok().and_then(|entries| {
+            entries
+                .flatten()
+                .find_map(|entry| numbered(&entry.file_name(), "node"))
+        });
+
+        if locations.len() <= cpu {
+            locations.resize(cpu + 1, CpuLocation::default());
+        }
+        locations[cpu] = CpuLocation { socket, numa_node };
+    }
+
+    locations
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use std::fs;
+
+    use super::{CpuLocation, read_cpu_locations};
+
+    #[test]
+    fn cpu_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        let cpu_dir = dir.path();
+
+        for (cpu, socket, node) in [(0, 0, 0), (1, 0, 0), (3, 1, 1)] {
+            let topology = cpu_dir.join(format!("cpu{cpu}/topology"));
+            fs::create_dir_all(&topology).unwrap();
+            fs::write(topology.join("physical_package_id"), format!("{socket}\n")).unwrap();
+            fs::create_dir(cpu_dir.join(format!("cpu{cpu}/node{node}"))).unwrap();
+        }
+        fs::create_dir(cpu_dir.join("cpufreq")).unwrap();
+
+        let locations = read_cpu_locations(cpu_dir);
+        assert_eq!(
+            locations,
+            vec![
+                CpuLocation {
+                    socket: Some(0),
+                    numa_node: Some(0)
+                },
+                CpuLocation {
+                    socket: Some(0),
+                    numa_node: Some(0)
+                },
+                CpuLocation::default(),
+                CpuLocation {
+                    socket: Some(1),
+                    numa_node: Some(1)
+                },
+            ]
+        );
+    }
+}
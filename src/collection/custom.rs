@@ -0,0 +1,464 @@
+//! Data collection for custom metric sources, which are fed by either running a
+//! command or reading a file.
+
+use std::{
+    io::Read,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+/// How often to check whether a running command has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Where a custom source gets its values from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CustomInput {
+    /// A program followed by its arguments.
+    Command(Vec<String>),
+
+    /// A file to read.
+    File(PathBuf),
+}
+
+/// How to parse the output of a custom source.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CustomFormat {
+    /// Guess the format from the output.
+    #[default]
+    Auto,
+
+    /// Whitespace-separated numbers, keyed by their index.
+    Plain,
+
+    /// `key=value` or `key: value` pairs, one per line.
+    KeyValue,
+
+    /// A JSON object or array; nested values are keyed with dots.
+    Json,
+}
+
+impl std::str::FromStr for CustomFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(CustomFormat::Auto),
+            "plain" => Ok(CustomFormat::Plain),
+            "key_value" | "kv" => Ok(CustomFormat::KeyValue),
+            "json" => Ok(CustomFormat::Json),
+            _ => Err(format!(
+                "'{s}' is an invalid format, must be one of \"auto\", \"plain\", \"key_value\", or \"json\""
+            )),
+        }
+    }
+}
+
+/// A custom metric source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomSource {
+    pub name: String,
+    pub input: CustomInput,
+    pub format: CustomFormat,
+    pub interval: Option<Duration>,
+    pub timeout: Duration,
+}
+
+/// The values harvested from a custom source.
+#[derive(Clone, Debug, Default)]
+pub struct CustomHarvest {
+    pub name: String,
+    pub values: Vec<(String, f64)>,
+}
+
+/// Values parsed from a source's output.
+type CustomValues = Vec<(String, f64)>;
+
+/// A custom source, along with the thread that runs it in the background so a
+/// slow source can't hold up the rest of data collection.
+#[derive(Debug)]
+struct CustomWorker {
+    source: CustomSource,
+    last_run: Option<Instant>,
+    is_running: bool,
+    last_values: Option<CustomValues>,
+
+    /// Asks the thread to run the source once.
+    requests: mpsc::Sender<()>,
+
+    /// The values from each run, or [`None`] if it failed.
+    results: mpsc::Receiver<Option<CustomValues>>,
+}
+
+impl CustomWorker {
+    fn new(source: CustomSource) -> Self {
+        let (requests, request_receiver) = mpsc::channel::<()>();
+        let (result_sender, results) = mpsc::channel();
+
+        // The thread stops once the worker is dropped and its current run, if
+        // any, has finished.
+        let thread_source = source.clone();
+        std::thread::spawn(move || {
+            for () in request_receiver {
+                let values = read_source(&thread_source)
+                    .map(|output| parse_output(thread_source.format, &output))
+                    .filter(|values| !values.is_empty());
+
+                if result_sender.send(values).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            source,
+            last_run: None,
+            is_running: false,
+            last_values: None,
+            requests,
+            results,
+        }
+    }
+}
+
+/// Keeps track of when each custom source was last run, and what it returned.
+#[derive(Debug, Default)]
+pub(crate) struct CustomCollector {
+    workers: Vec<CustomWorker>,
+}
+
+impl CustomCollector {
+    pub(crate) fn new(sources: Vec<CustomSource>) -> Self {
+        Self {
+            workers: sources.into_iter().map(CustomWorker::new).collect(),
+        }
+    }
+
+    /// Whether this collects exactly the given sources.
+    pub(crate) fn has_sources(&self, sources: &[CustomSource]) -> bool {
+        self.workers
+            .iter()
+            .map(|worker| &worker.source)
+            .eq(sources.iter())
+    }
+
+    /// Return the latest values for every source, and start any that are due
+    /// in the background. This never waits on a source.
+    ///
+    /// A source that is still running, or not yet due, returns the values from
+    /// its last finished run.
+    pub(crate) fn harvest(&mut self, now: Instant) -> Vec<CustomHarvest> {
+        self.workers
+            .iter_mut()
+            .filter_map(|worker| {
+                if let Ok(values) = worker.results.try_recv() {
+                    worker.is_running = false;
+                    worker.last_values = values;
+                }
+
+                let is_due = match (worker.last_run, worker.source.interval) {
+                    (Some(last_run), Some(interval)) => now.duration_since(last_run) >= interval,
+                    _ => true,
+                };
+
+                if is_due && !worker.is_running && worker.requests.send(()).is_ok() {
+                    worker.is_running = true;
+                    worker.last_run = Some(now);
+                }
+
+                worker.last_values.as_ref().map(|values| CustomHarvest {
+                    name: worker.source.name.clone(),
+                    values: values.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Read the raw output of a source, or [`None`] if it failed.
+fn read_source(source: &CustomSource) -> Option<String> {
+    match &source.input {
+        CustomInput::File(path) => std::fs::read_to_string(path).ok(),
+        CustomInput::Command(command) => run_command(command, source.timeout),
+    }
+}
+
+/// Run a command and return its stdout if it exits successfully within `timeout`.
+fn run_command(command: &[String], timeout: Duration) -> Option<String> {
+    let (program, args) = command.split_first()?;
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    // Give the command its own process group, so anything it starts can be
+    // killed along with it.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let mut child = command.spawn().ok()?;
+
+    // Read on a separate thread so a chatty command can't fill the pipe and block.
+    let mut stdout = child.stdout.take()?;
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = sender.send(stdout.read_to_string(&mut output).ok().map(|_| output));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            _ => {
+                kill_command(&mut child);
+                break None;
+            }
+        }
+    };
+
+    // Anything the command started can hold stdout open after it exits, so
+    // don't wait on the reader past the deadline either.
+    let output = receiver
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()
+        .flatten();
+    if output.is_none() {
+        kill_command(&mut child);
+    }
+
+    status.filter(|status| status.success()).and(output)
+}
+
+/// Kill a command, along with anything it started that is still in its
+/// process group.
+fn kill_command(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: `killpg` has no memory safety requirements. The command leads
+        // its own process group, so its ID is also the group's ID.
+        unsafe {
+            libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Parse the output of a custom source into named values.
+pub fn parse_output(format: CustomFormat, output: &str) -> Vec<(String, f64)> {
+    match format {
+        CustomFormat::Auto => {
+            let trimmed = output.trim_start();
+            if trimmed.starts_with('{') || trimmed.starts_with('[') {
+                parse_json(output)
+            } else if output.lines().any(|line| line.contains(['=', ':'])) {
+                parse_key_value(output)
+            } else {
+                parse_plain(output)
+            }
+        }
+        CustomFormat::Plain => parse_plain(output),
+        CustomFormat::KeyValue => parse_key_value(output),
+        CustomFormat::Json => parse_json(output),
+    }
+}
+
+fn parse_plain(output: &str) -> Vec<(String, f64)> {
+    output
+        .split_whitespace()
+        .filter_map(|value| value.parse::<f64>().ok())
+        .enumerate()
+        .map(|(index, value)| (index.to_string(), value))
+        .collect()
+}
+
+fn parse_key_value(output: &str) -> Vec<(String, f64)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=').or_else(|| line.split_once(':'))?;
+            let key = key.trim();
+            let value = value.trim().parse::<f64>().ok()?;
+
+            (!key.is_empty()).then(|| (key.to_string(), value))
+        })
+        .collect()
+}
+
+fn parse_json(output: &str) -> Vec<(String, f64)> {
+    fn flatten(prefix: &str, value: &serde_json::Value, values: &mut Vec<(String, f64)>) {
+        let join = |key: &str| {
+            if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{prefix}.{key}")
+            }
+        };
+
+        match value {
+            serde_json::Value::Number(number) => {
+                if let Some(number) = number.as_f64() {
+                    values.push((prefix.to_string(), number));
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    flatten(&join(key), value, values);
+                }
+            }
+            serde_json::Value::Array(array) => {
+                for (index, value) in array.iter().enumerate() {
+                    flatten(&join(&index.to_string()), value, values);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut values = Vec::new();
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(output) {
+        flatten("", &value, &mut values);
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(format: CustomFormat, output: &str) -> Vec<(String, f64)> {
+        parse_output(format, output)
+    }
+
+    fn pairs(values: &[(&str, f64)]) -> Vec<(String, f64)> {
+        values.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn plain_numbers() {
+        assert_eq!(
+            parsed(CustomFormat::Plain, "1.5 2\n3 abc"),
+            pairs(&[("0", 1.5), ("1", 2.0), ("2", 3.0)])
+        );
+    }
+
+    #[test]
+    fn key_value_pairs() {
+        assert_eq!(
+            parsed(CustomFormat::KeyValue, "a=1\nb: 2.5\nbad\nc = x\n"),
+            pairs(&[("a", 1.0), ("b", 2.5)])
+        );
+    }
+
+    #[test]
+    fn nested_json() {
+        assert_eq!(
+            parsed(
+                CustomFormat::Json,
+                r#"{"load": {"avg1": 0.5, "name": "x"}, "queue": [1, 2]}"#
+            ),
+            pairs(&[("load.avg1", 0.5), ("queue.0", 1.0), ("queue.1", 2.0)])
+        );
+    }
+
+    #[test]
+    fn auto_detection() {
+        assert_eq!(parsed(CustomFormat::Auto, "42\n"), pairs(&[("0", 42.0)]));
+        assert_eq!(parsed(CustomFormat::Auto, "x=4\n"), pairs(&[("x", 4.0)]));
+        assert_eq!(parsed(CustomFormat::Auto, "  [7]"), pairs(&[("0", 7.0)]));
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(parsed(CustomFormat::Json, "{").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_output() {
+        let source = CustomSource {
+            name: "test".into(),
+            input: CustomInput::Command(vec!["echo".into(), "a=1".into()]),
+            format: CustomFormat::Auto,
+            interval: Some(Duration::from_secs(60)),
+            timeout: Duration::from_secs(5),
+        };
+
+        let mut collector = CustomCollector::new(vec![source]);
+        let now = Instant::now();
+
+        // The command runs in the background, so there is nothing until it finishes.
+        assert!(collector.harvest(now).is_empty());
+        let harvest = wait_for_harvest(&mut collector, now);
+        assert_eq!(harvest.len(), 1);
+        assert_eq!(harvest[0].values, pairs(&[("a", 1.0)]));
+
+        // Not due yet, so the cached values are returned again.
+        let harvest = collector.harvest(now + Duration::from_secs(1));
+        assert_eq!(harvest[0].values, pairs(&[("a", 1.0)]));
+    }
+
+    /// Harvest until some source has finished running.
+    fn wait_for_harvest(collector: &mut CustomCollector, now: Instant) -> Vec<CustomHarvest> {
+        let start = Instant::now();
+        loop {
+            let harvest = collector.harvest(now);
+            if !harvest.is_empty() {
+                return harvest;
+            }
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn slow_command_does_not_block() {
+        let source = |name: &str, command: &[&str]| CustomSource {
+            name: name.into(),
+            input: CustomInput::Command(command.iter().map(|arg| arg.to_string()).collect()),
+            format: CustomFormat::Auto,
+            interval: None,
+            timeout: Duration::from_secs(5),
+        };
+        let mut collector = CustomCollector::new(vec![
+            source("slow", &["sleep", "3"]),
+            source("fast", &["echo", "1"]),
+        ]);
+
+        let start = Instant::now();
+        let harvest = wait_for_harvest(&mut collector, start);
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(harvest.len(), 1);
+        assert_eq!(harvest[0].name, "fast");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_timeout() {
+        let output = run_command(&["sleep".into(), "5".into()], Duration::from_millis(50));
+        assert!(output.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_timeout_with_children() {
+        // The shell's children keep stdout open, and must be stopped too.
+        for script in ["sleep 5; echo 1", "echo 1; sleep 5 &"] {
+            let start = Instant::now();
+            let output = run_command(
+                &["sh".into(), "-c".into(), script.into()],
+                Duration::from_millis(50),
+            );
+            assert!(output.is_none(), "{script}");
+            assert!(start.elapsed() < Duration::from_secs(2), "{script}");
+        }
+    }
+}
@@ -14,6 +14,9 @@ pub struct NetworkHarvest {
     pub tx_packets: u64,
     pub total_rx_packets: u64,
     pub total_tx_packets: u64,
+    /// Each kept interface, along with whether it is up. Interfaces with an
+    /// unknown state are left out.
+    pub interfaces: Vec<(String, bool)>,
 }
 
 impl NetworkHarvest {
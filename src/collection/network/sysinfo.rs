@@ -2,7 +2,7 @@
 
 use std::time::Instant;
 
-use sysinfo::Networks;
+use sysinfo::{InterfaceOperationalState, Networks};
 
 use super::NetworkHarvest;
 use crate::app::filter::Filter;
@@ -18,6 +18,7 @@ pub fn get_network_data(
     let mut total_tx: u64 = 0;
     let mut total_rx_packets: u64 = 0;
     let mut total_tx_packets: u64 = 0;
+    let mut interfaces = Vec::new();
 
     for (name, network) in networks {
         let to_keep = if let Some(filter) = filter {
@@ -31,6 +32,14 @@ pub fn get_network_data(
             total_tx += network.total_transmitted() * 8;
             total_rx_packets += network.total_packets_received();
             total_tx_packets += network.total_packets_transmitted();
+
+            match network.operational_state() {
+                InterfaceOperationalState::Up => interfaces.push((name.clone(), true)),
+                InterfaceOperationalState::Down
+                | InterfaceOperationalState::LowerLayerDown
+                | InterfaceOperationalState::NotPresent => interfaces.push((name.clone(), false)),
+                _ => {}
+            }
         }
     }
 
@@ -60,5 +69,6 @@ pub fn get_network_data(
         tx_packets,
         total_rx_packets,
         total_tx_packets,
+        interfaces,
     }
 }
@@ -112,6 +112,9 @@ pub struct ProcessHarvest {
     /// The exact command for the process.
     pub command: String,
 
+    /// The path of the process' executable, if it could be read.
+    pub exe: Option<String>,
+
     /// Bytes read per second.
     pub read_per_sec: Bytes,
 
@@ -163,6 +166,14 @@ pub struct ProcessHarvest {
 
     /// The kernel scheduling priority.
     pub priority: i32,
+
+    /// The CPUs the process is allowed to run on.
+    #[cfg(target_os = "linux")]
+    pub cpu_affinity: Option<Vec<usize>>,
+
+    /// The CPU the process last ran on.
+    #[cfg(target_os = "linux")]
+    pub last_cpu: Option<usize>,
     // TODO: Additional fields
     // pub rss_kb: u64,
     // pub virt_kb: u64,
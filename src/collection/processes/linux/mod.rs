@@ -15,7 +15,10 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use sysinfo::ProcessStatus;
 
 use super::{Pid, ProcessHarvest, UserTable, process_status_str};
-use crate::collection::{DataCollector, error::CollectionResult, processes::ProcessType};
+use crate::{
+    collection::{DataCollector, error::CollectionResult, processes::ProcessType},
+    utils::process_priority::get_affinity,
+};
 
 /// Maximum character length of a `/proc/<PID>/stat` process name (the length is
 /// 16, but this includes a null terminator).
@@ -143,6 +146,7 @@ fn read_proc(
         stat,
         io,
         cmdline,
+        exe,
     } = process;
 
     let ReadProcArgs {
@@ -153,6 +157,7 @@ fn read_proc(
         time_difference_in_secs,
         system_uptime,
         get_process_threads: _,
+        get_process_affinity,
     } = args;
 
     let process_state_char = stat.state;
@@ -273,6 +278,7 @@ fn read_proc(
             virtual_mem,
             name,
             command,
+            exe,
             read_per_sec,
             write_per_sec,
             total_read,
@@ -291,6 +297,13 @@ fn read_proc(
             #[cfg(unix)]
             nice: stat.nice,
             priority: stat.priority,
+            // This is a syscall per process, so skip it if it isn't shown.
+            cpu_affinity: if get_process_affinity {
+                get_affinity(process.pid)
+            } else {
+                None
+            },
+            last_cpu: stat.processor,
         },
         new_process_times,
     ))
@@ -333,6 +346,7 @@ pub(crate) struct ProcHarvestOptions {
     pub use_current_cpu_total: bool,
     pub unnormalized_cpu: bool,
     pub get_process_threads: bool,
+    pub get_process_affinity: bool,
 }
 
 fn is_str_numeric(s: &str) -> bool {
@@ -349,6 +363,7 @@ pub(crate) struct ReadProcArgs {
     pub time_difference_in_secs: u64,
     pub system_uptime: u64,
     pub get_process_threads: bool,
+    pub get_process_affinity: bool,
 }
 
 pub(crate) fn linux_process_data(
@@ -363,6 +378,7 @@ pub(crate) fn linux_process_data(
         use_current_cpu_total: collector.use_current_cpu_total,
         unnormalized_cpu: collector.unnormalized_cpu,
         get_process_threads: collector.get_process_threads,
+        get_process_affinity: collector.get_process_affinity,
     };
     let prev_process_details = &mut collector.prev_process_details;
     let user_table = &mut collector.user_table;
@@ -371,6 +387,7 @@ pub(crate) fn linux_process_data(
         use_current_cpu_total,
         unnormalized_cpu,
         get_process_threads: get_threads,
+        get_process_affinity,
     } = proc_harvest_options;
 
     let PrevProc {
@@ -416,6 +433,7 @@ pub(crate) fn linux_process_data(
         time_difference_in_secs,
         system_uptime: sysinfo::System::uptime(),
         get_process_threads: get_threads,
+        get_process_affinity,
     };
 
     // TODO: Maybe pre-allocate these buffers in the future w/ routine cleanup.
@@ -69,6 +69,9 @@ pub(crate) struct Stat {
     /// The nice value (user-settable scheduling hint).
     #[cfg(unix)]
     pub nice: i32,
+
+    /// The CPU the process last ran on. Only present since Linux 2.2.8.
+    pub processor: Option<usize>,
 }
 
 impl Stat {
@@ -132,6 +135,9 @@ impl Stat {
         let vsize: u64 = next_part(&mut rest)?.parse()?;
         let rss: u64 = next_part(&mut rest)?.parse()?;
 
+        // Skip 14 fields (rsslim through exit_signal) to get to processor.
+        let processor = rest.nth(14).and_then(|processor| processor.parse().ok());
+
         Ok(Stat {
             comm,
             state,
@@ -144,6 +150,7 @@ impl Stat {
             is_kernel_thread,
             priority,
             nice,
+            processor,
         })
     }
 
@@ -241,6 +248,7 @@ pub(crate) struct Process {
     pub stat: Stat,
     pub io: Option<Io>,
     pub cmdline: Option<String>,
+    pub exe: Option<String>,
 }
 
 #[inline]
@@ -319,6 +327,11 @@ impl Process {
 
         reset(&mut root, buffer);
 
+        // This can fail if permission is denied, or for kernel threads.
+        let exe = rustix::fs::readlinkat(&pid_dir, "exe", Vec::new())
+            .ok()
+            .map(|path| path.to_string_lossy().into_owned());
+
         let threads = threads(&mut root, pid, get_threads);
 
         Ok((
@@ -328,6 +341,7 @@ impl Process {
                 stat,
                 io,
                 cmdline,
+                exe,
             },
             threads,
         ))
@@ -137,6 +137,9 @@ pub(crate) trait UnixProcessExt {
                 parent_pid: Self::parent_pid(process_val),
                 name,
                 command,
+                exe: process_val
+                    .exe()
+                    .map(|exe| exe.to_string_lossy().into_owned()),
                 mem_usage_percent: if total_memory > 0 {
                     (process_val.memory() as f64 * 100.0 / total_memory as f64) as f32
                 } else {
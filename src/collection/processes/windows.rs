@@ -129,6 +129,9 @@ pub fn sysinfo_process_data(
             parent_pid: process.parent().map(|p| p.as_u32() as _),
             name,
             command,
+            exe: process
+                .exe()
+                .map(|exe| exe.to_string_lossy().into_owned()),
             mem_usage_percent: if total_memory > 0 {
                 process.memory() as f64 * 100.0 / total_memory as f64
             } else {
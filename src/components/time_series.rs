@@ -9,10 +9,10 @@ use std::{
 
 pub use auto_y::*;
 pub use percent::*;
-use tui::{style::Style, symbols::Marker, widgets::BorderType};
+use tui::{layout::Rect, style::Style, symbols::Marker, widgets::BorderType};
 
 use crate::canvas::components::time_series::{
-    AxisBound, ChartScaling, GraphData, LegendConstraints, LegendPosition, TimeGraph,
+    AxisBound, ChartScaling, GraphData, LegendConstraints, LegendPosition, TimeGraph, pan_times,
 };
 
 const STALE_MIN_MILLISECONDS: u64 = Duration::from_secs(30).as_millis() as u64;
@@ -31,6 +31,17 @@ pub struct TimeseriesState {
     config: TimeseriesConfig,
     current_display_time: u64,
     autohide_timer: Option<Instant>,
+
+    /// The position of the inspect cursor, in milliseconds before the newest
+    /// data point, if the graph is being inspected.
+    inspect: Option<u64>,
+
+    /// The area the data was drawn in during the last draw.
+    graph_area: Option<Rect>,
+
+    /// How far the right edge of the graph is panned back from the newest
+    /// data point, in milliseconds.
+    pan: u64,
 }
 
 impl TimeseriesState {
@@ -40,6 +51,9 @@ impl TimeseriesState {
             current_display_time: config.default_time_value,
             config,
             autohide_timer,
+            inspect: None,
+            graph_area: None,
+            pan: 0,
         }
     }
 
@@ -77,6 +91,92 @@ impl TimeseriesState {
         self.maybe_start_autohide();
     }
 
+    /// Start or stop inspecting the graph. The cursor starts at the newest
+    /// data point.
+    pub fn toggle_inspect(&mut self) {
+        self.inspect = match self.inspect {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    /// Move the inspect cursor by `steps` columns of the graph, where positive
+    /// steps move back in time. Returns whether the cursor moved.
+    pub fn move_inspect_cursor(&mut self, steps: i64) -> bool {
+        let Some(offset) = self.inspect else {
+            return false;
+        };
+
+        // Move by one column's worth of time, so every step moves the cursor.
+        let step = match self.graph_area {
+            Some(area) if area.width > 1 => {
+                (self.current_display_time / u64::from(area.width - 1)).max(1)
+            }
+            _ => self.config.time_interval,
+        };
+        let distance = step.saturating_mul(steps.unsigned_abs());
+        let new_offset = if steps > 0 {
+            offset
+                .saturating_add(distance)
+                .min(self.current_display_time)
+        } else {
+            offset.saturating_sub(distance)
+        };
+
+        self.inspect = Some(new_offset);
+        new_offset != offset
+    }
+
+    /// Move the inspect cursor to the column at `x`, if (`x`, `y`) is in the
+    /// graph. Returns whether the cursor moved.
+    pub fn set_inspect_cursor_at(&mut self, x: u16, y: u16) -> bool {
+        let (Some(offset), Some(area)) = (self.inspect, self.graph_area) else {
+            return false;
+        };
+        if x < area.left() || x >= area.right() || y < area.top() || y >= area.bottom() {
+            return false;
+        }
+
+        let new_offset = if area.width > 1 {
+            let ratio = f64::from(area.right() - 1 - x) / f64::from(area.width - 1);
+            (ratio * self.current_display_time as f64).round() as u64
+        } else {
+            0
+        };
+
+        self.inspect = Some(new_offset);
+        new_offset != offset
+    }
+
+    /// How far back the graph shows, including any panning.
+    pub fn shown_span(&self) -> Duration {
+        Duration::from_millis(self.pan + self.current_display_time)
+    }
+
+    /// How far to pan with each step, which is a quarter of the displayed time.
+    fn pan_step(&self) -> u64 {
+        (self.current_display_time / 4).max(1)
+    }
+
+    /// Pan the graph back in time. `history` is how much time the retained
+    /// data spans, in milliseconds; the graph can't be panned past its start.
+    pub fn pan_back(&mut self, history: u64) {
+        let max_pan = history.saturating_sub(self.current_display_time);
+        if self.pan < max_pan {
+            self.pan = min(self.pan + self.pan_step(), max_pan);
+        }
+    }
+
+    /// Pan the graph forward in time, towards the newest data.
+    pub fn pan_forward(&mut self) {
+        self.pan = self.pan.saturating_sub(self.pan_step());
+    }
+
+    /// Pan the graph back to the newest data.
+    pub fn jump_to_live(&mut self) {
+        self.pan = 0;
+    }
+
     fn maybe_start_autohide(&mut self) {
         if self.config.autohide_time {
             self.autohide_timer = Some(Instant::now());
@@ -99,6 +199,10 @@ pub struct GraphDrawCtx<'a> {
     pub is_expanded: bool,
     pub legend_position: Option<LegendPosition>,
     pub legend_constraints: Option<LegendConstraints>,
+    pub legend_stats: bool,
+    /// How much time each point is an average over, if the data is downsampled.
+    pub resolution: Option<Duration>,
+    pub events: Vec<Instant>,
 }
 
 #[cfg(test)]
@@ -117,6 +221,9 @@ mod time_series_tests {
             config: cfg,
             current_display_time: display_time,
             autohide_timer: None,
+            inspect: None,
+            graph_area: None,
+            pan: 0,
         }
     }
 
@@ -167,6 +274,60 @@ mod time_series_tests {
         state.zoom_in();
         assert!(state.autohide_timer.is_some());
     }
+
+    #[test]
+    fn inspect_cursor() {
+        let mut state = state_at(60_000, TEST_CONFIG);
+        assert!(!state.move_inspect_cursor(1));
+
+        state.toggle_inspect();
+        assert_eq!(state.inspect, Some(0));
+
+        // Without a drawn graph, the cursor moves by the time interval.
+        assert!(state.move_inspect_cursor(1));
+        assert_eq!(state.inspect, Some(15_000));
+
+        // Once drawn, each step is one column, and the cursor stays in the graph.
+        state.graph_area = Some(Rect::new(10, 0, 61, 10));
+        assert!(state.move_inspect_cursor(-2));
+        assert_eq!(state.inspect, Some(13_000));
+        state.move_inspect_cursor(100);
+        assert_eq!(state.inspect, Some(60_000));
+        state.move_inspect_cursor(-100);
+        assert!(!state.move_inspect_cursor(-1));
+        assert_eq!(state.inspect, Some(0));
+
+        assert!(state.set_inspect_cursor_at(40, 5));
+        assert_eq!(state.inspect, Some(30_000));
+        assert!(!state.set_inspect_cursor_at(9, 5));
+        assert!(state.set_inspect_cursor_at(10, 5));
+        assert_eq!(state.inspect, Some(60_000));
+
+        state.toggle_inspect();
+        assert_eq!(state.inspect, None);
+    }
+
+    #[test]
+    fn pan() {
+        let mut state = state_at(60_000, TEST_CONFIG);
+        state.pan_forward();
+        assert_eq!(state.pan, 0);
+
+        // Without enough history, the graph can't be panned.
+        state.pan_back(50_000);
+        assert_eq!(state.pan, 0);
+
+        state.pan_back(100_000);
+        assert_eq!(state.pan, 15_000);
+        state.pan_back(100_000);
+        state.pan_back(100_000);
+        assert_eq!(state.pan, 40_000);
+
+        state.pan_forward();
+        assert_eq!(state.pan, 25_000);
+        state.jump_to_live();
+        assert_eq!(state.pan, 0);
+    }
 }
 
 #[cfg(test)]
@@ -8,6 +8,7 @@ use tui::{Frame, layout::Rect};
 
 use super::{
     AxisBound, ChartScaling, GraphData, GraphDrawCtx, TimeGraph, TimeseriesConfig, TimeseriesState,
+    pan_times,
 };
 
 struct GraphHeightCacheInner {
@@ -108,11 +109,16 @@ impl AutoYAxisTimeGraph {
         &mut self.state
     }
 
+    pub(crate) fn state(&self) -> &TimeseriesState {
+        &self.state
+    }
+
     pub(crate) fn y_max<'a, F, S>(&mut self, sources: S, times: &[Instant]) -> f64
     where
         F: Into<f64> + Clone + Copy + 'a,
         S: Iterator<Item = &'a ChunkedData<F>>,
     {
+        let times = pan_times(times, self.state.pan);
         if let Some(last_time) = times.last() {
             self.height_cache.get_or_update(
                 last_time,
@@ -126,10 +132,10 @@ impl AutoYAxisTimeGraph {
     }
 
     pub(crate) fn draw<F: Copy + Default + Into<f64>>(
-        &self, f: &mut Frame<'_>, draw_loc: Rect, ctx: GraphDrawCtx<'_>, y_bounds: AxisBound,
+        &mut self, f: &mut Frame<'_>, draw_loc: Rect, ctx: GraphDrawCtx<'_>, y_bounds: AxisBound,
         y_labels: &[Cow<'_, str>], scaling: ChartScaling, data: Vec<GraphData<'_, F>>,
     ) {
-        TimeGraph {
+        let graph_area = TimeGraph {
             x_min: -(self.state.current_display_time() as f64),
             hide_x_labels: ctx.hide_x_labels,
             y_bounds,
@@ -146,8 +152,15 @@ impl AutoYAxisTimeGraph {
             legend_constraints: ctx.legend_constraints,
             marker: ctx.marker,
             scaling,
+            inspect: self.state.inspect,
+            pan: self.state.pan,
+            legend_stats: ctx.legend_stats,
+            resolution: ctx.resolution,
+            events: ctx.events,
         }
-        .draw(f, draw_loc, data)
+        .draw(f, draw_loc, data);
+
+        self.state.graph_area = Some(graph_area);
     }
 }
 
@@ -23,14 +23,18 @@ impl PercentTimeGraph {
         &mut self.state
     }
 
+    pub(crate) fn state(&self) -> &TimeseriesState {
+        &self.state
+    }
+
     pub(crate) fn draw<F: Copy + Default + Into<f64>>(
-        &self, f: &mut Frame<'_>, draw_loc: Rect, ctx: GraphDrawCtx<'_>,
+        &mut self, f: &mut Frame<'_>, draw_loc: Rect, ctx: GraphDrawCtx<'_>,
         data: Vec<GraphData<'_, F>>,
     ) {
         const Y_BOUNDS: AxisBound = AxisBound::Max(100.5);
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
-        TimeGraph {
+        let graph_area = TimeGraph {
             x_min: -(self.state.current_display_time() as f64),
             hide_x_labels: ctx.hide_x_labels,
             y_bounds: Y_BOUNDS,
@@ -47,7 +51,14 @@ impl PercentTimeGraph {
             legend_constraints: ctx.legend_constraints,
             marker: ctx.marker,
             scaling: ChartScaling::Linear,
+            inspect: self.state.inspect,
+            pan: self.state.pan,
+            legend_stats: ctx.legend_stats,
+            resolution: ctx.resolution,
+            events: ctx.events,
         }
-        .draw(f, draw_loc, data)
+        .draw(f, draw_loc, data);
+
+        self.state.graph_area = Some(graph_area);
     }
 }
@@ -2,9 +2,14 @@
 //!
 //! FIXME: Move these to where it makes more sense.
 
+use crate::app::keybindings::{Action, HelpLine};
+
 // Default widget ID
 pub const DEFAULT_WIDGET_ID: u64 = 56709;
 
+// Name of the layout defined by the top-level `[[row]]` section
+pub const DEFAULT_LAYOUT_NAME: &str = "default";
+
 // Limits for when we should stop showing table gaps/labels (anything less means
 // not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
@@ -25,63 +30,204 @@ const HELP_CONTENTS_TEXT: [&str; 12] = [
     "Press 'Ctrl-f' or '/' to search for a keyword in the help text.",
 ];
 
+/// A line of help text for the given actions, followed by keys that can't be rebound.
+macro_rules! help_line {
+    ([$($action:ident),*], [$($fixed:literal),*], $description:literal) => {
+        HelpLine {
+            actions: &[$(Action::$action),*],
+            fixed: &[$($fixed),*],
+            description: $description,
+        }
+    };
+    ([$($action:ident),*], $description:literal) => {
+        help_line!([$($action),*], [], $description)
+    };
+}
+
+/// A section of the help menu.
+pub(crate) enum HelpSection {
+    /// Fixed text, where the first line is the header.
+    Text(&'static [&'static str]),
+
+    /// A header followed by lines whose keys come from the active keybindings.
+    Bindings(&'static str, &'static [HelpLine]),
+}
+
 // TODO [Help]: Move to using tables for easier formatting?
-pub(crate) const GENERAL_HELP_TEXT: [&str; 24] = [
+const GENERAL_HELP_TEXT: HelpSection = HelpSection::Bindings(
     "1 - General",
-    "q, Ctrl-c               Quit",
-    "Esc                     Close dialog windows, search, widgets, or exit expanded mode",
-    "Ctrl-r                  Reset display and any collected data",
-    "f                       Freeze/unfreeze updating with new data",
-    "Shift/Ctrl-Left, H, A   Move widget selection left",
-    "Shift/Ctrl-Right, L, D  Move widget selection right",
-    "Shift/Ctrl-Up, K, W     Move widget selection up",
-    "Shift/Ctrl-Down, J, S   Move widget selection down",
-    "Left, h                 Move left within widget",
-    "Down, j                 Move down within widget",
-    "Up, k                   Move up within widget",
-    "Right, l                Move right within widget",
-    "?                       Open help menu",
-    "gg                      Jump to the first entry",
-    "G                       Jump to the last entry",
-    "e                       Toggle expanding the currently selected widget",
-    "+                       Zoom in on chart (decrease time range)",
-    "-                       Zoom out on chart (increase time range)",
-    "=                       Reset zoom",
-    "PgUp, PgDown            Scroll up/down a table by a page",
-    "Ctrl-u, Ctrl-d          Scroll up/down a table by half a page",
-    "Mouse scroll            Scroll through the tables or zoom in/out of charts by scrolling up/down",
-    "Mouse click             Selects the clicked widget, table entry, dialog option, or tab",
-];
-
-const CPU_HELP_TEXT: [&str; 2] = [
+    &[
+        help_line!([Quit], ["Ctrl-c"], "Quit"),
+        help_line!(
+            [],
+            ["Esc"],
+            "Close dialog windows, search, widgets, or exit expanded mode"
+        ),
+        help_line!([Reset], "Reset display and any collected data"),
+        help_line!([Freeze], "Freeze/unfreeze updating with new data"),
+        help_line!([SelectWidgetLeft], "Move widget selection left"),
+        help_line!([SelectWidgetRight], "Move widget selection right"),
+        help_line!([SelectWidgetUp], "Move widget selection up"),
+        help_line!([SelectWidgetDown], "Move widget selection down"),
+        help_line!([Left], "Move left within widget"),
+        help_line!([Down], "Move down within widget"),
+        help_line!([Up], "Move up within widget"),
+        help_line!([Right], "Move right within widget"),
+        help_line!([Help], "Open help menu"),
+        help_line!([SkipToFirst], "Jump to the first entry"),
+        help_line!([SkipToLast], "Jump to the last entry"),
+        help_line!([Expand], "Toggle expanding the currently selected widget"),
+        help_line!([ZoomIn], "Zoom in on chart (decrease time range)"),
+        help_line!([ZoomOut], "Zoom out on chart (increase time range)"),
+        help_line!([ResetZoom], "Reset zoom"),
+        help_line!(
+            [ToggleInspect],
+            "Inspect a graph's values with a cursor, moved with left/right"
+        ),
+        help_line!([PanBack, PanForward], "Pan a graph back/forward in time"),
+        help_line!([JumpToLive], "Pan a graph back to the newest data"),
+        help_line!(
+            [NextLayout, PreviousLayout],
+            "Switch to the next/previous layout"
+        ),
+        help_line!(
+            [
+                Layout1, Layout2, Layout3, Layout4, Layout5, Layout6, Layout7, Layout8, Layout9
+            ],
+            "Switch to the nth layout"
+        ),
+        help_line!([EditLayout], "Open the layout editor"),
+        help_line!([CycleTheme], "Switch to the next theme"),
+        help_line!([ShowEvents], "Show the list of recorded events"),
+        help_line!([PageUp, PageDown], "Scroll up/down a table by a page"),
+        help_line!(
+            [HalfPageUp, HalfPageDown],
+            "Scroll up/down a table by half a page"
+        ),
+        help_line!(
+            [],
+            ["Mouse scroll"],
+            "Scroll through the tables or zoom in/out of charts by scrolling up/down"
+        ),
+        help_line!(
+            [],
+            ["Mouse click"],
+            "Selects the clicked widget, table entry, dialog option, or tab"
+        ),
+    ],
+);
+
+const CPU_HELP_TEXT: HelpSection = HelpSection::Text(&[
     "2 - CPU widget",
     "Mouse scroll            Scrolling over a CPU core/average shows only that entry on the chart",
-];
+    "Arrow keys              Select a core in the CPU heatmap and show its usage in the title",
+    "Mouse hover             Hovering over a core in the CPU heatmap shows its usage in the title",
+]);
 
-const PROCESS_HELP_TEXT: [&str; 20] = [
+const PROCESS_HELP_TEXT: HelpSection = HelpSection::Bindings(
     "3 - Process widget",
-    "dd, F9, Delete          Kill the selected process",
-    "c                       Sort by CPU usage, press again to reverse",
-    "m                       Sort by memory usage, press again to reverse",
-    "p                       Sort by PID name, press again to reverse",
-    "n                       Sort by process name, press again to reverse",
-    "Tab                     Group/un-group processes with the same name",
-    "Ctrl-f, /               Open process search widget",
-    "P                       Toggle between showing the full command or just the process name",
-    "s, F6                   Open process sort widget",
-    "I                       Invert current sort",
-    "%                       Toggle between values and percentages for memory usage",
-    "t, F5                   Toggle tree mode",
-    "Right                   Collapse a branch while in tree mode",
-    "Left                    Expand a branch while in tree mode",
-    "+, -, click, Space      Toggle whether a branch is expanded or collapsed in tree mode",
-    "click on header         Sorts the entries by that column, click again to invert the sort",
-    "C                       Sort by GPU usage, press again to reverse",
-    "M                       Sort by GPU memory usage, press again to reverse",
-    "z                       Toggle the display of kernel threads",
-];
-
-const SEARCH_HELP_TEXT: [&str; 53] = [
+    &[
+        help_line!([KillProcess], "Kill the selected process"),
+        help_line!(
+            [KillTree],
+            "Kill the selected process and all its descendants"
+        ),
+        help_line!(
+            [ChangePriority],
+            "Change the priority of the selected process"
+        ),
+        help_line!(
+            [ChangeAffinity],
+            "Change the CPU affinity of the selected process"
+        ),
+        help_line!([SortByCpu], "Sort by CPU usage, press again to reverse"),
+        help_line!(
+            [SortByMemory],
+            "Sort by memory usage, press again to reverse"
+        ),
+        help_line!([SortByPid], "Sort by PID name, press again to reverse"),
+        help_line!([SortByName], "Sort by process name, press again to reverse"),
+        help_line!(
+            [ToggleGrouping],
+            "Group/un-group processes with the same name"
+        ),
+        help_line!(
+            [CycleGroupKey],
+            "Group by name, command, user, parent, or executable"
+        ),
+        help_line!([Search], "Open process search widget"),
+        help_line!(
+            [ToggleCommand],
+            "Toggle between showing the full command or just the process name"
+        ),
+        help_line!([ToggleSortMenu], "Open process sort widget"),
+        help_line!(
+            [AddSortKey],
+            "In the sort widget, also sort by the selected column"
+        ),
+        help_line!([InvertSort], "Invert current sort"),
+        help_line!(
+            [TogglePercentages],
+            "Toggle between values and percentages for memory usage"
+        ),
+        help_line!([ToggleTreeMode], "Toggle tree mode"),
+        help_line!([Right], "Collapse a branch while in tree mode"),
+        help_line!([Left], "Expand a branch while in tree mode"),
+        help_line!(
+            [ZoomIn, ZoomOut, ToggleBranch],
+            ["click"],
+            "Toggle whether a branch or group is expanded or collapsed"
+        ),
+        help_line!(
+            [],
+            ["click on header"],
+            "Sorts the entries by that column, click again to invert the sort"
+        ),
+        help_line!(
+            [],
+            ["shift-click on header"],
+            "Also sorts the entries by that column"
+        ),
+        help_line!([SortByGpu], "Sort by GPU usage, press again to reverse"),
+        help_line!(
+            [SortByGpuMemory],
+            "Sort by GPU memory usage, press again to reverse"
+        ),
+        help_line!(
+            [ToggleKernelThreads],
+            "Toggle the display of kernel threads"
+        ),
+        help_line!([ToggleExited], "Toggle showing recently exited processes"),
+        help_line!(
+            [ToggleFollow],
+            "Follow the selected process as it moves, or stop following"
+        ),
+        help_line!(
+            [ToggleTreeFilter],
+            "Only show the selected process and its descendants, or show all"
+        ),
+        help_line!(
+            [ChooseColumns],
+            "Choose, reorder, and save the shown columns"
+        ),
+        help_line!(
+            [ToggleHistory],
+            "Toggle the usage history of the selected and watched processes"
+        ),
+        help_line!([ToggleMark], "Mark or unmark the selected process"),
+        help_line!(
+            [MarkRange],
+            "Mark every process from the last marked one to the selected one"
+        ),
+        help_line!(
+            [MarkAll],
+            "Mark every process matching the search, or unmark them if all are"
+        ),
+        help_line!([ClearMarks], "Unmark every process"),
+    ],
+);
+
+const SEARCH_HELP_TEXT: HelpSection = HelpSection::Text(&[
     "4 - Process search widget",
     "Esc                     Close the search widget (retains the filter)",
     "Ctrl-a                  Skip to the start of the search query",
@@ -135,61 +281,116 @@ const SEARCH_HELP_TEXT: [&str; 53] = [
     "MiB                     ex: read > 1 mib",
     "GiB                     ex: read > 1 gib",
     "TiB                     ex: read > 1 tib",
-];
+]);
 
-const SORT_HELP_TEXT: [&str; 6] = [
+const SORT_HELP_TEXT: HelpSection = HelpSection::Bindings(
     "5 - Sort widget",
-    "Down, 'j'               Scroll down in list",
-    "Up, 'k'                 Scroll up in list",
-    "Mouse scroll            Scroll through sort widget",
-    "Esc                     Close the sort widget",
-    "Enter                   Sort by current selected column",
-];
-
-const TEMP_HELP_WIDGET: [&str; 3] = [
+    &[
+        help_line!([Down], "Scroll down in list"),
+        help_line!([Up], "Scroll up in list"),
+        help_line!([], ["Mouse scroll"], "Scroll through sort widget"),
+        help_line!([], ["Esc"], "Close the sort widget"),
+        help_line!([], ["Enter"], "Sort by current selected column"),
+    ],
+);
+
+const TEMP_HELP_WIDGET: HelpSection = HelpSection::Bindings(
     "6 - Temperature widget",
-    "'s'                     Sort by sensor name, press again to reverse",
-    "'t'                     Sort by temperature, press again to reverse",
-];
-
-const DISK_HELP_WIDGET: [&str; 9] = [
+    &[
+        help_line!(
+            [TempSortBySensor],
+            "Sort by sensor name, press again to reverse"
+        ),
+        help_line!(
+            [TempSortByValue],
+            "Sort by temperature, press again to reverse"
+        ),
+        help_line!([Left], "Move the header cursor left"),
+        help_line!([Right], "Move the header cursor right"),
+        help_line!(
+            [TempAddSortKey],
+            "Also sort by the column under the header cursor"
+        ),
+    ],
+);
+
+const DISK_HELP_WIDGET: HelpSection = HelpSection::Bindings(
     "7 - Disk widget",
-    "'d'                     Sort by disk name, press again to reverse",
-    "'m'                     Sort by disk mount, press again to reverse",
-    "'u'                     Sort by disk usage, press again to reverse",
-    "'n'                     Sort by disk free space, press again to reverse",
-    "'t'                     Sort by total disk space, press again to reverse",
-    "'p'                     Sort by disk usage percentage, press again to reverse",
-    "'r'                     Sort by disk read activity, press again to reverse",
-    "'w'                     Sort by disk write activity, press again to reverse",
-];
-
-const BATTERY_HELP_TEXT: [&str; 3] = [
+    &[
+        help_line!(
+            [DiskSortByName],
+            "Sort by disk name, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByMount],
+            "Sort by disk mount, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByUsed],
+            "Sort by disk usage, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByFree],
+            "Sort by disk free space, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByTotal],
+            "Sort by total disk space, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByUsedPercent],
+            "Sort by disk usage percentage, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByRead],
+            "Sort by disk read activity, press again to reverse"
+        ),
+        help_line!(
+            [DiskSortByWrite],
+            "Sort by disk write activity, press again to reverse"
+        ),
+        help_line!([Left], "Move the header cursor left"),
+        help_line!([Right], "Move the header cursor right"),
+        help_line!(
+            [DiskAddSortKey],
+            "Also sort by the column under the header cursor"
+        ),
+    ],
+);
+
+const BATTERY_HELP_TEXT: HelpSection = HelpSection::Bindings(
     "8 - Battery widget",
-    "Left                    Go to previous battery",
-    "Right                   Go to next battery",
-];
+    &[
+        help_line!([Left], "Go to previous battery"),
+        help_line!([Right], "Go to next battery"),
+    ],
+);
 
-const BASIC_MEM_HELP_TEXT: [&str; 2] = [
+const BASIC_MEM_HELP_TEXT: HelpSection = HelpSection::Bindings(
     "9 - Basic memory widget",
-    "%                       Toggle between values and percentages for memory usage",
-];
+    &[help_line!(
+        [TogglePercentages],
+        "Toggle between values and percentages for memory usage"
+    )],
+);
 
 /// The number of help sections.
 const HELP_SECTIONS: usize = 10;
 
 // TODO: Add temp graph help section.
-pub(crate) const HELP_TEXT: [&[&str]; HELP_SECTIONS] = [
-    &HELP_CONTENTS_TEXT,
-    &GENERAL_HELP_TEXT,
-    &CPU_HELP_TEXT,
-    &PROCESS_HELP_TEXT,
-    &SEARCH_HELP_TEXT,
-    &SORT_HELP_TEXT,
-    &TEMP_HELP_WIDGET,
-    &DISK_HELP_WIDGET,
-    &BATTERY_HELP_TEXT,
-    &BASIC_MEM_HELP_TEXT,
+/// The help menu, whose keys are filled in from the keybindings with
+/// [`Keybindings::help_text`](crate::app::keybindings::Keybindings::help_text).
+pub(crate) const HELP_TEXT: [HelpSection; HELP_SECTIONS] = [
+    HelpSection::Text(&HELP_CONTENTS_TEXT),
+    GENERAL_HELP_TEXT,
+    CPU_HELP_TEXT,
+    PROCESS_HELP_TEXT,
+    SEARCH_HELP_TEXT,
+    SORT_HELP_TEXT,
+    TEMP_HELP_WIDGET,
+    DISK_HELP_WIDGET,
+    BATTERY_HELP_TEXT,
+    BASIC_MEM_HELP_TEXT,
 ];
 
 pub(crate) const DEFAULT_LAYOUT: &str = r#"
@@ -396,6 +597,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # How much data is stored at once in terms of time.
 #retention = "10m"
 
+# Saves graph history to this file, and loads it on startup.
+#history_file = "/path/to/history.json"
+
 # Deprecated - use memory.legend_position.
 # Where to place the legend for the memory widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #memory_legend = "top-right"
@@ -408,11 +612,12 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Processes widget configuration
 #[processes]
 # The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built):
-# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%, Nice, Priority
+# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%, Nice, Priority,
+# Affinity, Last CPU
 #columns = ["PID", "Name", "CPU%", "Mem%", "Virt", "R/s", "W/s", "T.Read", "T.Write", "User", "State", "GMem%", "GPU%", "Priority"]
 
-# The default sort column when bottom starts. Accepts any of the column names above.
-# If unset, defaults to CPU%.
+# The default sort column when bottom starts. Accepts any of the column names above, or a
+# list of them to break ties, like ["User", "CPU%"]. If unset, defaults to CPU%.
 #default_sort = "CPU%"
 
 # Gather process child thread information
@@ -430,12 +635,18 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Disable the advanced kill dialog and just show the basic one with no options. Only available on Linux, macOS, and FreeBSD.
 #disable_advanced_kill = false
 
+# How long a kill with escalation waits before sending SIGKILL to processes that have not exited. Not available on Windows.
+#kill_escalation = "5s"
+
 # Defaults to showing process memory usage by value.
 #default_memory_value = false
 
 # Groups processes with the same name by default. No effect if tree is set.
 #default_grouped = false
 
+# What processes are grouped by when grouped. Options: "name" (default), "command", "user", "parent", "exe".
+#group_by = "name"
+
 # Enables regex by default while searching.
 #regex = false
 
@@ -454,6 +665,10 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Show process CPU% usage without averaging over the number of CPU cores.
 #unnormalized_cpu = false
 
+# Processes to always show the usage history of, by name or PID. Press h in the process widget to toggle the history.
+# Watched processes starting or exiting are also recorded as events.
+#watch_list = ["firefox", 1234]
+
 
 # CPU widget configuration
 #[cpu]
@@ -463,6 +678,12 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Whether to show a decimal place for CPU usage values.
 #show_decimal = false
 
+# How to group cores in the CPU heatmap widget. One of "none" (default), "socket", "numa"
+#heatmap_grouping = "socket"
+
+# Whether to show the min, average, max and 95th percentile of the selected entry in the graph.
+#legend_stats = false
+
 
 # Disk widget configuration
 #[disk]
@@ -473,6 +694,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # The default sort type. Can be one of the following:
 # Disk, Mount, Used, Free, Total, Used%, Free%, R/s, W/s
+# A list of them can also be given to break ties, like ["Mount", "Disk"].
 #
 # Defaults to "Disk".
 #default_sort = "Disk"
@@ -518,6 +740,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # The default sort type. Can be one of the following:
 # Temp, Temperature, Sensor
+# A list of them can also be given to break ties, like ["Temp", "Sensor"].
 #
 # Defaults to "Sensor".
 #default_sort = "Sensor"
@@ -546,6 +769,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Where to place the legend for the temperature graph widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #legend_position = "top-right"
 
+# Whether to show the min, average, max and 95th percentile of each sensor in the legend.
+#legend_stats = false
+
 # An upper temperature value for the graph; entries higher than this will be hidden. If not set,
 # there is no limit. Is in the configured temperature unit.
 #max_temp = 100.0
@@ -570,6 +796,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # Network widget configuration
 #[network_graph]
+# Whether to show the min, average, max and 95th percentile of RX and TX in the legend.
+#legend_stats = false
+
 # By default, there are no network interface filters enabled. An example use case is provided below.
 #[network_graph.interface_filter]
 # Whether to ignore any matches. Defaults to true.
@@ -588,6 +817,37 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #whole_word = false
 
 
+# Custom widget sources. Each source is shown by a "custom" widget in the layout, picked by its name.
+#[[custom]]
+#name = "load"
+# A command to run as a program followed by its arguments, which is not run through a shell. Use "file" to read a file instead.
+#command = ["cat", "/proc/loadavg"]
+# How often to run the command. Defaults to every update.
+#interval = "5s"
+# How long to wait for the command before killing it. Defaults to 1 second.
+#timeout = "1s"
+# One of "auto" (default), "plain", "key_value", or "json".
+#format = "plain"
+
+# The series to show from the source. If not set, all values are shown.
+#[[custom.series]]
+# For "plain", this is the index of the value. For "json", nested values are joined with dots (e.g. "load.avg1").
+#key = "0"
+#name = "1 min"
+#unit = ""
+#colour = "light blue"
+
+
+# Keybindings, mapping actions to one or more keys. Keys can have "ctrl-", "alt-", or "shift-" modifiers,
+# and two keys separated by a space make a chord (e.g. "g g"). Unlisted actions keep their default keys,
+# and an empty list unbinds an action. See the documentation for the list of actions.
+#[keybindings]
+#quit = ["q", "ctrl-q"]
+#freeze = "f"
+#skip_to_first = ["g g", "home"]
+#kill_process = ["d d", "f9"]
+
+
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
 #[styles] # Uncomment if you want to use custom styling
@@ -635,6 +895,11 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #[styles.tables]
 #headers = {colour = "light blue", bold = true}
 
+#[[styles.tables.value_colours]]
+#widget = "proc"
+#column = "CPU%"
+#thresholds = [{value = 0, colour = "green"}, {value = 50, colour = "yellow"}, {value = 80, colour = "red"}]
+
 #[styles.graphs]
 #graph_colour = "gray"
 #legend_text = {colour = "gray"}
@@ -646,6 +911,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #text = {colour = "gray"}
 #selected_text = {colour = "black", bg_colour = "light blue"}
 #disabled_text = {colour = "dark gray"}
+#marked_text = {colour = "yellow"}
 # Disabled by default
 #bg_colour = "black"
 # Only on Linux
@@ -656,7 +922,8 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # [[row.child]] represents either a widget or a column.
 # [[row.child.child]] represents a widget.
 #
-# All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "temp_graph", "disk", "empty"].
+# All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "temp_graph", "disk", "custom", "empty"].
+# Custom widgets can also set a name value to pick which custom source to show.
 # All layout components have a ratio value - if this is not set, then it defaults to 1.
 # The default widget layout:
 #[[row]]
@@ -681,11 +948,19 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #  [[row.child]]
 #    type="proc"
 #    default=true
+#
+# More layouts can be added with [[layout.<name>.row]], and switched between at runtime with
+# '[' and ']' or the number keys. The [[row]] layout above is named "default" and comes first.
+#[[layout.processes.row]]
+#  [[layout.processes.row.child]]
+#    type="proc"
+#    default=true
 "#;
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::app::keybindings::Keybindings;
 
     #[test]
     fn help_menu_matches_entry_len() {
@@ -694,14 +969,14 @@ mod test {
 
         assert_eq!(
             HELP_CONTENTS_TEXT_LEN,
-            HELP_TEXT.len(),
+            Keybindings::default().help_text().len(),
             "the two should be equal, or this test should be updated"
         )
     }
 
     #[test]
     fn help_menu_text_has_sections() {
-        for (itx, line) in HELP_TEXT.iter().enumerate() {
+        for (itx, line) in Keybindings::default().help_text().iter().enumerate() {
             if itx > 0 {
                 assert!(line.len() >= 2, "each section should be at least 2 lines");
                 assert!(line[0].contains(" - "), "each section should have a header");
@@ -2,11 +2,15 @@
 
 use std::sync::mpsc::Sender;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 use crate::{
-    app::{App, layout_manager::WidgetDirection},
-    collection::Data,
+    app::{
+        App, DataFilters,
+        keybindings::{Action, KeyLookup},
+        layout_manager::{UsedWidgets, WidgetDirection},
+    },
+    collection::{Data, custom::CustomSource},
 };
 
 /// Events sent to the main thread.
@@ -18,17 +22,69 @@ pub enum BottomEvent {
     PasteEvent(String),
     Update(Box<Data>),
     Clean,
+    ConfigChange,
     Terminate,
 }
 
+/// Settings used by the collection thread, which are taken from the [`App`].
+#[derive(Debug)]
+pub struct CollectionSettings {
+    pub filters: DataFilters,
+    pub used_widgets: UsedWidgets,
+    pub use_current_cpu_total: bool,
+    pub unnormalized_cpu: bool,
+    pub show_average_cpu: bool,
+    pub update_rate: u64,
+    pub get_process_threads: bool,
+    pub get_process_affinity: bool,
+    pub custom_sources: Vec<CustomSource>,
+    #[cfg(feature = "zfs")]
+    pub free_arc: bool,
+}
+
+impl CollectionSettings {
+    pub fn new(app: &App) -> Self {
+        let app_config_fields = &app.app_config_fields;
+
+        Self {
+            filters: app.filters.clone(),
+            used_widgets: app.used_widgets,
+            use_current_cpu_total: app_config_fields.use_current_cpu_total,
+            unnormalized_cpu: app_config_fields.unnormalized_cpu,
+            show_average_cpu: app_config_fields.show_average_cpu,
+            update_rate: app_config_fields.update_rate,
+            get_process_threads: app_config_fields.get_process_threads,
+            get_process_affinity: app.shows_process_affinity(),
+            custom_sources: app_config_fields.custom_sources.clone(),
+            #[cfg(feature = "zfs")]
+            free_arc: app_config_fields.free_arc,
+        }
+    }
+}
+
 /// Events sent to the collection thread.
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+    UpdateSettings(Box<CollectionSettings>),
 }
 
 /// Handle a [`MouseEvent`].
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
+    if app.layout_editor.is_open() {
+        if !app.app_config_fields.disable_click {
+            let (x, y) = (event.column, event.row);
+            match event.kind {
+                MouseEventKind::Down(MouseButton::Left) => app.layout_editor.on_mouse_down(x, y),
+                MouseEventKind::Drag(MouseButton::Left) => app.layout_editor.on_mouse_drag(x, y),
+                MouseEventKind::Up(MouseButton::Left) => app.layout_editor.on_mouse_up(),
+                _ => return,
+            }
+            app.is_force_redraw = true;
+        }
+        return;
+    }
+
     match event.kind {
         MouseEventKind::ScrollUp => app.handle_scroll_up(),
         MouseEventKind::ScrollDown => app.handle_scroll_down(),
@@ -38,7 +94,8 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                 match button {
                     crossterm::event::MouseButton::Left => {
                         // Trigger left click widget activity
-                        app.on_left_mouse_up(x, y);
+                        let is_shift = event.modifiers.contains(KeyModifiers::SHIFT);
+                        app.on_left_mouse_up(x, y, is_shift);
                     }
                     crossterm::event::MouseButton::Right => {}
                     _ => {}
@@ -55,9 +112,71 @@ pub fn handle_key_event_or_break(
 ) -> bool {
     // c_debug!("KeyEvent: {event:?}");
 
+    // Ctrl-c always quits, regardless of keybindings.
+    if event.modifiers == KeyModifiers::CONTROL && event.code == KeyCode::Char('c') {
+        return true;
+    }
+
+    // The layout editor has its own fixed keys.
+    if app.layout_editor.is_open() {
+        app.on_layout_editor_key(event);
+        return false;
+    }
+
+    if app.event_list.is_open() {
+        app.on_event_list_key(event);
+        return false;
+    }
+
+    if app.process_priority_dialog.is_open() {
+        app.on_priority_dialog_key(event);
+        return false;
+    }
+
+    if app.process_affinity_dialog.is_open() {
+        app.on_affinity_dialog_key(event);
+        return false;
+    }
+
+    if app.process_columns_dialog.is_open() {
+        app.on_columns_dialog_key(event);
+        return false;
+    }
+
+    // Text input keys are fixed, so typing isn't interrupted by keybindings.
+    if app.is_in_any_search() {
+        handle_input_key_event(event, app);
+        return false;
+    }
+
+    match app.lookup_key(event) {
+        KeyLookup::Action(Action::Quit) => return true,
+        KeyLookup::Action(Action::Reset) => {
+            if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
+                app.reset();
+            }
+        }
+        KeyLookup::Action(action) => app.perform_action(action),
+        KeyLookup::Pending => {}
+        KeyLookup::Unbound if app.is_in_dialog() => handle_input_key_event(event, app),
+        KeyLookup::Unbound => {
+            if event.modifiers.is_empty() {
+                match event.code {
+                    KeyCode::Esc => app.on_esc(),
+                    KeyCode::Enter => app.on_enter(),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Handle a [`KeyEvent`] for text input or an open dialog, where keys are not configurable.
+fn handle_input_key_event(event: KeyEvent, app: &mut App) {
     if event.modifiers.is_empty() {
         match event.code {
-            KeyCode::Char('q') if !app.is_in_any_search() => return true,
             KeyCode::End => app.skip_to_last(),
             KeyCode::Home => app.skip_to_first(),
             KeyCode::Up => app.on_up_key(),
@@ -74,9 +193,6 @@ pub fn handle_key_event_or_break(
             KeyCode::F(1) => app.toggle_ignore_case(),
             KeyCode::F(2) => app.toggle_search_whole_word(),
             KeyCode::F(3) => app.toggle_search_regex(),
-            KeyCode::F(5) => app.toggle_tree_mode(),
-            KeyCode::F(6) => app.toggle_sort_menu(),
-            KeyCode::F(9) => app.kill_current_process(),
             KeyCode::PageDown => app.on_page_down(),
             KeyCode::PageUp => app.on_page_up(),
             _ => {}
@@ -93,19 +209,12 @@ pub fn handle_key_event_or_break(
                 _ => {}
             }
         } else if let KeyModifiers::CONTROL = event.modifiers {
-            if event.code == KeyCode::Char('c') {
-                return true;
-            }
-
             match event.code {
                 KeyCode::Char('f') => app.on_slash(),
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
-                KeyCode::Char('r') if reset_sender.send(CollectionThreadEvent::Reset).is_ok() => {
-                    app.reset();
-                }
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
                 KeyCode::Char('u') if app.is_in_any_search() => app.clear_search(),
@@ -113,10 +222,6 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('h') => app.on_backspace(),
                 KeyCode::Char('d') => app.scroll_half_page_down(),
                 KeyCode::Char('u') => app.scroll_half_page_up(),
-                // KeyCode::Char('j') => {}, // Move down
-                // KeyCode::Char('k') => {}, // Move up
-                // KeyCode::Char('h') => {}, // Move right
-                // KeyCode::Char('l') => {}, // Move left
                 // Can't do now, CTRL+BACKSPACE doesn't work and graphemes
                 // are hard to iter while truncating last (eloquently).
                 // KeyCode::Backspace => app.skip_word_backspace(),
@@ -133,6 +238,4 @@ pub fn handle_key_event_or_break(
             }
         }
     }
-
-    false
 }
@@ -18,6 +18,7 @@ mod utils {
     pub(crate) mod int_hash;
     pub(crate) mod logging;
     pub(crate) mod process_killer;
+    pub(crate) mod process_priority;
     pub(crate) mod strings;
 }
 pub(crate) mod canvas;
@@ -30,7 +31,9 @@ pub mod widgets;
 use std::{
     boxed::Box,
     io::{Write, stderr, stdout},
+    mem,
     panic::{self, PanicHookInfo},
+    path::PathBuf,
     sync::{
         Arc,
         mpsc::{self, Receiver, Sender},
@@ -39,24 +42,32 @@ use std::{
     time::{Duration, Instant},
 };
 
-use app::{App, AppConfigFields, DataFilters, layout_manager::UsedWidgets};
+use anyhow::Context;
+use app::{
+    App, AppConfigFields, DataFilters,
+    data::{HISTORY_SAVE_INTERVAL, RAW_DATA_AGE},
+    monitor::Monitor,
+};
 use crossterm::{
     cursor::{Hide, Show},
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-        Event, KeyEventKind, MouseEventKind, poll, read,
+        Event, KeyEventKind, MouseButton, MouseEventKind, poll, read,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use event::{BottomEvent, CollectionThreadEvent, handle_key_event_or_break, handle_mouse_event};
-use options::{args, get_or_create_config, init_app};
+use event::{
+    BottomEvent, CollectionSettings, CollectionThreadEvent, handle_key_event_or_break,
+    handle_mouse_event,
+};
+use options::{args, config_modified_time, get_or_create_config, init_app, read_config};
 use tui::{Terminal, backend::CrosstermBackend};
 #[allow(unused_imports, reason = "this is needed if logging is enabled")]
 use utils::logging::*;
 use utils::{cancellation_token::CancellationToken, conversion::*};
 
-use crate::collection::Data;
+use crate::collection::{Data, processes::Pid};
 
 // Used for heap allocation debugging purposes.
 // #[global_allocator]
@@ -194,8 +205,12 @@ fn create_input_thread(
                                 }
                             }
                             Event::Mouse(mouse) => match mouse.kind {
-                                MouseEventKind::Moved | MouseEventKind::Drag(..) => {}
-                                MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                                // Left-button drags are used to resize in the layout editor.
+                                MouseEventKind::Drag(button) if button != MouseButton::Left => {}
+                                // Movement is used to hover over CPU heatmap cells.
+                                MouseEventKind::Moved
+                                | MouseEventKind::ScrollDown
+                                | MouseEventKind::ScrollUp => {
                                     if Instant::now().duration_since(mouse_timer).as_millis() >= 20
                                     {
                                         if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
@@ -221,30 +236,31 @@ fn create_input_thread(
     })
 }
 
+/// Apply [`CollectionSettings`] to a [`collection::DataCollector`].
+fn apply_collection_settings(
+    data_collector: &mut collection::DataCollector, settings: CollectionSettings,
+) {
+    data_collector.set_filters(settings.filters);
+    data_collector.set_collection(settings.used_widgets);
+    data_collector.set_use_current_cpu_total(settings.use_current_cpu_total);
+    data_collector.set_unnormalized_cpu(settings.unnormalized_cpu);
+    data_collector.set_show_average_cpu(settings.show_average_cpu);
+    data_collector.set_get_process_threads(settings.get_process_threads);
+    data_collector.set_get_process_affinity(settings.get_process_affinity);
+    data_collector.set_custom_sources(settings.custom_sources);
+    #[cfg(feature = "zfs")]
+    data_collector.set_free_arc_mem(settings.free_arc);
+}
+
 /// Create a thread to handle data collection.
 fn create_collection_thread(
     sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
-    cancellation_token: Arc<CancellationToken>, app_config_fields: &AppConfigFields,
-    filters: DataFilters, used_widget_set: UsedWidgets,
+    cancellation_token: Arc<CancellationToken>, settings: CollectionSettings,
 ) -> JoinHandle<()> {
-    let use_current_cpu_total = app_config_fields.use_current_cpu_total;
-    let unnormalized_cpu = app_config_fields.unnormalized_cpu;
-    let show_average_cpu = app_config_fields.show_average_cpu;
-    let update_sleep = app_config_fields.update_rate;
-    let get_process_threads = app_config_fields.get_process_threads;
-    #[cfg(feature = "zfs")]
-    let get_arc_free = app_config_fields.free_arc;
-
     thread::spawn(move || {
-        let mut data_collector = collection::DataCollector::new(filters);
-
-        data_collector.set_collection(used_widget_set);
-        data_collector.set_use_current_cpu_total(use_current_cpu_total);
-        data_collector.set_unnormalized_cpu(unnormalized_cpu);
-        data_collector.set_show_average_cpu(show_average_cpu);
-        data_collector.set_get_process_threads(get_process_threads);
-        #[cfg(feature = "zfs")]
-        data_collector.set_free_arc_mem(get_arc_free);
+        let mut update_sleep = settings.update_rate;
+        let mut data_collector = collection::DataCollector::new(DataFilters::default());
+        apply_collection_settings(&mut data_collector, settings);
 
         data_collector.update_data();
         data_collector.data = Data::default();
@@ -267,6 +283,10 @@ fn create_collection_thread(
                     CollectionThreadEvent::Reset => {
                         data_collector.data.cleanup();
                     }
+                    CollectionThreadEvent::UpdateSettings(settings) => {
+                        update_sleep = settings.update_rate;
+                        apply_collection_settings(&mut data_collector, *settings);
+                    }
                 }
             }
 
@@ -294,6 +314,44 @@ fn create_collection_thread(
     })
 }
 
+/// Create a thread that sends a [`BottomEvent::ConfigChange`] whenever the
+/// config file at `path` is modified.
+fn create_config_watcher_thread(
+    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>, path: PathBuf,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let modified_time = || config_modified_time(&path);
+        let mut last_modified = modified_time();
+
+        loop {
+            if cancellation_token.sleep_with_cancellation(Duration::from_secs(1)) {
+                break;
+            }
+
+            let modified = modified_time();
+            if modified != last_modified {
+                last_modified = modified;
+
+                if sender.send(BottomEvent::ConfigChange).is_err() {
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Re-read the config file at `path`, and create a new [`App`] and
+/// [`canvas::Painter`] from it.
+fn reload_config(
+    args: args::BottomArgs, path: &std::path::Path,
+) -> anyhow::Result<(App, canvas::Painter)> {
+    let config = read_config(path)?;
+    let (app, widget_layouts, styling) = init_app(args, config)?;
+    let painter = canvas::Painter::init(widget_layouts, styling)?;
+
+    Ok((app, painter))
+}
+
 /// Main code to call to start bottom.
 #[inline]
 pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
@@ -315,10 +373,27 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     let config = get_or_create_config(args.general.config_location.as_deref())?;
 
     // Create the "app" and initialize a bunch of stuff.
-    let (mut app, widget_layout, styling) = init_app(args, config)?;
+    let (mut app, widget_layouts, styling) = init_app(args.clone(), config)?;
 
     // Create painter and set colours.
-    let mut painter = canvas::Painter::init(widget_layout, styling)?;
+    let mut painter = canvas::Painter::init(widget_layouts, styling)?;
+
+    // Load any saved history, so graphs don't start out empty.
+    if let Some(path) = app.app_config_fields.history_file.clone() {
+        if let Err(err) = app.data_store.load_history(&path, &app.app_config_fields) {
+            app.set_status_error(&err.context("History not loaded"));
+        }
+    }
+    let mut last_history_save = Instant::now();
+
+    // Launch or attach to any processes to monitor; this must happen before
+    // collection starts, as it turns on process collection.
+    if !args.process.command.is_empty() {
+        app.set_monitor(Monitor::launch(&args.process.command)?);
+    } else if !args.process.pid.is_empty() {
+        let pids = args.process.pid.iter().map(|&pid| pid as Pid).collect();
+        app.set_monitor(Monitor::attach(pids)?);
+    }
 
     // Check if the current environment is in a terminal.
     check_if_terminal();
@@ -333,9 +408,7 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
         sender.clone(),
         collection_thread_ctrl_receiver,
         cancellation_token.clone(),
-        &app.app_config_fields,
-        app.filters.clone(),
-        app.used_widgets,
+        CollectionSettings::new(&app),
     );
 
     // Set up the input handling loop thread.
@@ -349,7 +422,11 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     let _cleaning_thread = {
         let cancellation_token = cancellation_token.clone();
         let cleaning_sender = sender.clone();
-        let offset_wait = Duration::from_millis(app.app_config_fields.retention_ms + 60000);
+        // Raw data is kept for at most `RAW_DATA_AGE`, even with a longer retention period, so
+        // clean at least that often.
+        let offset_wait = Duration::from_millis(app.app_config_fields.retention_ms)
+            .min(RAW_DATA_AGE)
+            + Duration::from_secs(60);
         thread::spawn(move || {
             loop {
                 if cancellation_token.sleep_with_cancellation(offset_wait) {
@@ -363,6 +440,12 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
         })
     };
 
+    // Set up the config file watching thread.
+    let _config_watcher_thread =
+        app.app_config_fields.config_path.clone().map(|path| {
+            create_config_watcher_thread(sender.clone(), cancellation_token.clone(), path)
+        });
+
     // Set up tui and crossterm
     *enable_error_hook = true;
 
@@ -402,6 +485,7 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     })?;
 
     let mut first_run = true;
+    let mut get_process_affinity = app.shows_process_affinity();
 
     // Draw once first to initialize the canvas, so it doesn't feel like it's
     // frozen.
@@ -418,9 +502,24 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+
+                    // The affinity column can be shown or hidden with the column chooser.
+                    if app.shows_process_affinity() != get_process_affinity {
+                        get_process_affinity = !get_process_affinity;
+                        let settings = Box::new(CollectionSettings::new(&app));
+                        let _ = collection_thread_ctrl_sender
+                            .send(CollectionThreadEvent::UpdateSettings(settings));
+                    }
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
+                BottomEvent::MouseInput(event) if event.kind == MouseEventKind::Moved => {
+                    if !app.app_config_fields.disable_click
+                        && app.on_mouse_move(event.column, event.row)
+                    {
+                        try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    }
+                }
                 BottomEvent::MouseInput(event) => {
                     handle_mouse_event(event, &mut app);
                     app.update_data();
@@ -434,6 +533,19 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
                 BottomEvent::Update(data) => {
                     app.data_store.eat_data(data, &app.app_config_fields);
 
+                    if let Some(monitor) = &mut app.monitor {
+                        if monitor.update(app.data_store.live_process_data()) {
+                            break;
+                        }
+                    }
+
+                    if last_history_save.elapsed() >= HISTORY_SAVE_INTERVAL {
+                        last_history_save = Instant::now();
+                        if let Err(err) = save_history(&app) {
+                            app.set_status_error(&err);
+                        }
+                    }
+
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
                     if first_run {
@@ -441,6 +553,9 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
                         app.is_force_redraw = true;
                     }
 
+                    // Check on any kill in progress, even while frozen.
+                    let is_killing = app.process_kill_dialog.update_progress();
+
                     if !app.data_store.is_frozen() {
                         // Convert all data into data for the displayed widgets.
 
@@ -470,8 +585,47 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
 
                         app.update_data();
                         try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    } else if is_killing {
+                        try_drawing(&mut terminal, &mut app, &mut painter)?;
                     }
                 }
+                BottomEvent::ConfigChange => {
+                    let Some(path) = app.app_config_fields.config_path.clone() else {
+                        continue;
+                    };
+                    if app.is_config_saved_by_self(&path) {
+                        continue;
+                    }
+
+                    match reload_config(args.clone(), &path) {
+                        Ok((new_app, new_painter)) => {
+                            if new_app.app_config_fields.disable_click
+                                != app.app_config_fields.disable_click
+                            {
+                                if new_app.app_config_fields.disable_click {
+                                    execute!(terminal.backend_mut(), DisableMouseCapture)?;
+                                } else {
+                                    execute!(terminal.backend_mut(), EnableMouseCapture)?;
+                                }
+                            }
+
+                            let keep_styles = app.reload(new_app);
+                            let styles = mem::replace(&mut painter, new_painter).styles;
+                            if keep_styles {
+                                painter.styles = styles;
+                            }
+
+                            let settings = Box::new(CollectionSettings::new(&app));
+                            get_process_affinity = settings.get_process_affinity;
+                            let _ = collection_thread_ctrl_sender
+                                .send(CollectionThreadEvent::UpdateSettings(settings));
+                        }
+                        Err(err) => app.set_status_error(&err.context("Config not reloaded")),
+                    }
+
+                    app.update_data();
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
                 BottomEvent::Clean => {
                     app.data_store
                         .clean_data(Duration::from_millis(app.app_config_fields.retention_ms));
@@ -485,5 +639,23 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     cancellation_token.cancel();
     cleanup_terminal(&mut terminal)?;
 
-    Ok(())
+    if let Some(monitor) = &mut app.monitor {
+        monitor.stop();
+        println!("{}", monitor.summary());
+    }
+
+    save_history(&app)
+}
+
+/// Save the graph history if a history file is set. Nothing is saved in basic
+/// mode, as there is no history to save.
+fn save_history(app: &App) -> anyhow::Result<()> {
+    let settings = &app.app_config_fields;
+    match &settings.history_file {
+        Some(path) if !settings.use_basic_mode => app
+            .data_store
+            .save_history(path, settings)
+            .context("History not saved"),
+        _ => Ok(()),
+    }
 }
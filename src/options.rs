@@ -12,12 +12,12 @@ use std::{
     io::Write,
     path::{Path, PathBuf},
     str::FromStr,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::{Context, Result};
 pub use config::Config;
-use config::style::Styles;
+use config::style::{Styles, ThemeCycle, str_to_colour, theme_dirs};
 use data::TemperatureType;
 pub(crate) use error::{OptionError, OptionResult};
 use indexmap::IndexSet;
@@ -28,11 +28,27 @@ use starship_battery::Manager;
 
 use self::{
     args::BottomArgs,
-    config::{IgnoreList, StringOrNum, layout::Row},
+    config::{
+        IgnoreList, OneOrMany, StringOrNum,
+        custom::CustomConfig,
+        layout::{LayoutSection, Row},
+    },
 };
+use tui::style::Style;
+
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
+    app::{
+        data::WatchedProcess,
+        filter::Filter,
+        keybindings::{Action, KeySequence, Keybindings},
+        layout_manager::*,
+        *,
+    },
     canvas::components::time_series::LegendPosition,
+    collection::{
+        custom::{CustomFormat, CustomInput, CustomSource},
+        processes::Pid,
+    },
     components::time_series::TimeseriesConfig,
     constants::*,
     utils::data_units::DataUnit,
@@ -192,6 +208,25 @@ fn create_config_at_path(path: &Path) -> anyhow::Result<Config> {
     Ok(Config::default())
 }
 
+/// When the config file at `path` was last modified, if it can be read.
+pub(crate) fn config_modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Read and parse the config file at `path`.
+pub(crate) fn read_config(path: &Path) -> anyhow::Result<Config> {
+    let config_string = fs::read_to_string(path).with_context(|| {
+        format!(
+            "bottom could not read the config file at '{}'.",
+            path.display()
+        )
+    })?;
+
+    Ok(toml_edit::de::from_str(&config_string)?)
+}
+
 /// Get the config at `config_path`. If there is no config file at the specified
 /// path, it will try to create a new file with the default settings, and return
 /// the default config.
@@ -255,7 +290,9 @@ pub(crate) fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result
 }
 
 /// Initialize the app.
-pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomLayout, Styles)> {
+pub(crate) fn init_app(
+    args: BottomArgs, config: Config,
+) -> Result<(App, Vec<BottomLayout>, Styles)> {
     use BottomWidgetType::*;
 
     // Since everything takes a reference, but we want to take ownership here to
@@ -263,11 +300,17 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let args = &args;
     let config = &config;
 
-    let styling = Styles::new(args, config)?;
+    let config_path = get_config_path(args.general.config_location.as_deref());
+    let theme_dirs = theme_dirs(config_path.as_deref());
+    let styling = Styles::new(args, config, &theme_dirs)?;
+    let themes = ThemeCycle::new(
+        Styles::theme_name(args, config).unwrap_or("default"),
+        theme_dirs,
+        config.styles.clone(),
+    );
 
-    let (widget_layout, default_widget_id, default_widget_type_option) =
-        get_widget_layout(args, config)
-            .context("Found an issue while trying to build the widget layout.")?;
+    let (widget_layouts, default_widget_type_option) = get_widget_layout(args, config)
+        .context("Found an issue while trying to build the widget layout.")?;
 
     let retention_ms = get_retention(args, config)?;
     let autohide_time = is_flag_enabled!(autohide_time, args.general, config);
@@ -351,6 +394,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let mut widget_map = FxHashMap::default();
     let mut cpu_state_map: FxHashMap<u64, CpuWidgetState> = FxHashMap::default();
+    let mut cpu_heatmap_state_map: FxHashMap<u64, CpuHeatmapWidgetState> = FxHashMap::default();
     let mut mem_state_map: FxHashMap<u64, MemWidgetState> = FxHashMap::default();
     let mut net_state_map: FxHashMap<u64, NetWidgetState> = FxHashMap::default();
     let mut proc_state_map: FxHashMap<u64, ProcWidgetState> = FxHashMap::default();
@@ -358,6 +402,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let mut temp_graph_state_map: FxHashMap<u64, TempGraphWidgetState> = FxHashMap::default();
     let mut disk_state_map: FxHashMap<u64, DiskTableWidget> = FxHashMap::default();
     let mut battery_state_map: FxHashMap<u64, BatteryWidgetState> = FxHashMap::default();
+    let mut custom_state_map: FxHashMap<u64, CustomWidgetState> = FxHashMap::default();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -365,9 +410,9 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         None
     };
 
-    let mut initial_widget_id: u64 = default_widget_id;
+    let mut initial_widget_id: u64 = widget_layouts[0].default_widget_id;
     let mut initial_widget_type = Proc;
-    let is_custom_layout = config.row.is_some();
+    let is_custom_layout = config.row.is_some() || config.layout.is_some();
     let mut used_widget_set = FxHashSet::default();
 
     let network_unit_type = get_network_unit_type(args, config);
@@ -399,6 +444,8 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let network_legend_position = get_network_legend_position(args, config)?;
     let memory_legend_position = get_memory_legend_position(args, config)?;
     let temperature_legend_position = get_temperature_legend_position(config)?;
+    let custom_sources = get_custom_sources(config)?;
+    let keybindings = get_keybindings(config)?;
 
     // TODO: Can probably just reuse the options struct.
     let app_config_fields = AppConfigFields {
@@ -414,6 +461,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
             cpu.left_legend,
             flags.cpu_left_legend,
         ),
+        cpu_legend_stats: config_or!(config, cpu.legend_stats, false),
         use_current_cpu_total: enabled_option_with_deprecated!(
             args.process.current_usage,
             config,
@@ -447,15 +495,25 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
         is_advanced_kill,
         is_read_only,
+        kill_escalation_ms: get_kill_escalation(config)?,
         #[cfg(target_os = "linux")]
         hide_k_threads,
         memory_legend_position,
+        memory_legend_stats: config_or!(config, memory_graph.legend_stats, false),
         network_legend_position,
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
         network_show_packets,
+        network_legend_stats: config_or!(config, network_graph.legend_stats, false),
         retention_ms,
+        history_file: args.general.history_file.clone().or_else(|| {
+            config
+                .flags
+                .as_ref()
+                .and_then(|flags| flags.history_file.as_ref())
+                .map(PathBuf::from)
+        }),
         dedicated_average_row: enabled_option_with_deprecated!(
             false,
             config,
@@ -465,24 +523,45 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         default_tree_collapse: is_default_tree_collapsed,
         #[cfg(feature = "zfs")]
         free_arc,
-        default_temp_sort_column: config
+        default_temp_sort_columns: config
             .temperature
             .as_ref()
-            .and_then(|cfg| cfg.default_sort.to_owned()),
-        default_disk_sort_column: config
+            .and_then(|cfg| cfg.default_sort.clone())
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default(),
+        default_disk_sort_columns: config
             .disk
             .as_ref()
-            .and_then(|cfg| cfg.default_sort.to_owned()),
+            .and_then(|cfg| cfg.default_sort.clone())
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default(),
         temperature_legend_position,
+        temperature_legend_stats: config_or!(config, temperature_graph.legend_stats, false),
+        custom_sources,
+        process_watch_list: get_process_watch_list(config)?,
+        keybindings,
+        config_path,
+        themes,
     };
 
     let process_default_sort = match &args.process.process_default_sort {
-        Some(name) => Some(ProcColumn::parse_column_name(name).ok_or_else(|| {
-            OptionError::arg(format!(
-                "'{name}' is not a valid process column for '--process_default_sort'"
-            ))
-        })?),
-        None => config.processes.as_ref().and_then(|cfg| cfg.default_sort),
+        Some(names) => names
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                ProcColumn::parse_column_name(name).ok_or_else(|| {
+                    OptionError::arg(format!(
+                        "'{name}' is not a valid process column for '--process_default_sort'"
+                    ))
+                })
+            })
+            .collect::<OptionResult<Vec<_>>>()?,
+        None => config
+            .processes
+            .as_ref()
+            .and_then(|cfg| cfg.default_sort.clone())
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default(),
     };
 
     let ts_config = TimeseriesConfig {
@@ -499,9 +578,10 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         show_memory_as_values: process_memory_as_value,
         is_command: is_default_command,
         default_sort: process_default_sort,
+        group_key: get_process_group_key(args, config),
     };
 
-    for row in &widget_layout.rows {
+    for row in widget_layouts.iter().flat_map(|named| &named.layout.rows) {
         for col in &row.children {
             for col_row in &col.children {
                 for widget in &col_row.children {
@@ -551,6 +631,15 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
                                 ),
                             );
                         }
+                        CpuHeatmap => {
+                            let grouping = config
+                                .cpu
+                                .as_ref()
+                                .map(|cfg| cfg.heatmap_grouping)
+                                .unwrap_or_default();
+                            cpu_heatmap_state_map
+                                .insert(widget.widget_id, CpuHeatmapWidgetState::new(grouping));
+                        }
                         Mem => {
                             mem_state_map.insert(
                                 widget.widget_id,
@@ -577,7 +666,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
                                 ProcWidgetState::new(
                                     &app_config_fields,
                                     mode,
-                                    table_config,
+                                    table_config.clone(),
                                     &styling,
                                     &proc_columns,
                                 ),
@@ -614,6 +703,17 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
                             battery_state_map
                                 .insert(widget.widget_id, BatteryWidgetState::default());
                         }
+                        Custom => {
+                            custom_state_map.insert(
+                                widget.widget_id,
+                                get_custom_widget_state(
+                                    config,
+                                    widget.custom_source.as_deref(),
+                                    ts_config,
+                                    autohide_timer,
+                                )?,
+                            );
+                        }
                         // FIXME: This is kind of a hack that we have these cases at all.
                         Empty | BasicCpu | BasicMem | BasicNet | BasicTables | CpuLegend
                         | ProcSort | ProcSearch => {}
@@ -648,7 +748,9 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let use_mem = used_widget_set.contains(&Mem) || used_widget_set.contains(&BasicMem);
     let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.contains(&Cpu) || used_widget_set.contains(&BasicCpu),
+        use_cpu: used_widget_set.contains(&Cpu)
+            || used_widget_set.contains(&BasicCpu)
+            || used_widget_set.contains(&CpuHeatmap),
         use_mem,
         use_cache: use_mem && get_enable_cache_memory(args, config),
         use_gpu: get_enable_gpu(args, config),
@@ -658,6 +760,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         use_temp: used_widget_set.contains(&Temp),
         use_temp_graph: used_widget_set.contains(&TempGraph),
         use_battery: used_widget_set.contains(&Battery),
+        use_custom: used_widget_set.contains(&Custom),
     };
 
     let (disk_name_filter, disk_mount_filter) = {
@@ -691,6 +794,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let states = AppWidgetStates {
         cpu_state: CpuState::init(cpu_state_map),
+        cpu_heatmap_state: CpuHeatmapStates::init(cpu_heatmap_state_map),
         mem_state: MemState::init(mem_state_map),
         net_state: NetState::init(net_state_map),
         proc_state: ProcState::init(proc_state_map),
@@ -698,6 +802,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         temp_graph_state: TempGraphStates::init(temp_graph_state_map),
         disk_state: DiskState::init(disk_state_map),
         battery_state: AppBatteryState::init(battery_state_map),
+        custom_state: CustomStates::init(custom_state_map),
         basic_table_widget_state,
     };
 
@@ -714,24 +819,50 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     };
     let is_expanded = expanded && !use_basic_mode;
 
-    Ok((
-        App::new(
-            app_config_fields,
-            states,
-            widget_map,
-            current_widget,
-            used_widgets,
-            filters,
-            is_expanded,
-        ),
-        widget_layout,
-        styling,
-    ))
+    let mut layout_names = Vec::with_capacity(widget_layouts.len());
+    let mut layout_sources = Vec::with_capacity(widget_layouts.len());
+    let mut layout_widgets = Vec::with_capacity(widget_layouts.len());
+    let mut layouts = Vec::with_capacity(widget_layouts.len());
+    for named in widget_layouts {
+        match named.section {
+            Some((section, rows)) => {
+                layout_names.push(section.name().to_string());
+                layout_sources.push((section, rows));
+            }
+            None => layout_names.push(DEFAULT_LAYOUT_NAME.to_string()),
+        }
+        layout_widgets.push(named.default_widget_id);
+        layouts.push(named.layout);
+    }
+    layout_widgets[0] = initial_widget_id;
+    let layout_tabs = LayoutTabs::new(layout_names, layout_widgets, layout_sources);
+
+    let mut app = App::new(
+        app_config_fields,
+        states,
+        widget_map,
+        current_widget,
+        used_widgets,
+        filters,
+        is_expanded,
+        layout_tabs,
+    );
+    app.config = config.clone();
+
+    Ok((app, layouts, styling))
+}
+
+/// A built layout, along with where it came from and the ID of its initially selected widget.
+struct NamedBottomLayout {
+    /// The config section and rows the layout was built from. Basic mode has none.
+    section: Option<(LayoutSection, Vec<Row>)>,
+    layout: BottomLayout,
+    default_widget_id: u64,
 }
 
 fn get_widget_layout(
     args: &BottomArgs, config: &Config,
-) -> OptionResult<(BottomLayout, u64, Option<BottomWidgetType>)> {
+) -> OptionResult<(Vec<NamedBottomLayout>, Option<BottomWidgetType>)> {
     let cpu_left_legend = enabled_option_with_deprecated!(
         args.cpu.cpu_left_legend,
         config,
@@ -739,32 +870,56 @@ fn get_widget_layout(
         flags.cpu_left_legend,
     );
 
-    let (default_widget_type, mut default_widget_count) =
-        get_default_widget_and_count(args, config)?;
-    let mut default_widget_id = 1;
+    let (default_widget_type, default_widget_count) = get_default_widget_and_count(args, config)?;
 
-    let bottom_layout = if is_flag_enabled!(basic, args.general, config) {
-        default_widget_id = DEFAULT_WIDGET_ID;
+    if is_flag_enabled!(basic, args.general, config) {
+        let bottom_layout = BottomLayout::init_basic_default(get_use_battery(args, config));
 
-        BottomLayout::init_basic_default(get_use_battery(args, config))
-    } else {
-        let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
-                ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
-                    DEFAULT_BATTERY_LAYOUT
-                } else {
-                    DEFAULT_LAYOUT
-                })?
-                .row
-                .expect("This cannot (like it really shouldn't) fail!");
-                &ref_row
-            }
-        };
+        return Ok((
+            vec![NamedBottomLayout {
+                section: None,
+                layout: bottom_layout,
+                default_widget_id: DEFAULT_WIDGET_ID,
+            }],
+            default_widget_type,
+        ));
+    }
+
+    let mut named_rows: Vec<(LayoutSection, Vec<Row>)> = Vec::new();
+
+    if let Some(rows) = &config.row {
+        named_rows.push((LayoutSection::Row, rows.clone()));
+    }
 
-        let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
+    if let Some(layouts) = &config.layout {
+        for (name, layout) in layouts {
+            named_rows.push((
+                LayoutSection::Named(name.clone()),
+                layout.row.clone().unwrap_or_default(),
+            ));
+        }
+    }
+
+    if named_rows.is_empty() {
+        let rows = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
+            DEFAULT_BATTERY_LAYOUT
+        } else {
+            DEFAULT_LAYOUT
+        })?
+        .row
+        .expect("This cannot (like it really shouldn't) fail!");
+        named_rows.push((LayoutSection::Row, rows));
+    }
+
+    // IDs are shared across all layouts so that every widget is unique.
+    let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
+    let mut layouts = Vec::with_capacity(named_rows.len());
+
+    for (section, rows) in named_rows {
+        let first_id = iter_id;
         let mut total_height_ratio = 0;
+        let mut default_widget_id = first_id + 1;
+        let mut default_widget_count = default_widget_count;
 
         let mut ret_bottom_layout = BottomLayout {
             rows: rows
@@ -785,17 +940,21 @@ fn get_widget_layout(
         };
 
         // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
+        if iter_id > first_id {
             ret_bottom_layout.get_movement_mappings();
-            ret_bottom_layout
+            layouts.push(NamedBottomLayout {
+                section: Some((section, rows)),
+                layout: ret_bottom_layout,
+                default_widget_id,
+            });
         } else {
-            return Err(OptionError::config(
-                "have at least one widget under the '[[row]]' section.",
-            ));
+            return Err(OptionError::config(format!(
+                "have at least one widget under the '{section}' section."
+            )));
         }
-    };
+    }
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    Ok((layouts, default_widget_type))
 }
 
 #[inline]
@@ -947,6 +1106,25 @@ fn get_default_cpu_selection(args: &BottomArgs, config: &Config) -> config::cpu:
     }
 }
 
+fn get_process_group_key(args: &BottomArgs, config: &Config) -> config::process::ProcessGroupKey {
+    use config::process::ProcessGroupKey;
+
+    match &args.process.group_by {
+        Some(key) => match key {
+            args::GroupBy::Name => ProcessGroupKey::Name,
+            args::GroupBy::Command => ProcessGroupKey::Command,
+            args::GroupBy::User => ProcessGroupKey::User,
+            args::GroupBy::Parent => ProcessGroupKey::Parent,
+            args::GroupBy::Exe => ProcessGroupKey::Exe,
+        },
+        None => config
+            .processes
+            .as_ref()
+            .and_then(|cfg| cfg.group_by)
+            .unwrap_or_default(),
+    }
+}
+
 #[inline]
 fn get_default_time_value(
     args: &BottomArgs, config: &Config, retention_ms: u64,
@@ -1262,6 +1440,211 @@ fn get_temperature_legend_position(config: &Config) -> OptionResult<Option<Legen
     )
 }
 
+/// Default timeout for custom source commands.
+const DEFAULT_CUSTOM_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+
+fn get_custom_sources(config: &Config) -> OptionResult<Vec<CustomSource>> {
+    let Some(custom) = &config.custom else {
+        return Ok(Vec::new());
+    };
+
+    let mut names = FxHashSet::default();
+    custom
+        .iter()
+        .map(|cfg| {
+            let name = &cfg.name;
+            if !names.insert(name.as_str()) {
+                return Err(OptionError::config(format!(
+                    "the custom source name '{name}' is used more than once."
+                )));
+            }
+
+            let input = match (&cfg.command, &cfg.file) {
+                (Some(command), None) if !command.is_empty() => {
+                    CustomInput::Command(command.clone())
+                }
+                (None, Some(file)) => CustomInput::File(PathBuf::from(file)),
+                _ => {
+                    return Err(OptionError::config(format!(
+                        "the custom source '{name}' must set exactly one of 'command' or 'file'."
+                    )));
+                }
+            };
+
+            let format = match &cfg.format {
+                Some(format) => format.parse().map_err(OptionError::config)?,
+                None => CustomFormat::default(),
+            };
+
+            let interval = get_custom_interval(cfg)?;
+            let timeout = get_custom_timeout(cfg)?;
+
+            Ok(CustomSource {
+                name: name.clone(),
+                input,
+                format,
+                interval: (interval > 0).then(|| Duration::from_millis(interval)),
+                timeout: Duration::from_millis(timeout),
+            })
+        })
+        .collect()
+}
+
+fn get_keybindings(config: &Config) -> OptionResult<Keybindings> {
+    let Some(keybindings) = &config.keybindings else {
+        return Ok(Keybindings::default());
+    };
+
+    let overrides = keybindings
+        .0
+        .iter()
+        .map(|(name, keys)| {
+            let action = name.parse::<Action>().map_err(|err| {
+                OptionError::config(format!("{err} in the 'keybindings' section."))
+            })?;
+
+            let keys = keys
+                .keys()
+                .iter()
+                .map(|key| {
+                    key.parse::<KeySequence>().map_err(|err| {
+                        OptionError::config(format!("{err} for the '{name}' keybinding."))
+                    })
+                })
+                .collect::<OptionResult<Vec<_>>>()?;
+
+            Ok((action, keys))
+        })
+        .collect::<OptionResult<Vec<_>>>()?;
+
+    Keybindings::with_overrides(overrides)
+        .map_err(|err| OptionError::config(format!("invalid keybindings, {err}.")))
+}
+
+/// The processes to always show the usage history of. Numbers are PIDs, and
+/// strings are names.
+fn get_process_watch_list(config: &Config) -> OptionResult<Vec<WatchedProcess>> {
+    let Some(watch_list) = config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.watch_list.as_ref())
+    else {
+        return Ok(Vec::new());
+    };
+
+    watch_list
+        .iter()
+        .map(|entry| match entry {
+            StringOrNum::String(name) => Ok(WatchedProcess::Name(name.clone())),
+            StringOrNum::Num(pid) => Pid::try_from(*pid)
+                .map(WatchedProcess::Pid)
+                .map_err(|_| OptionError::invalid_config_value("watch_list")),
+        })
+        .collect()
+}
+
+/// How long a kill with escalation waits before sending SIGKILL.
+fn get_kill_escalation(config: &Config) -> OptionResult<u64> {
+    const DEFAULT_KILL_ESCALATION_MS: u64 = 5 * 1000;
+
+    parse_ms_option!(
+        &None::<String>,
+        config
+            .processes
+            .as_ref()
+            .and_then(|processes| processes.kill_escalation.as_ref()),
+        DEFAULT_KILL_ESCALATION_MS,
+        "kill_escalation",
+        None,
+        None,
+    )
+}
+
+/// How often a custom source is run. Zero means every update.
+fn get_custom_interval(cfg: &CustomConfig) -> OptionResult<u64> {
+    parse_ms_option!(
+        &None::<String>,
+        cfg.interval.as_ref(),
+        0,
+        "interval",
+        None,
+        None
+    )
+}
+
+/// How long a custom source command may take.
+fn get_custom_timeout(cfg: &CustomConfig) -> OptionResult<u64> {
+    parse_ms_option!(
+        &None::<String>,
+        cfg.timeout.as_ref(),
+        DEFAULT_CUSTOM_TIMEOUT_IN_MILLISECONDS,
+        "timeout",
+        Some(10),
+        None,
+    )
+}
+
+/// Build the state for a custom widget displaying the source `name`. If `name` is not set,
+/// then there must be exactly one custom source.
+fn get_custom_widget_state(
+    config: &Config, name: Option<&str>, ts_config: TimeseriesConfig,
+    autohide_timer: Option<Instant>,
+) -> OptionResult<CustomWidgetState> {
+    let sources = config.custom.as_deref().unwrap_or_default();
+    let cfg = match name {
+        Some(name) => sources.iter().find(|cfg| cfg.name == name).ok_or_else(|| {
+            OptionError::config(format!("there is no custom source named '{name}'."))
+        })?,
+        None => match sources {
+            [cfg] => cfg,
+            _ => {
+                return Err(OptionError::config(
+                    "set 'name' on each custom widget to pick which '[[custom]]' source to show.",
+                ));
+            }
+        },
+    };
+
+    let series = cfg
+        .series
+        .iter()
+        .flatten()
+        .map(|series| {
+            let style = match &series.colour {
+                Some(colour) => {
+                    Some(Style::default().fg(str_to_colour(colour).map_err(|err| {
+                        OptionError::config(format!(
+                            "invalid colour for the custom source '{}': {err}",
+                            cfg.name
+                        ))
+                    })?))
+                }
+                None => None,
+            };
+
+            Ok(CustomSeries {
+                key: series.key.clone(),
+                name: series.name.clone().unwrap_or_else(|| series.key.clone()),
+                unit: series.unit.clone().unwrap_or_default(),
+                style,
+            })
+        })
+        .collect::<OptionResult<Vec<_>>>()?;
+
+    let legend_position =
+        parse_legend_position(None, cfg.legend_position.as_ref(), None, "legend_position")?;
+
+    Ok(CustomWidgetState::new(
+        ts_config,
+        autohide_timer,
+        cfg.name.clone(),
+        cfg.title.clone().unwrap_or_else(|| cfg.name.clone()),
+        cfg.max,
+        legend_position,
+        series,
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use clap::Parser;
@@ -1506,7 +1889,12 @@ mod test {
         // we're testing with.
         let skip = ["help", "version", "celsius", "battery", "generate_schema"];
 
-        for arg in app.get_arguments().collect::<Vec<_>>() {
+        // Positional arguments (the command to launch) have no flag to test.
+        for arg in app
+            .get_arguments()
+            .filter(|arg| !arg.is_positional())
+            .collect::<Vec<_>>()
+        {
             let arg_name = arg
                 .get_long_and_visible_aliases()
                 .unwrap()
@@ -1537,6 +1925,177 @@ mod test {
         }
     }
 
+    #[test]
+    fn named_layouts() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+              [[row.child]]
+                type="cpu"
+              [[row.child]]
+                type="proc"
+                default=true
+
+            [[layout.overview.row]]
+              [[layout.overview.row.child]]
+                type="mem"
+              [[layout.overview.row.child]]
+                type="disk"
+                default=true
+            "#,
+        )
+        .unwrap();
+
+        let (mut app, layouts, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+
+        assert_eq!(layouts.len(), 2);
+        assert_eq!(app.layout_tabs.names, vec!["default", "overview"]);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+
+        let proc_widget_id = app.current_widget.widget_id;
+        app.switch_layout(1);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Disk);
+
+        app.switch_layout(0);
+        assert_eq!(app.current_widget.widget_id, proc_widget_id);
+
+        // Out-of-range layouts are ignored.
+        app.switch_layout(2);
+        assert_eq!(app.layout_tabs.current, 0);
+    }
+
+    #[test]
+    fn reload_keeps_data_and_layout() {
+        let config_text = r#"
+            [[row]]
+              [[row.child]]
+                type="cpu"
+
+            [[layout.overview.row]]
+              [[layout.overview.row.child]]
+                type="mem"
+            "#;
+
+        let config: Config = toml_edit::de::from_str(config_text).unwrap();
+        let (mut app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        app.switch_layout(1);
+        app.data_store.toggle_frozen();
+
+        let err = toml_edit::de::from_str::<Config>("[[row]\n").unwrap_err();
+        app.set_status_error(&err.into());
+        let status_error = app.status_error.as_deref().unwrap();
+        assert!(status_error.starts_with("TOML parse error at line 1"));
+        assert!(!status_error.contains('\n'));
+
+        let config: Config =
+            toml_edit::de::from_str(&format!("{config_text}\n[processes]\ncolumns = [\"PID\"]"))
+                .unwrap();
+        let (new_app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        app.reload(new_app);
+
+        assert_eq!(app.layout_tabs.current, 1);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Mem);
+        assert!(app.data_store.is_frozen());
+        assert!(app.status_error.is_none());
+    }
+
+    #[test]
+    fn reload_keeps_widget_state() {
+        use crate::canvas::components::data_table::SortOrder;
+
+        let (mut app, _, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let proc_widget_id = app.current_widget.widget_id;
+        let cpu_widget_id = app
+            .states
+            .cpu_state
+            .widget_states
+            .keys()
+            .next()
+            .copied()
+            .unwrap();
+        app.current_widget = app.widget_map[&cpu_widget_id].clone();
+
+        let proc = app
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&proc_widget_id)
+            .unwrap();
+        proc.proc_search
+            .search_state
+            .input_field_state
+            .insert_string("bash".to_string());
+        proc.table.set_sort_keys([(1, SortOrder::Ascending)]);
+        proc.marked_pids.insert(1);
+
+        // Only the CPU config changes, so the process widget is kept.
+        let config: Config = toml_edit::de::from_str("[cpu]\ndefault = \"average\"").unwrap();
+        let (new_app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        app.reload(new_app);
+
+        assert_eq!(app.current_widget.widget_id, cpu_widget_id);
+        let proc = &app.states.proc_state.widget_states[&proc_widget_id];
+        assert_eq!(
+            proc.proc_search
+                .search_state
+                .input_field_state
+                .current_query(),
+            "bash"
+        );
+        assert_eq!(proc.table.sort_keys(), vec![(1, SortOrder::Ascending)]);
+        assert!(proc.marked_pids.contains(&1));
+
+        // The process config changes, so the process widget is rebuilt.
+        let config: Config =
+            toml_edit::de::from_str("[processes]\ncolumns = [\"PID\", \"Name\"]").unwrap();
+        let (new_app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        app.reload(new_app);
+
+        let proc = &app.states.proc_state.widget_states[&proc_widget_id];
+        assert!(proc.marked_pids.is_empty());
+        assert!(
+            proc.proc_search
+                .search_state
+                .input_field_state
+                .current_query()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn ignore_own_column_saves() {
+        use std::{fs::File, time::SystemTime};
+
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(&path, "").unwrap();
+
+        let (mut app, _, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        app.app_config_fields.config_path = Some(path.clone());
+        assert!(!app.is_config_saved_by_self(&path));
+
+        let proc_widget_id = app.current_widget.widget_id;
+        let columns = app.states.proc_state.widget_states[&proc_widget_id].shown_columns();
+        app.process_columns_dialog.open(&columns);
+        app.on_columns_dialog_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+        assert!(fs::read_to_string(&path).unwrap().contains("columns"));
+        assert!(app.is_config_saved_by_self(&path));
+
+        // Changes made by anything else are still reloaded.
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert!(!app.is_config_saved_by_self(&path));
+    }
+
     /// This one has slightly more complex behaviour due to `dirs` not
     /// respecting XDG on macOS, so we manually handle it. However, to
     /// ensure backwards-compatibility, we also have to do some special cases.
@@ -21,7 +21,7 @@ const TEMPLATE: &str = indoc! {
     {all-args}"
 };
 
-const USAGE: &str = "btm [OPTIONS]";
+const USAGE: &str = "btm [OPTIONS] [-- <COMMAND>...]";
 
 const VERSION: &str = match option_env!("NIGHTLY_VERSION") {
     Some(nightly_version) => nightly_version,
@@ -41,7 +41,7 @@ const CHART_WIDGET_POSITIONS: [&str; 9] = [
 ];
 
 /// Represents the arguments that can be passed in to bottom.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(
     name = crate_name!(),
     version = VERSION,
@@ -239,6 +239,17 @@ pub struct GeneralArgs {
     #[arg(long, action = ArgAction::SetTrue, help = "Hides the time scale from being shown.", alias = "hide-time")]
     pub hide_time: bool,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Saves graph history to a file, and loads it on startup.",
+        long_help = "Saves graph history to a file every minute and when bottom exits, and loads it again on \
+                    startup. Only data within the retention period (--retention) is loaded.",
+        alias = "history-file"
+    )]
+    pub history_file: Option<PathBuf>,
+
     #[arg(
         short = 'r',
         long,
@@ -255,8 +266,9 @@ pub struct GeneralArgs {
         value_name = "TIME",
         help = "How far back data will be stored up to.",
         long_help = "How far back data will be stored up to. Either a number in milliseconds or a 'human duration' \
-                    (e.g. 10m, 1h). Defaults to 10 minutes, and must be at least  1 minute. Larger values \
-                    may result in higher memory usage."
+                    (e.g. 10m, 1h). Defaults to 10 minutes, and must be at least  1 minute. Data older than \
+                    10 minutes is kept as averages over 10 seconds for up to 6 hours, and over 1 minute past \
+                    that."
     )]
     pub retention: Option<String>,
 
@@ -345,15 +357,40 @@ pub struct ProcessArgs {
 
     #[arg(
         long,
-        value_name = "COLUMN",
-        help = "Sets the default sort column for the process widget.",
+        value_name = "COLUMNS",
+        help = "Sets the default sort columns for the process widget.",
         long_help = "Sets the default sort column for the process widget. Accepts any of the \
                      valid process column names (e.g. \"cpu%\", \"mem\", \"pid\", \"name\"). \
-                     Overrides the [processes] default_sort setting in the config file.",
+                     A comma-separated list sorts by each column in turn when processes are \
+                     tied, e.g. \"user,cpu%\". Overrides the [processes] default_sort setting \
+                     in the config file.",
         alias = "process-default-sort"
     )]
     pub process_default_sort: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "PID",
+        num_args = 1..,
+        conflicts_with = "command",
+        help = "Monitors the given processes and their descendants.",
+        long_help = "Monitors the given processes and their descendants. The process widget only shows \
+                     their trees, and once all of them have exited, bottom exits and prints a summary \
+                     of their peak CPU usage, peak memory usage, total I/O, and wall time. Fails if none of \
+                     them are running."
+    )]
+    pub pid: Vec<u32>,
+
+    #[arg(
+        last = true,
+        value_name = "COMMAND",
+        help = "Launches a command and monitors it and its descendants.",
+        long_help = "Launches a command and monitors it and its descendants, like --pid. Its input and \
+                     output are discarded, and it is killed if bottom is quit before it exits. For \
+                     example, 'btm -- cargo build'."
+    )]
+    pub command: Vec<String>,
+
     #[arg(
         short = 'g',
         long,
@@ -365,6 +402,18 @@ pub struct ProcessArgs {
     )]
     pub group_processes: bool,
 
+    #[arg(
+        long,
+        value_name = "KEY",
+        value_parser = value_parser!(GroupBy),
+        help = "Sets what processes are grouped by when grouped.",
+        long_help = "Sets what processes are grouped by when grouped: the process name (the default), \
+                     the full command, the user, the parent process, or the executable path. \
+                     Overrides the [processes] group_by setting in the config file.",
+        alias = "group-by"
+    )]
+    pub group_by: Option<GroupBy>,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -420,6 +469,38 @@ pub struct ProcessArgs {
     pub whole_word: bool,
 }
 
+/// What processes are grouped by in grouped mode.
+#[derive(Clone, Copy, Debug)]
+pub enum GroupBy {
+    Name,
+    Command,
+    User,
+    Parent,
+    Exe,
+}
+
+impl ValueEnum for GroupBy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            GroupBy::Name,
+            GroupBy::Command,
+            GroupBy::User,
+            GroupBy::Parent,
+            GroupBy::Exe,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            GroupBy::Name => Some(PossibleValue::new("name")),
+            GroupBy::Command => Some(PossibleValue::new("command")),
+            GroupBy::User => Some(PossibleValue::new("user")),
+            GroupBy::Parent => Some(PossibleValue::new("parent")),
+            GroupBy::Exe => Some(PossibleValue::new("exe").alias("executable")),
+        }
+    }
+}
+
 /// Temperature arguments/config options.
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Temperature Options", rename_all = "snake_case")]
@@ -636,26 +717,21 @@ pub struct StyleArgs {
     #[arg(
         long,
         value_name = "SCHEME",
-        value_parser = [
-            "default",
-            "default-light",
-            "gruvbox",
-            "gruvbox-light",
-            "nord",
-            "nord-light",
-        ],
-        hide_possible_values = true,
         help = indoc! {
-            "Use a built-in colour theme, use '--help' for info on the colours. [possible values: default, default-light, gruvbox, gruvbox-light, nord, nord-light]",
+            "Use a built-in colour theme or a theme file, use '--help' for info on the colours. [built-in values: default, default-light, gruvbox, gruvbox-light, nord, nord-light]",
         },
         long_help = indoc! {
-            "Use a pre-defined colour theme. Currently supported themes are:
+            "Use a pre-defined colour theme. Currently supported built-in themes are:
             - default
             - default-light (default but adjusted for lighter backgrounds)
             - gruvbox       (a bright theme with 'retro groove' colours)
             - gruvbox-light (gruvbox but adjusted for lighter backgrounds)
             - nord          (an arctic, north-bluish colour palette)
-            - nord-light    (nord but adjusted for lighter backgrounds)"
+            - nord-light    (nord but adjusted for lighter backgrounds)
+
+            Any other name is looked up as a '<SCHEME>.toml' theme file in the
+            'themes' directory next to the config file, or in 'bottom/themes' in
+            the XDG data directories."
         }
     )]
     pub theme: Option<String>,
@@ -1,7 +1,9 @@
 pub mod cpu;
+pub mod custom;
 pub mod disk;
 pub mod flags;
 mod ignore_list;
+pub mod keybindings;
 pub mod layout;
 pub mod memory_graph;
 pub mod network_graph;
@@ -10,26 +12,39 @@ pub mod style;
 pub mod temperature;
 pub mod temperature_graph;
 
+use std::{fmt, marker::PhantomData};
+
+use custom::CustomConfig;
 use disk::DiskConfig;
 use flags::GeneralConfig;
+use indexmap::IndexMap;
+use keybindings::KeybindingsConfig;
 use network_graph::NetworkGraphConfig;
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Serialize,
+    de::{self, IntoDeserializer, SeqAccess, Visitor, value::SeqAccessDeserializer},
+};
 use style::StyleConfig;
 use temperature::TempConfig;
 use temperature_graph::TempGraphConfig;
 
 pub use self::ignore_list::IgnoreList;
-use self::{cpu::CpuConfig, layout::Row, process::ProcessesConfig};
+use self::{
+    cpu::CpuConfig,
+    layout::{NamedLayout, Row},
+    process::ProcessesConfig,
+};
 use crate::options::config::memory_graph::MemoryGraphConfig;
 
 /// Overall config for `bottom`.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub struct Config {
     pub(crate) flags: Option<GeneralConfig>,
     pub(crate) styles: Option<StyleConfig>,
     pub(crate) row: Option<Vec<Row>>,
+    pub(crate) layout: Option<IndexMap<String, NamedLayout>>,
     pub(crate) processes: Option<ProcessesConfig>,
     pub(crate) disk: Option<DiskConfig>,
     pub(crate) temperature: Option<TempConfig>,
@@ -39,12 +54,13 @@ pub struct Config {
     #[serde(alias = "memory")]
     pub(crate) memory_graph: Option<MemoryGraphConfig>,
     pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) custom: Option<Vec<CustomConfig>>,
+    pub(crate) keybindings: Option<KeybindingsConfig>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub(crate) enum StringOrNum {
     String(String),
     Num(u64),
@@ -62,6 +78,56 @@ impl From<u64> for StringOrNum {
     }
 }
 
+/// Either a single value or a list of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "generate_schema",
+    derive(schemars::JsonSchema),
+    schemars(untagged)
+)]
+pub(crate) enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+// This isn't derived with `serde(untagged)`, as that would replace the error
+// for an invalid value with a generic one.
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OneOrMany<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct OneOrManyVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for OneOrManyVisitor<T> {
+            type Value = OneOrMany<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a value or a list of values")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                T::deserialize(v.into_deserializer()).map(OneOrMany::One)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(OneOrMany::Many)
+            }
+        }
+
+        deserializer.deserialize_any(OneOrManyVisitor(PhantomData))
+    }
+}
+
+impl<T> OneOrMany<T> {
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1,10 +1,9 @@
 use serde::Deserialize;
 
 /// The default selected entry of the CPU widget.
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub(crate) enum CpuDefault {
     #[default]
     All,
@@ -12,10 +11,22 @@ pub(crate) enum CpuDefault {
     Average,
 }
 
+/// How the cores of the CPU heatmap widget are grouped.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CpuHeatmapGrouping {
+    #[default]
+    None,
+    Socket,
+    #[serde(alias = "node")]
+    Numa,
+}
+
 /// CPU column settings.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct CpuConfig {
     /// The default selected entry of the CPU widget.
     #[serde(default)]
@@ -30,8 +41,17 @@ pub(crate) struct CpuConfig {
     /// Whether to put the CPU chart legend on the left side.
     pub(crate) left_legend: Option<bool>,
 
+    /// Whether to show a legend in the graph with the min, average, max and 95th percentile of the
+    /// selected entry over the visible time range.
+    pub(crate) legend_stats: Option<bool>,
+
     /// Whether to give the average CPU entry a dedicated row in basic mode.
     pub(crate) basic_average_cpu_row: Option<bool>,
+
+    /// How to group the cores of the CPU heatmap widget. Grouping by socket
+    /// or NUMA node is only supported on Linux.
+    #[serde(default)]
+    pub(crate) heatmap_grouping: CpuHeatmapGrouping,
 }
 
 #[cfg(test)]
@@ -0,0 +1,59 @@
+use serde::Deserialize;
+
+use super::StringOrNum;
+
+/// Configuration for a custom metric source, which is fed by either a command or a file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
+pub(crate) struct CustomConfig {
+    /// The name of the source. Used by `custom` widgets in the layout to refer to this source.
+    pub(crate) name: String,
+
+    /// A command to run, as a program followed by its arguments. This is not run through a shell.
+    pub(crate) command: Option<Vec<String>>,
+
+    /// A file to read. Only one of `command` or `file` can be set.
+    pub(crate) file: Option<String>,
+
+    /// How often to run the command or read the file. Defaults to every update.
+    pub(crate) interval: Option<StringOrNum>,
+
+    /// How long to wait for a command to finish before killing it. Defaults to 1 second.
+    pub(crate) timeout: Option<StringOrNum>,
+
+    /// The format of the output. One of "auto" (default), "plain", "key_value", or "json".
+    pub(crate) format: Option<String>,
+
+    /// The title of the widget. Defaults to the source name.
+    pub(crate) title: Option<String>,
+
+    /// The location of the graph's legend.
+    pub(crate) legend_position: Option<String>,
+
+    /// An upper bound for the graph. If not set, the graph scales to fit the data.
+    pub(crate) max: Option<f64>,
+
+    /// The series to display. If not set, all values found in the output are shown.
+    pub(crate) series: Option<Vec<CustomSeriesConfig>>,
+}
+
+/// A single series for a custom metric source.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
+pub(crate) struct CustomSeriesConfig {
+    /// The key to read the value from. For plain output, this is the zero-based index of the
+    /// value. For JSON output, nested values can be referred to with dots, e.g. `"load.avg1"`.
+    pub(crate) key: String,
+
+    /// The name shown in the legend. Defaults to the key.
+    pub(crate) name: Option<String>,
+
+    /// A unit shown after the value in the legend.
+    pub(crate) unit: Option<String>,
+
+    /// The colour of the series.
+    #[serde(alias = "color")]
+    pub(crate) colour: Option<String>,
+}
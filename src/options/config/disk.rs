@@ -1,12 +1,12 @@
 use serde::Deserialize;
 
-use super::IgnoreList;
+use super::{IgnoreList, OneOrMany};
 use crate::options::DiskWidgetColumn;
 
 /// Disk configuration.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct DiskConfig {
     /// A filter over the disk names.
     pub(crate) name_filter: Option<IgnoreList>,
@@ -20,14 +20,16 @@ pub(crate) struct DiskConfig {
     #[serde(default)]
     pub(crate) columns: Option<Vec<DiskWidgetColumn>>,
 
-    /// The default sort column.
+    /// The default sort column, or a list of columns to sort by in order of
+    /// priority.
     #[serde(default)]
-    pub(crate) default_sort: Option<DiskWidgetColumn>,
+    pub(crate) default_sort: Option<OneOrMany<DiskWidgetColumn>>,
 }
 
 #[cfg(test)]
 mod test {
-    use super::DiskConfig;
+    use super::{DiskConfig, OneOrMany};
+    use crate::options::DiskWidgetColumn;
 
     #[test]
     fn none_column_setting() {
@@ -54,4 +56,24 @@ mod test {
         let config = r#"columns = ["diskk"]"#;
         toml_edit::de::from_str::<DiskConfig>(config).expect_err("Should error out!");
     }
+
+    #[test]
+    fn default_sort_settings() {
+        let config = r#"default_sort = "r/s""#;
+        let generated: DiskConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.default_sort.map(OneOrMany::into_vec),
+            Some(vec![DiskWidgetColumn::IoRead])
+        );
+
+        let config = r#"default_sort = ["mount", "disk"]"#;
+        let generated: DiskConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.default_sort.map(OneOrMany::into_vec),
+            Some(vec![DiskWidgetColumn::Mount, DiskWidgetColumn::Disk])
+        );
+
+        let config = r#"default_sort = ["mount", "diskk"]"#;
+        toml_edit::de::from_str::<DiskConfig>(config).expect_err("Should error out!");
+    }
 }
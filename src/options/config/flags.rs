@@ -23,9 +23,9 @@ impl TableGap {
 }
 
 // TODO: Break this up.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct GeneralConfig {
     pub(crate) dot_marker: Option<bool>,
     pub(crate) rate: Option<StringOrNum>,
@@ -49,6 +49,7 @@ pub(crate) struct GeneralConfig {
     pub(crate) read_only: Option<bool>,
     pub(crate) disable_gpu: Option<bool>,
     pub(crate) retention: Option<StringOrNum>,
+    pub(crate) history_file: Option<String>,
     pub(crate) temperature_type: Option<String>,
 
     // FIXME: Deprecate these in the future.
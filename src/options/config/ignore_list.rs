@@ -5,9 +5,9 @@ fn default_as_true() -> bool {
     true
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub struct IgnoreList {
     #[serde(default = "default_as_true")]
     // TODO: Deprecate and/or rename, current name sounds awful.
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// Keybinding configuration, which maps action names to the keys that trigger them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+pub(crate) struct KeybindingsConfig(pub(crate) BTreeMap<String, KeyList>);
+
+/// The keys bound to an action. Each entry is either a single key like `"ctrl-r"`, or a
+/// two-key chord separated by a space like `"g g"`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+pub(crate) enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub(crate) fn keys(&self) -> &[String] {
+        match self {
+            KeyList::One(key) => std::slice::from_ref(key),
+            KeyList::Many(keys) => keys,
+        }
+    }
+}
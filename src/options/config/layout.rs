@@ -1,18 +1,58 @@
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
+use toml_edit::{ArrayOfTables, Item, Table, Value, value};
 
-use crate::{app::layout_manager::*, options::OptionResult};
+use crate::{app::layout_manager::*, constants::DEFAULT_LAYOUT_NAME, options::OptionResult};
 
 /// Represents a row. This has a length of some sort (optional) and a vector
 /// of children.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 #[serde(rename = "row")]
 pub struct Row {
     pub ratio: Option<u16>,
     pub child: Option<Vec<RowChildren>>,
 }
 
+/// A named layout, made up of rows in the same way as the top-level `[[row]]` layout.
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
+pub struct NamedLayout {
+    pub row: Option<Vec<Row>>,
+}
+
+/// Where a layout is defined in the config file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutSection {
+    /// The top-level `[[row]]` section.
+    Row,
+
+    /// A `[[layout.<name>.row]]` section.
+    Named(String),
+}
+
+impl LayoutSection {
+    /// The name of the layout, as shown in the tab bar.
+    pub fn name(&self) -> &str {
+        match self {
+            LayoutSection::Row => DEFAULT_LAYOUT_NAME,
+            LayoutSection::Named(name) => name,
+        }
+    }
+}
+
+impl Display for LayoutSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutSection::Row => write!(f, "[[row]]"),
+            LayoutSection::Named(name) => write!(f, "[[layout.{name}.row]]"),
+        }
+    }
+}
+
 fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64) -> BottomColRow {
     let cpu_id = *iter_id;
     *iter_id += 1;
@@ -115,10 +155,10 @@ impl Row {
                                 .total_col_row_ratio(2)
                                 .ratio(width_ratio)
                             }
-                            _ => BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
-                                widget_type,
-                                *iter_id,
-                            )])])
+                            _ => BottomCol::new(vec![BottomColRow::new(vec![
+                                BottomWidget::new(widget_type, *iter_id)
+                                    .custom_source(widget.name.clone()),
+                            ])])
                             .ratio(width_ratio),
                         });
                     }
@@ -186,10 +226,10 @@ impl Row {
                                     total_col_row_ratio += col_row_height_ratio;
 
                                     col_row_children.push(
-                                        BottomColRow::new(vec![BottomWidget::new(
-                                            widget_type,
-                                            *iter_id,
-                                        )])
+                                        BottomColRow::new(vec![
+                                            BottomWidget::new(widget_type, *iter_id)
+                                                .custom_source(widget.name.clone()),
+                                        ])
                                         .ratio(col_row_height_ratio),
                                     )
                                 }
@@ -210,6 +250,103 @@ impl Row {
             .total_col_ratio(total_col_ratio)
             .ratio(row_ratio))
     }
+
+    /// Updates a TOML table from the config file to match the row, so it can
+    /// be written back. Anything that hasn't changed is left as it is, along
+    /// with its comments and formatting.
+    pub fn update_table(&self, table: &mut Table) {
+        update_value(
+            table,
+            "ratio",
+            self.ratio.map(|ratio| i64::from(ratio).into()),
+        );
+        update_tables(
+            table,
+            "child",
+            self.child.as_deref(),
+            |child, table| match child {
+                RowChildren::Widget(widget) => {
+                    table.remove("child");
+                    widget.update_table(table);
+                }
+                RowChildren::Col { ratio, child } => {
+                    for key in ["type", "default", "name"] {
+                        table.remove(key);
+                    }
+                    update_value(table, "ratio", ratio.map(|ratio| i64::from(ratio).into()));
+                    update_tables(table, "child", Some(child), FinalWidget::update_table);
+                }
+            },
+        );
+    }
+}
+
+/// Updates the `row` array of tables in `table` to match `rows`, like
+/// [`Row::update_table`].
+pub fn update_rows(table: &mut Table, rows: &[Row]) {
+    update_tables(table, "row", Some(rows), Row::update_table);
+}
+
+/// Sets `key` in `table` to `new`, or removes it if there is no new value. An
+/// unchanged value is left alone, and a changed one keeps its comments.
+fn update_value(table: &mut Table, key: &str, new: Option<Value>) {
+    let Some(new) = new else {
+        table.remove(key);
+        return;
+    };
+
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        Some(old) => {
+            let is_same = match (&*old, &new) {
+                (Value::Integer(old), Value::Integer(new)) => old.value() == new.value(),
+                (Value::String(old), Value::String(new)) => old.value() == new.value(),
+                (Value::Boolean(old), Value::Boolean(new)) => old.value() == new.value(),
+                _ => false,
+            };
+
+            if !is_same {
+                let decor = old.decor().clone();
+                *old = new;
+                *old.decor_mut() = decor;
+            }
+        }
+        None => {
+            table.insert(key, value(new));
+        }
+    }
+}
+
+/// Updates the array of tables at `key` in `table` to hold `items`, updating
+/// the tables already there in place and only adding or removing tables at
+/// the end. The array is removed if there are no items.
+fn update_tables<T>(
+    table: &mut Table, key: &str, items: Option<&[T]>, update: impl Fn(&T, &mut Table),
+) {
+    let Some(items) = items.filter(|items| !items.is_empty()) else {
+        table.remove(key);
+        return;
+    };
+
+    if !table.get(key).is_some_and(Item::is_array_of_tables) {
+        table.insert(key, Item::ArrayOfTables(ArrayOfTables::new()));
+    }
+    let Some(tables) = table.get_mut(key).and_then(Item::as_array_of_tables_mut) else {
+        return;
+    };
+
+    while tables.len() > items.len() {
+        tables.remove(tables.len() - 1);
+    }
+    for (index, item) in items.iter().enumerate() {
+        if index == tables.len() {
+            // New tables have no position, so they are written right after
+            // the table before them.
+            tables.push(Table::new());
+        }
+        if let Some(table) = tables.get_mut(index) {
+            update(item, table);
+        }
+    }
 }
 
 /// Represents a child of a Row - either a Col (column) or a FinalWidget.
@@ -217,10 +354,10 @@ impl Row {
 /// A Col can also have an optional length and children.  We only allow columns
 /// to have FinalWidgets as children, lest we get some amount of mutual
 /// recursion between Row and Col.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub enum RowChildren {
     Widget(FinalWidget),
     Col {
@@ -230,14 +367,31 @@ pub enum RowChildren {
 }
 
 /// Represents a widget.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub struct FinalWidget {
     pub ratio: Option<u16>,
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// The name of the custom source to display. Only used by `custom` widgets.
+    pub name: Option<String>,
+}
+
+impl FinalWidget {
+    /// Updates a TOML table from the config file to match the widget, like
+    /// [`Row::update_table`].
+    pub fn update_table(&self, table: &mut Table) {
+        update_value(
+            table,
+            "ratio",
+            self.ratio.map(|ratio| i64::from(ratio).into()),
+        );
+        update_value(table, "type", Some(self.widget_type.as_str().into()));
+        update_value(table, "default", self.default.map(Value::from));
+        update_value(table, "name", self.name.as_deref().map(Value::from));
+    }
 }
 
 #[cfg(test)]
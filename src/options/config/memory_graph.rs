@@ -3,15 +3,19 @@
 use serde::Deserialize;
 
 /// Memory-related configuration file options.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub struct MemoryGraphConfig {
     // TODO: We probably want to make this an enum...? If we want to also support external legends
     // (e.g. table-style, list-style) then we probably need a new system outright.
     /// Where to place the legend for the memory chart widget.
     pub(crate) legend_position: Option<String>,
 
+    /// Whether to show the min, average, max and 95th percentile of each entry over the visible
+    /// time range in the legend.
+    pub(crate) legend_stats: Option<bool>,
+
     /// Whether to collect and display cache and buffer memory. Not available on Windows.
     #[cfg_attr(target_os = "windows", allow(dead_code))]
     pub(crate) cache_memory: Option<bool>,
@@ -3,9 +3,9 @@ use serde::Deserialize;
 use super::IgnoreList;
 
 /// Network configuration.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct NetworkGraphConfig {
     /// A filter over the network interface names.
     pub(crate) interface_filter: Option<IgnoreList>,
@@ -18,6 +18,10 @@ pub(crate) struct NetworkGraphConfig {
     /// Where to place the legend for the network chart widget.
     pub(crate) legend_position: Option<String>,
 
+    /// Whether to show the min, average, max and 95th percentile of each entry over the visible
+    /// time range in the legend.
+    pub(crate) legend_stats: Option<bool>,
+
     /// Displays the network widget using bytes. Defaults to bits.
     pub(crate) use_bytes: Option<bool>,
 
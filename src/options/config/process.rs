@@ -1,11 +1,31 @@
 use serde::Deserialize;
 
+use super::{OneOrMany, StringOrNum};
 use crate::widgets::ProcColumn;
 
+/// What processes are grouped by in grouped mode.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ProcessGroupKey {
+    /// The process name, or the full command if it is shown instead.
+    #[default]
+    Name,
+    /// The full command.
+    Command,
+    /// The user running the process.
+    User,
+    /// The parent process.
+    Parent,
+    /// The path of the executable.
+    #[serde(alias = "executable")]
+    Exe,
+}
+
 /// Process configuration fields.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct ProcessesConfig {
     /// A list of process widget columns.
     ///
@@ -14,9 +34,10 @@ pub(crate) struct ProcessesConfig {
     #[serde(default)]
     pub columns: Vec<ProcColumn>,
 
-    /// The default sort column.
+    /// The default sort column, or a list of columns to sort by in order of
+    /// priority.
     #[serde(default)]
-    pub default_sort: Option<ProcColumn>,
+    pub default_sort: Option<OneOrMany<ProcColumn>>,
 
     /// Whether to get process child threads.
     pub get_threads: Option<bool>,
@@ -42,12 +63,20 @@ pub(crate) struct ProcessesConfig {
     )]
     pub disable_advanced_kill: Option<bool>,
 
+    /// How long to wait after a signal before sending SIGKILL to any processes
+    /// that have not exited, when escalation is turned on in the kill dialog.
+    /// Defaults to 5s. Does nothing on Windows.
+    pub kill_escalation: Option<StringOrNum>,
+
     /// Defaults to showing process memory usage by value.
     pub default_memory_value: Option<bool>,
 
     /// Groups processes with the same name by default. No effect if `--tree` is set.
     pub default_grouped: Option<bool>,
 
+    /// What processes are grouped by when grouped. Defaults to the name.
+    pub group_by: Option<ProcessGroupKey>,
+
     /// Enables regex by default while searching.
     pub regex: Option<bool>,
 
@@ -65,11 +94,15 @@ pub(crate) struct ProcessesConfig {
 
     /// Show process CPU% usage without averaging over the number of CPU cores.
     pub unnormalized_cpu: Option<bool>,
+
+    /// Processes to always show the usage history of, by name or PID. These are also
+    /// recorded as events when they start or exit.
+    pub watch_list: Option<Vec<StringOrNum>>,
 }
 
 #[cfg(test)]
 mod test {
-    use super::{ProcColumn, ProcessesConfig};
+    use super::{OneOrMany, ProcColumn, ProcessesConfig};
     use crate::widgets::ProcWidgetColumn;
 
     #[test]
@@ -131,11 +164,24 @@ mod test {
     fn valid_default_sort_config() {
         let config = r#"default_sort = "mem""#;
         let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
-        assert_eq!(generated.default_sort, Some(ProcColumn::MemPercent));
+        assert_eq!(
+            generated.default_sort,
+            Some(OneOrMany::One(ProcColumn::MemPercent))
+        );
 
         let config = r#"default_sort = "PID""#;
         let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
-        assert_eq!(generated.default_sort, Some(ProcColumn::Pid));
+        assert_eq!(
+            generated.default_sort,
+            Some(OneOrMany::One(ProcColumn::Pid))
+        );
+
+        let config = r#"default_sort = ["user", "cpu%"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.default_sort.map(OneOrMany::into_vec),
+            Some(vec![ProcColumn::User, ProcColumn::CpuPercent])
+        );
 
         let config = "";
         let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
@@ -146,6 +192,9 @@ mod test {
     fn invalid_default_sort_config() {
         let config = r#"default_sort = "soup""#;
         toml_edit::de::from_str::<ProcessesConfig>(config).expect_err("Should error out!");
+
+        let config = r#"default_sort = ["user", "soup"]"#;
+        toml_edit::de::from_str::<ProcessesConfig>(config).expect_err("Should error out!");
     }
 
     #[test]
@@ -8,11 +8,13 @@ mod memory;
 mod network;
 mod tables;
 mod temp_graph;
+mod theme_files;
 mod themes;
 mod utils;
+mod value_colours;
 mod widgets;
 
-use std::borrow::Cow;
+use std::{borrow::Cow, path::PathBuf};
 
 use battery::BatteryStyle;
 use cpu::CpuStyle;
@@ -22,25 +24,25 @@ use network::NetworkStyle;
 use serde::{Deserialize, Serialize};
 use tables::TableStyle;
 use temp_graph::TempGraphStyle;
+pub(crate) use theme_files::{ThemeCycle, theme_dirs};
 use tui::{style::Style, widgets::BorderType};
+pub(crate) use utils::str_to_colour;
 use utils::{opt, set_colour, set_colour_list, set_style};
+pub(crate) use value_colours::ValueColours;
 use widgets::WidgetStyle;
 
 use super::Config;
-use crate::options::{
-    OptionError, OptionResult, args::BottomArgs, config::style::utils::set_bg_colour,
-};
+use crate::options::{OptionResult, args::BottomArgs, config::style::utils::set_bg_colour};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub(crate) struct ColourStr(Cow<'static, str>);
 
 /// A style for text.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) enum TextStyleConfig {
     Colour(ColourStr),
     TextStyle {
@@ -63,11 +65,12 @@ pub(crate) enum TextStyleConfig {
 }
 
 /// Style-related configs.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct StyleConfig {
-    /// A built-in theme.
+    /// A built-in theme, or the name of a theme file. In a theme file, this is
+    /// the built-in theme that the file's styles are applied on top of.
     ///
     /// If this is and a custom colour are both set, in the config file,
     /// the custom colour scheme will be prioritized first. If a theme
@@ -133,9 +136,11 @@ pub struct Styles {
     pub(crate) low_battery: Style,
     pub(crate) invalid_query_style: Style,
     pub(crate) disabled_text_style: Style,
+    pub(crate) marked_text_style: Style,
     #[cfg(target_os = "linux")]
     pub(crate) thread_text_style: Style,
     pub(crate) border_type: BorderType,
+    pub(crate) value_colours: ValueColours,
 }
 
 impl Default for Styles {
@@ -145,13 +150,10 @@ impl Default for Styles {
 }
 
 impl Styles {
-    pub fn new(args: &BottomArgs, config: &Config) -> anyhow::Result<Self> {
-        let mut palette = match &args.style.theme {
-            Some(theme) => Self::from_theme(theme)?,
-            None => match config.styles.as_ref().and_then(|s| s.theme.as_ref()) {
-                Some(theme) => Self::from_theme(theme)?,
-                None => Self::default(),
-            },
+    pub fn new(args: &BottomArgs, config: &Config, theme_dirs: &[PathBuf]) -> anyhow::Result<Self> {
+        let mut palette = match Self::theme_name(args, config) {
+            Some(theme) => Self::from_theme(theme, theme_dirs)?,
+            None => Self::default(),
         };
 
         // Apply theme from config on top.
@@ -162,19 +164,12 @@ impl Styles {
         Ok(palette)
     }
 
-    fn from_theme(theme: &str) -> anyhow::Result<Self> {
-        let lower_case = theme.to_lowercase();
-        match lower_case.as_str() {
-            "default" => Ok(Self::default_palette()),
-            "default-light" => Ok(Self::default_light_palette()),
-            "gruvbox" => Ok(Self::gruvbox_palette()),
-            "gruvbox-light" => Ok(Self::gruvbox_light_palette()),
-            "nord" => Ok(Self::nord_palette()),
-            "nord-light" => Ok(Self::nord_light_palette()),
-            _ => Err(OptionError::other(format!(
-                "'{theme}' is an invalid built-in colour scheme."
-            ))
-            .into()),
+    /// The name of the theme set in either the args or the config, with the
+    /// args taking priority.
+    pub(crate) fn theme_name<'a>(args: &'a BottomArgs, config: &'a Config) -> Option<&'a str> {
+        match &args.style.theme {
+            Some(theme) => Some(theme),
+            None => config.styles.as_ref().and_then(|s| s.theme.as_deref()),
         }
     }
 
@@ -217,6 +212,9 @@ impl Styles {
 
         // Tables
         set_style!(self.table_header_style, config.tables, headers);
+        if let Some(value_colours) = opt!(config.tables.as_ref()?.value_colours.as_ref()) {
+            self.value_colours = ValueColours::new(value_colours)?;
+        }
 
         // Widget graphs
         set_colour!(self.graph_style, config.graphs, graph_colour);
@@ -228,6 +226,7 @@ impl Styles {
         set_style!(self.text_style, config.widgets, text);
         set_style!(self.selected_text_style, config.widgets, selected_text);
         set_style!(self.disabled_text_style, config.widgets, disabled_text);
+        set_style!(self.marked_text_style, config.widgets, marked_text);
 
         #[cfg(target_os = "linux")]
         {
@@ -332,11 +331,11 @@ mod test {
 
     #[test]
     fn built_in_colour_schemes_work() {
-        Styles::from_theme("default").unwrap();
-        Styles::from_theme("default-light").unwrap();
-        Styles::from_theme("gruvbox").unwrap();
-        Styles::from_theme("gruvbox-light").unwrap();
-        Styles::from_theme("nord").unwrap();
-        Styles::from_theme("nord-light").unwrap();
+        Styles::from_theme("default", &[]).unwrap();
+        Styles::from_theme("default-light", &[]).unwrap();
+        Styles::from_theme("gruvbox", &[]).unwrap();
+        Styles::from_theme("gruvbox-light", &[]).unwrap();
+        Styles::from_theme("nord", &[]).unwrap();
+        Styles::from_theme("nord-light", &[]).unwrap();
     }
 }
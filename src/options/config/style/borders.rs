@@ -1,9 +1,8 @@
 use serde::{Deserialize, Serialize};
 use tui::widgets::BorderType;
 
-#[derive(Default, Clone, Copy, Debug, Serialize)]
+#[derive(Default, Clone, Copy, Debug, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub(crate) enum WidgetBorderType {
     #[default]
     Default,
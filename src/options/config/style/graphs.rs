@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 use super::{ColourStr, TextStyleConfig};
 
 /// General styling for graph widgets.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct GraphStyle {
     /// The general colour of the parts of the graph.
     #[serde(alias = "graph_color")]
@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 use super::ColourStr;
 
 /// Styling specific to the network widget.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct NetworkStyle {
     /// The colour of the RX (download) label and graph line.
     #[serde(alias = "rx_color")]
@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use super::TextStyleConfig;
+use super::{TextStyleConfig, value_colours::ValueColourConfig};
 
 /// General styling for table widgets.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct TableStyle {
     /// Text styling for table headers.
     pub(crate) headers: Option<TextStyleConfig>,
+
+    /// Colours for the values in numeric columns, based on thresholds.
+    #[serde(alias = "value_colors")]
+    pub(crate) value_colours: Option<Vec<ValueColourConfig>>,
 }
@@ -0,0 +1,243 @@
+//! Themes loaded from theme files, and switching between themes at runtime.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use super::{StyleConfig, Styles};
+use crate::options::OptionError;
+
+/// The names of the built-in themes.
+pub(crate) const BUILT_IN_THEMES: [&str; 6] = [
+    "default",
+    "default-light",
+    "gruvbox",
+    "gruvbox-light",
+    "nord",
+    "nord-light",
+];
+
+/// The file extension of theme files.
+const THEME_FILE_EXTENSION: &str = "toml";
+
+/// Return the directories to look for theme files in, from highest to lowest
+/// priority. These are the `themes` directory next to the config file, then
+/// `bottom/themes` in the user's data directory and in `$XDG_DATA_DIRS`.
+pub(crate) fn theme_dirs(config_path: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(config_dir) = config_path.and_then(Path::parent) {
+        dirs.push(config_dir.join("themes"));
+    }
+
+    if let Some(data_dir) = dirs::data_dir() {
+        dirs.push(data_dir.join("bottom").join("themes"));
+    }
+
+    if cfg!(unix) {
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|data_dirs| !data_dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+        for data_dir in data_dirs.split(':').filter(|dir| !dir.is_empty()) {
+            let dir = Path::new(data_dir).join("bottom").join("themes");
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Find the theme file for the theme `name` in `dirs`.
+fn find_theme_file(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .map(|dir| dir.join(format!("{name}.{THEME_FILE_EXTENSION}")))
+        .find(|path| path.is_file())
+}
+
+/// Return the names of all theme files in `dirs`, sorted and without
+/// duplicates. Theme files with the same name as a built-in theme are skipped,
+/// as the built-in theme takes priority.
+pub(crate) fn theme_file_names(dirs: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != THEME_FILE_EXTENSION {
+                return None;
+            }
+
+            let name = path.file_stem()?.to_str()?.to_string();
+            (!BUILT_IN_THEMES.contains(&name.as_str())).then_some(name)
+        })
+        .collect();
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+impl Styles {
+    /// Create the styles for a built-in theme, if `theme` is one.
+    pub(super) fn from_built_in_theme(theme: &str) -> Option<Self> {
+        match theme.to_lowercase().as_str() {
+            "default" => Some(Self::default_palette()),
+            "default-light" => Some(Self::default_light_palette()),
+            "gruvbox" => Some(Self::gruvbox_palette()),
+            "gruvbox-light" => Some(Self::gruvbox_light_palette()),
+            "nord" => Some(Self::nord_palette()),
+            "nord-light" => Some(Self::nord_light_palette()),
+            _ => None,
+        }
+    }
+
+    /// Create the styles from a theme file. If the file sets `theme`, the
+    /// file's styles are applied on top of that built-in theme.
+    fn from_theme_file(path: &Path) -> anyhow::Result<Self> {
+        let theme_config = fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(toml_edit::de::from_str::<StyleConfig>(&text)?))
+            .with_context(|| {
+                format!("bottom could not read the theme file '{}'.", path.display())
+            })?;
+
+        let mut palette = match &theme_config.theme {
+            Some(base) => Self::from_built_in_theme(base).ok_or_else(|| {
+                OptionError::other(format!(
+                    "the theme file '{}' can only inherit from a built-in theme, but '{base}' is not one.",
+                    path.display()
+                ))
+            })?,
+            None => Self::default_palette(),
+        };
+        palette.set_styles_from_config(&theme_config)?;
+
+        Ok(palette)
+    }
+
+    /// Create the styles for `theme`, which is either a built-in theme or the
+    /// name of a theme file in `theme_dirs`.
+    pub(crate) fn from_theme(theme: &str, theme_dirs: &[PathBuf]) -> anyhow::Result<Self> {
+        if let Some(palette) = Self::from_built_in_theme(theme) {
+            return Ok(palette);
+        }
+
+        match find_theme_file(theme, theme_dirs) {
+            Some(path) => Self::from_theme_file(&path),
+            None => Err(OptionError::other(format!(
+                "'{theme}' is not a built-in colour scheme, and no theme file called '{theme}.{THEME_FILE_EXTENSION}' was found."
+            ))
+            .into()),
+        }
+    }
+}
+
+/// The themes that can be cycled through at runtime.
+#[derive(Debug, Default)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub(crate) struct ThemeCycle {
+    names: Vec<String>,
+    current: usize,
+    theme_dirs: Vec<PathBuf>,
+    custom_styles: Option<StyleConfig>,
+}
+
+impl ThemeCycle {
+    /// Create a new [`ThemeCycle`] starting at `current`. `custom_styles` are
+    /// the styles from the config file, which are applied on top of every
+    /// theme.
+    pub(crate) fn new(
+        current: &str, theme_dirs: Vec<PathBuf>, custom_styles: Option<StyleConfig>,
+    ) -> Self {
+        let mut names: Vec<String> = BUILT_IN_THEMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        names.extend(theme_file_names(&theme_dirs));
+
+        let current_lower = current.to_lowercase();
+        let current = match names.iter().position(|name| *name == current_lower) {
+            Some(index) => index,
+            None => {
+                names.push(current.to_string());
+                names.len() - 1
+            }
+        };
+
+        Self {
+            names,
+            current,
+            theme_dirs,
+            custom_styles,
+        }
+    }
+
+    /// The name of the current theme.
+    pub(crate) fn current(&self) -> &str {
+        &self.names[self.current]
+    }
+
+    /// Switch to the next theme and return its styles. If the theme can't be
+    /// loaded, the error is returned, and the next call moves past it.
+    pub(crate) fn next(&mut self) -> anyhow::Result<Styles> {
+        self.current = (self.current + 1) % self.names.len();
+
+        let mut palette = Styles::from_theme(self.current(), &self.theme_dirs)?;
+        if let Some(custom_styles) = &self.custom_styles {
+            palette.set_styles_from_config(custom_styles)?;
+        }
+
+        Ok(palette)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tui::style::Color;
+
+    use super::{ThemeCycle, theme_file_names};
+    use crate::options::config::style::Styles;
+
+    #[test]
+    fn theme_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let themes_dir = dir.path().to_path_buf();
+        fs::write(
+            themes_dir.join("house.toml"),
+            "theme = \"nord\"\n[cpu]\nall_entry_colour = \"red\"\n",
+        )
+        .unwrap();
+        fs::write(themes_dir.join("broken.toml"), "theme = \"missing\"\n").unwrap();
+        fs::write(themes_dir.join("nord.toml"), "").unwrap();
+        fs::write(themes_dir.join("notes.txt"), "").unwrap();
+        let theme_dirs = vec![themes_dir];
+
+        assert_eq!(theme_file_names(&theme_dirs), vec!["broken", "house"]);
+
+        let house = Styles::from_theme("house", &theme_dirs).unwrap();
+        let nord = Styles::nord_palette();
+        assert_eq!(house.all_cpu_colour.fg, Some(Color::Red));
+        assert_eq!(house.ram_style, nord.ram_style);
+
+        assert!(Styles::from_theme("broken", &theme_dirs).is_err());
+        assert!(Styles::from_theme("unknown", &theme_dirs).is_err());
+
+        let mut themes = ThemeCycle::new("nord-light", theme_dirs, None);
+        assert!(themes.next().is_err());
+        assert_eq!(themes.current(), "broken");
+        assert!(themes.next().is_ok());
+        assert_eq!(themes.current(), "house");
+        assert!(themes.next().is_ok());
+        assert_eq!(themes.current(), "default");
+    }
+}
@@ -4,7 +4,7 @@ use tui::{
 };
 
 use super::colour;
-use crate::options::config::style::Styles;
+use crate::options::config::style::{Styles, ValueColours};
 
 impl Styles {
     pub(crate) fn default_palette() -> Self {
@@ -70,7 +70,9 @@ impl Styles {
             low_battery: colour!(Color::Red),
             invalid_query_style: colour!(Color::Red),
             disabled_text_style: colour!(Color::DarkGray),
+            marked_text_style: colour!(Color::Yellow),
             border_type: BorderType::Plain,
+            value_colours: ValueColours::default(),
             #[cfg(target_os = "linux")]
             thread_text_style: colour!(Color::Green),
         }
@@ -118,6 +120,7 @@ impl Styles {
             graph_style: colour!(Color::Black),
             graph_legend_style: colour!(Color::Black),
             disabled_text_style: colour!(Color::Gray),
+            marked_text_style: colour!(Color::Magenta),
             ..Self::default_palette()
         }
     }
@@ -4,7 +4,7 @@ use tui::{
 };
 
 use super::{colour, hex};
-use crate::options::config::style::{Styles, themes::hex_colour};
+use crate::options::config::style::{Styles, ValueColours, themes::hex_colour};
 
 impl Styles {
     pub(crate) fn gruvbox_palette() -> Self {
@@ -70,7 +70,9 @@ impl Styles {
             low_battery: hex!("#fb4934"),
             invalid_query_style: colour!(Color::Red),
             disabled_text_style: hex!("#665c54"),
+            marked_text_style: hex!("#fabd2f"),
             border_type: BorderType::Plain,
+            value_colours: ValueColours::default(),
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#458588"),
         }
@@ -139,7 +141,9 @@ impl Styles {
             low_battery: hex!("#cc241d"),
             invalid_query_style: colour!(Color::Red),
             disabled_text_style: hex!("#d5c4a1"),
+            marked_text_style: hex!("#b57614"),
             border_type: BorderType::Plain,
+            value_colours: ValueColours::default(),
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#458588"),
         }
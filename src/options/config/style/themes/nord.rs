@@ -4,7 +4,7 @@ use tui::{
 };
 
 use super::{colour, hex};
-use crate::options::config::style::{Styles, themes::hex_colour};
+use crate::options::config::style::{Styles, ValueColours, themes::hex_colour};
 
 impl Styles {
     pub(crate) fn nord_palette() -> Self {
@@ -58,7 +58,9 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: colour!(Color::Red),
             disabled_text_style: hex!("#4c566a"),
+            marked_text_style: hex!("#ebcb8b"),
             border_type: BorderType::Plain,
+            value_colours: ValueColours::default(),
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#a3be8c"),
         }
@@ -115,7 +117,9 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: colour!(Color::Red),
             disabled_text_style: hex!("#d8dee9"),
+            marked_text_style: hex!("#d08770"),
             border_type: BorderType::Plain,
+            value_colours: ValueColours::default(),
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#a3be8c"),
         }
@@ -0,0 +1,339 @@
+//! Colouring table cells based on their values.
+
+use serde::{
+    Deserialize, Serialize,
+    de::{IntoDeserializer, value::Error as ValueError},
+};
+use tui::style::{Color, Style};
+
+use super::{ColourStr, utils::str_to_colour};
+use crate::{
+    options::{OptionError, OptionResult},
+    widgets::{DiskWidgetColumn, ProcColumn, TempWidgetColumn},
+};
+
+/// A value and the colour to use for values at or above it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
+pub(crate) struct ValueThreshold {
+    /// The lowest value that uses this colour.
+    pub(crate) value: f64,
+
+    /// A built-in ANSI colour, RGB hex, or RGB colour code.
+    #[serde(alias = "color")]
+    pub(crate) colour: ColourStr,
+}
+
+// Threshold values are never NaN.
+impl Eq for ValueThreshold {}
+
+/// Colours for the values in a numeric column of a widget.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
+pub(crate) struct ValueColourConfig {
+    /// The widget with the column. Supported widgets are "proc", "disk",
+    /// "temp", "battery", and "cpu_heatmap".
+    pub(crate) widget: String,
+
+    /// The name of the column to colour, as used in the widget's `columns`
+    /// setting. For the battery widget, this must be "charge", and for the
+    /// CPU heatmap widget, this must be "usage".
+    pub(crate) column: String,
+
+    /// Whether to blend between the colours of two thresholds instead of
+    /// switching at each threshold. Only RGB and hex colours can be blended.
+    pub(crate) gradient: Option<bool>,
+
+    /// The thresholds, in increasing order of value. Values below the first
+    /// threshold keep the default text colour.
+    pub(crate) thresholds: Vec<ValueThreshold>,
+}
+
+/// The default colours of the CPU heatmap widget, blended from a dim colour for
+/// idle cores to red for busy ones.
+const DEFAULT_CPU_HEATMAP_THRESHOLDS: [(f64, Color); 3] = [
+    (0.0, Color::Rgb(48, 56, 70)),
+    (50.0, Color::Rgb(208, 160, 32)),
+    (100.0, Color::Rgb(224, 48, 48)),
+];
+
+/// Return the colour for `value` from `thresholds`, if there is one. If
+/// `gradient` is set, RGB colours are blended between thresholds.
+fn threshold_colour(thresholds: &[(f64, Color)], gradient: bool, value: f64) -> Option<Color> {
+    let index = thresholds
+        .iter()
+        .rposition(|(threshold, _)| value >= *threshold)?;
+    let (start, start_colour) = thresholds[index];
+
+    if gradient {
+        if let (Color::Rgb(r1, g1, b1), Some(&(end, Color::Rgb(r2, g2, b2)))) =
+            (start_colour, thresholds.get(index + 1))
+        {
+            let ratio = (value - start) / (end - start);
+            let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+
+            return Some(Color::Rgb(blend(r1, r2), blend(g1, g2), blend(b1, b2)));
+        }
+    }
+
+    Some(start_colour)
+}
+
+/// Colours for a range of values.
+#[derive(Debug)]
+struct ColourScale {
+    thresholds: Vec<(f64, Color)>,
+    gradient: bool,
+}
+
+impl ColourScale {
+    fn new(config: &ValueColourConfig) -> OptionResult<Self> {
+        let thresholds = config
+            .thresholds
+            .iter()
+            .map(|threshold| {
+                str_to_colour(&threshold.colour.0)
+                    .map(|colour| (threshold.value, colour))
+                    .map_err(|err| {
+                        OptionError::config(format!(
+                            "Please update 'styles.tables.value_colours' for the '{}' column in your config file. {err}",
+                            config.column
+                        ))
+                    })
+            })
+            .collect::<OptionResult<Vec<_>>>()?;
+
+        if thresholds.is_empty() || thresholds.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(OptionError::config(format!(
+                "the thresholds in 'styles.tables.value_colours' for the '{}' column must be non-empty and in increasing order.",
+                config.column
+            )));
+        }
+
+        Ok(Self {
+            thresholds,
+            gradient: config.gradient.unwrap_or(false),
+        })
+    }
+
+    /// Return the colour for `value`, if there is one.
+    fn colour(&self, value: f64) -> Option<Color> {
+        threshold_colour(&self.thresholds, self.gradient, value)
+    }
+
+    fn style(&self, value: f64) -> Option<Style> {
+        self.colour(value).map(|colour| Style::new().fg(colour))
+    }
+}
+
+/// Colours for the values of numeric columns in widgets.
+#[derive(Debug, Default)]
+pub(crate) struct ValueColours {
+    proc: Vec<(ProcColumn, ColourScale)>,
+    disk: Vec<(DiskWidgetColumn, ColourScale)>,
+    temp: Option<ColourScale>,
+    #[cfg(feature = "battery")]
+    battery: Option<ColourScale>,
+    cpu_heatmap: Option<ColourScale>,
+}
+
+impl ValueColours {
+    pub(super) fn new(configs: &[ValueColourConfig]) -> OptionResult<Self> {
+        let mut value_colours = Self::default();
+
+        for config in configs {
+            let scale = ColourScale::new(config)?;
+            let invalid_column = || {
+                OptionError::config(format!(
+                    "'{}' is not a numeric column of the '{}' widget in 'styles.tables.value_colours'.",
+                    config.column, config.widget
+                ))
+            };
+
+            match config.widget.to_lowercase().as_str() {
+                "proc" | "process" | "processes" => {
+                    let column = ProcColumn::parse_column_name(&config.column)
+                        .filter(|column| {
+                            !matches!(
+                                column,
+                                ProcColumn::Name
+                                    | ProcColumn::Command
+                                    | ProcColumn::State
+                                    | ProcColumn::User
+                            )
+                        })
+                        .ok_or_else(invalid_column)?;
+                    value_colours.proc.push((column, scale));
+                }
+                "disk" => {
+                    let column =
+                        DiskWidgetColumn::deserialize(config.column.as_str().into_deserializer())
+                            .map_err(|_: ValueError| invalid_column())?;
+                    if matches!(column, DiskWidgetColumn::Disk | DiskWidgetColumn::Mount) {
+                        return Err(invalid_column());
+                    }
+                    value_colours.disk.push((column, scale));
+                }
+                "temp" | "temperature" => {
+                    let column: Result<_, ValueError> =
+                        TempWidgetColumn::deserialize(config.column.as_str().into_deserializer());
+                    if !matches!(column, Ok(TempWidgetColumn::Temperature)) {
+                        return Err(invalid_column());
+                    }
+                    value_colours.temp = Some(scale);
+                }
+                #[cfg(feature = "battery")]
+                "battery" | "batt" => {
+                    if !config.column.eq_ignore_ascii_case("charge") {
+                        return Err(invalid_column());
+                    }
+                    value_colours.battery = Some(scale);
+                }
+                "cpu_heatmap" => {
+                    if !config.column.eq_ignore_ascii_case("usage") {
+                        return Err(invalid_column());
+                    }
+                    value_colours.cpu_heatmap = Some(scale);
+                }
+                widget => {
+                    return Err(OptionError::config(format!(
+                        "'{widget}' is not a widget that supports 'styles.tables.value_colours'. Supported widgets are 'proc', 'disk', 'temp', 'battery', and 'cpu_heatmap'."
+                    )));
+                }
+            }
+        }
+
+        Ok(value_colours)
+    }
+
+    /// The style of a process widget cell in `column` with `value`.
+    pub(crate) fn proc_style(&self, column: &ProcColumn, value: f64) -> Option<Style> {
+        let (_, scale) = self.proc.iter().find(|(c, _)| c == column)?;
+        scale.style(value)
+    }
+
+    /// The style of a disk widget cell in `column` with `value`.
+    pub(crate) fn disk_style(&self, column: &DiskWidgetColumn, value: f64) -> Option<Style> {
+        let (_, scale) = self.disk.iter().find(|(c, _)| c == column)?;
+        scale.style(value)
+    }
+
+    /// The style of a temperature widget cell with `value`.
+    pub(crate) fn temp_style(&self, value: f64) -> Option<Style> {
+        self.temp.as_ref()?.style(value)
+    }
+
+    /// The style of a battery charge bar with `value` as the charge percentage.
+    #[cfg(feature = "battery")]
+    pub(crate) fn battery_style(&self, value: f64) -> Option<Style> {
+        self.battery.as_ref()?.style(value)
+    }
+
+    /// The colour of a CPU heatmap cell with `value` as the usage percentage.
+    /// Unlike the other widgets, every value has a colour.
+    pub(crate) fn cpu_heatmap_colour(&self, value: f64) -> Color {
+        let colour = match &self.cpu_heatmap {
+            Some(scale) => scale.colour(value),
+            None => threshold_colour(&DEFAULT_CPU_HEATMAP_THRESHOLDS, true, value),
+        };
+
+        colour.unwrap_or(Color::Reset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::style::Color;
+
+    use super::{ValueColourConfig, ValueColours};
+    use crate::widgets::ProcColumn;
+
+    fn configs(text: &str) -> Vec<ValueColourConfig> {
+        #[derive(serde::Deserialize)]
+        struct Configs {
+            value_colours: Vec<ValueColourConfig>,
+        }
+
+        toml_edit::de::from_str::<Configs>(text)
+            .unwrap()
+            .value_colours
+    }
+
+    #[test]
+    fn thresholds() {
+        let value_colours = ValueColours::new(&configs(
+            r##"
+            [[value_colours]]
+            widget = "proc"
+            column = "CPU%"
+            thresholds = [
+                { value = 0, colour = "green" },
+                { value = 50, colour = "yellow" },
+                { value = 80, colour = "red" },
+            ]
+
+            [[value_colours]]
+            widget = "temp"
+            column = "temp"
+            gradient = true
+            thresholds = [{ value = 40, colour = "#000000" }, { value = 80, colour = "#ff8040" }]
+            "##,
+        ))
+        .unwrap();
+
+        let fg = |style: Option<tui::style::Style>| style.and_then(|style| style.fg);
+        let cpu = |value| fg(value_colours.proc_style(&ProcColumn::CpuPercent, value));
+        assert_eq!(cpu(10.0), Some(Color::Green));
+        assert_eq!(cpu(50.0), Some(Color::Yellow));
+        assert_eq!(cpu(99.0), Some(Color::Red));
+        assert_eq!(fg(value_colours.proc_style(&ProcColumn::Pid, 10.0)), None);
+
+        assert_eq!(fg(value_colours.temp_style(20.0)), None);
+        assert_eq!(
+            fg(value_colours.temp_style(60.0)),
+            Some(Color::Rgb(128, 64, 32))
+        );
+        assert_eq!(
+            fg(value_colours.temp_style(100.0)),
+            Some(Color::Rgb(255, 128, 64))
+        );
+
+        // The CPU heatmap always has a colour, even without any configured.
+        assert_eq!(
+            value_colours.cpu_heatmap_colour(0.0),
+            Color::Rgb(48, 56, 70)
+        );
+        assert_eq!(
+            value_colours.cpu_heatmap_colour(75.0),
+            Color::Rgb(216, 104, 40)
+        );
+    }
+
+    #[test]
+    fn invalid_value_colours() {
+        let invalid = [
+            r#"[[value_colours]]
+            widget = "proc"
+            column = "Name"
+            thresholds = [{ value = 0, colour = "red" }]"#,
+            r#"[[value_colours]]
+            widget = "cpu"
+            column = "CPU%"
+            thresholds = [{ value = 0, colour = "red" }]"#,
+            r#"[[value_colours]]
+            widget = "disk"
+            column = "used%"
+            thresholds = [{ value = 50, colour = "red" }, { value = 10, colour = "green" }]"#,
+            r#"[[value_colours]]
+            widget = "temp"
+            column = "sensor"
+            thresholds = [{ value = 0, colour = "red" }]"#,
+        ];
+
+        for text in invalid {
+            assert!(ValueColours::new(&configs(text)).is_err(), "{text}");
+        }
+    }
+}
@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 use super::{ColourStr, TextStyleConfig, borders::WidgetBorderType};
 
 /// General styling for generic widgets.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct WidgetStyle {
     /// The colour of the widgets' borders.
     #[serde(alias = "border_color")]
@@ -27,6 +27,9 @@ pub(crate) struct WidgetStyle {
     /// Text styling for text when representing something that is disabled.
     pub(crate) disabled_text: Option<TextStyleConfig>,
 
+    /// Text styling for text when representing something that is marked.
+    pub(crate) marked_text: Option<TextStyleConfig>,
+
     /// Text styling for text when representing process threads. Only usable
     /// on Linux at the moment.
     pub(crate) thread_text: Option<TextStyleConfig>,
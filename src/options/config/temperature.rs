@@ -1,17 +1,18 @@
 use serde::Deserialize;
 
-use super::IgnoreList;
+use super::{IgnoreList, OneOrMany};
 use crate::widgets::TempWidgetColumn;
 
 /// Temperature configuration.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct TempConfig {
     /// A filter over the sensor names.
     pub(crate) sensor_filter: Option<IgnoreList>,
 
-    /// The default sort column.
+    /// The default sort column, or a list of columns to sort by in order of
+    /// priority.
     #[serde(default)]
-    pub(crate) default_sort: Option<TempWidgetColumn>,
+    pub(crate) default_sort: Option<OneOrMany<TempWidgetColumn>>,
 }
@@ -3,9 +3,9 @@ use serde::Deserialize;
 use super::IgnoreList;
 
 /// Temperature graph configuration.
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
+#[cfg_attr(test, serde(deny_unknown_fields))]
 pub(crate) struct TempGraphConfig {
     /// A filter over the sensor names.
     pub(crate) sensor_filter: Option<IgnoreList>,
@@ -14,6 +14,10 @@ pub(crate) struct TempGraphConfig {
     #[serde(default)]
     pub(crate) legend_position: Option<String>,
 
+    /// Whether to show the min, average, max and 95th percentile of each entry over the visible
+    /// time range in the legend.
+    pub(crate) legend_stats: Option<bool>,
+
     /// An upper temperature value for the graph; entries higher than this will be hidden. If not set,
     /// there is no limit.
     ///
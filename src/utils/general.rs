@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, time::Duration};
 
 #[inline]
 pub(crate) const fn sort_partial_fn<T: PartialOrd>(is_descending: bool) -> fn(T, T) -> Ordering {
@@ -72,6 +72,66 @@ pub(crate) fn saturating_log10(value: f64) -> f64 {
     if value > 0.0 { value.log10() } else { 0.0 }
 }
 
+/// Format how long ago something happened, at a precision that fits the age.
+pub(crate) fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 60 * 60 {
+        format!("{}m {}s ago", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m ago", secs / 3600, secs / 60 % 60)
+    }
+}
+
+/// Format a sorted list of CPUs as ranges, like `0-3,8,10-11`.
+#[cfg(target_os = "linux")]
+pub(crate) fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Bail with the error from the last failed call, which tried to `action` a
+/// process.
+#[cfg(unix)]
+pub(crate) fn last_error(action: &str) -> anyhow::Result<()> {
+    let err_code = std::io::Error::last_os_error().raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.".to_string(),
+        Some(libc::EPERM) => format!(
+            "the calling process does not have the permissions to {action} the target process(es)."
+        ),
+        Some(libc::EACCES) => {
+            format!("elevated permissions are required to {action} the target process(es).")
+        }
+        Some(libc::EINVAL) => "an invalid value was specified.".to_string(),
+        _ => "Unknown error occurred.".to_string(),
+    };
+
+    if let Some(err_code) = err_code {
+        anyhow::bail!(format!("Error code {err_code} - {err}"))
+    } else {
+        anyhow::bail!(format!("Error code unknown - {err}"))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -117,4 +177,19 @@ mod test {
         y.sort_by(|a, b| sort_partial_fn(true)(a, b));
         assert_eq!(y, vec![16.15, 15.0, 1.0, -1.0, -100.0, -100.0, -100.1]);
     }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(5)), "5s ago");
+        assert_eq!(format_age(Duration::from_secs(65)), "1m 5s ago");
+        assert_eq!(format_age(Duration::from_secs(3720)), "1h 2m ago");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_format_cpu_list() {
+        assert_eq!(format_cpu_list(&[]), "");
+        assert_eq!(format_cpu_list(&[2]), "2");
+        assert_eq!(format_cpu_list(&[0, 1, 2, 3, 8, 10, 11]), "0-3,8,10-11");
+    }
 }
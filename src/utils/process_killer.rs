@@ -1,6 +1,7 @@
 //! This file is meant to house (OS specific) implementations on how to kill
 //! processes.
 
+#[cfg(target_os = "windows")]
 use anyhow::bail;
 #[cfg(target_os = "windows")]
 use windows::Win32::{
@@ -11,6 +12,8 @@ use windows::Win32::{
 };
 
 use crate::collection::processes::Pid;
+#[cfg(unix)]
+use crate::utils::general::last_error;
 
 /// Based from [this SO answer](https://stackoverflow.com/a/55231715).
 #[cfg(target_os = "windows")]
@@ -64,23 +67,48 @@ pub fn kill_process_given_pid(pid: Pid, signal: usize) -> anyhow::Result<()> {
     let output = unsafe { libc::kill(pid, signal as i32) };
 
     if output != 0 {
-        // We had an error...
-        let err_code = std::io::Error::last_os_error().raw_os_error();
-        let err = match err_code {
-            Some(libc::ESRCH) => "the target process did not exist.",
-            Some(libc::EPERM) => {
-                "the calling process does not have the permissions to terminate the target process(es)."
-            }
-            Some(libc::EINVAL) => "an invalid signal was specified.",
-            _ => "Unknown error occurred.",
-        };
-
-        if let Some(err_code) = err_code {
-            bail!(format!("Error code {err_code} - {err}"))
-        } else {
-            bail!(format!("Error code unknown - {err}"))
-        };
+        return last_error("signal");
+    }
+
+    Ok(())
+}
+
+/// Sends a signal to every process in a process group, given its ID.
+#[cfg(unix)]
+pub fn kill_process_group(pgid: Pid, signal: usize) -> anyhow::Result<()> {
+    // SAFETY: the signal should be valid, and we act properly on an error (exit
+    // code not 0).
+    let output = unsafe { libc::killpg(pgid, signal as i32) };
+
+    if output != 0 {
+        return last_error("signal");
     }
 
     Ok(())
 }
+
+/// Whether a process still exists, given a PID. A process we are not allowed
+/// to signal still exists.
+#[cfg(unix)]
+pub fn is_process_alive(pid: Pid) -> bool {
+    // SAFETY: a signal of 0 only checks whether the process can be signalled.
+    let output = unsafe { libc::kill(pid, 0) };
+
+    output == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Gets the process group ID of a process, given a PID.
+#[cfg(unix)]
+pub fn get_process_group(pid: Pid) -> Option<Pid> {
+    // SAFETY: getpgid takes no pointers.
+    let pgid = unsafe { libc::getpgid(pid) };
+    (pgid >= 0).then_some(pgid)
+}
+
+/// Gets the session ID of a process, given a PID.
+#[cfg(unix)]
+pub fn get_session(pid: Pid) -> Option<Pid> {
+    // SAFETY: getsid takes no pointers.
+    let sid = unsafe { libc::getsid(pid) };
+    (sid >= 0).then_some(sid)
+}
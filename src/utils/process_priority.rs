@@ -0,0 +1,226 @@
+//! This file is meant to house (OS specific) implementations on how to change
+//! the priority and scheduling of processes.
+
+#[cfg(unix)]
+use crate::{collection::processes::Pid, utils::general::last_error};
+
+/// An I/O scheduling class, see `ioprio_set(2)`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoClass {
+    None = 0,
+    Realtime = 1,
+    BestEffort = 2,
+    Idle = 3,
+}
+
+#[cfg(target_os = "linux")]
+impl IoClass {
+    pub const ALL: [IoClass; 4] = [
+        IoClass::None,
+        IoClass::Realtime,
+        IoClass::BestEffort,
+        IoClass::Idle,
+    ];
+
+    /// Return the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IoClass::None => "None",
+            IoClass::Realtime => "Realtime",
+            IoClass::BestEffort => "Best effort",
+            IoClass::Idle => "Idle",
+        }
+    }
+}
+
+/// A scheduling policy, see `sched(7)`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchedPolicy {
+    Other,
+    Batch,
+    Idle,
+    Fifo,
+    RoundRobin,
+}
+
+#[cfg(target_os = "linux")]
+impl SchedPolicy {
+    pub const ALL: [SchedPolicy; 5] = [
+        SchedPolicy::Other,
+        SchedPolicy::Batch,
+        SchedPolicy::Idle,
+        SchedPolicy::Fifo,
+        SchedPolicy::RoundRobin,
+    ];
+
+    /// Return the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchedPolicy::Other => "OTHER",
+            SchedPolicy::Batch => "BATCH",
+            SchedPolicy::Idle => "IDLE",
+            SchedPolicy::Fifo => "FIFO",
+            SchedPolicy::RoundRobin => "RR",
+        }
+    }
+
+    /// Whether this is a real-time policy, which uses a real-time priority.
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, SchedPolicy::Fifo | SchedPolicy::RoundRobin)
+    }
+
+    fn to_libc(self) -> libc::c_int {
+        match self {
+            SchedPolicy::Other => libc::SCHED_OTHER,
+            SchedPolicy::Batch => libc::SCHED_BATCH,
+            SchedPolicy::Idle => libc::SCHED_IDLE,
+            SchedPolicy::Fifo => libc::SCHED_FIFO,
+            SchedPolicy::RoundRobin => libc::SCHED_RR,
+        }
+    }
+
+    fn from_libc(policy: libc::c_int) -> Option<Self> {
+        SchedPolicy::ALL
+            .into_iter()
+            .find(|known| known.to_libc() == policy)
+    }
+}
+
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// Sets the nice value of a process, given a PID.
+#[cfg(unix)]
+pub fn set_nice(pid: Pid, nice: i32) -> anyhow::Result<()> {
+    // SAFETY: This only reads its arguments, and we act properly on an error
+    // (return value not 0).
+    let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+
+    if output != 0 {
+        return last_error("change");
+    }
+
+    Ok(())
+}
+
+/// Gets the I/O scheduling class and level of a process, given a PID.
+#[cfg(target_os = "linux")]
+pub fn get_io_priority(pid: Pid) -> Option<(IoClass, u8)> {
+    // SAFETY: ioprio_get only reads its arguments.
+    let output = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid) };
+    if output < 0 {
+        return None;
+    }
+
+    let class = IoClass::ALL
+        .into_iter()
+        .find(|class| *class as libc::c_long == output >> IOPRIO_CLASS_SHIFT)?;
+    Some((class, (output & 0xff) as u8))
+}
+
+/// Sets the I/O scheduling class and level of a process, given a PID.
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: Pid, class: IoClass, level: u8) -> anyhow::Result<()> {
+    let ioprio = (class as libc::c_int) << IOPRIO_CLASS_SHIFT | libc::c_int::from(level);
+
+    // SAFETY: ioprio_set only reads its arguments, and we act properly on an
+    // error (return value not 0).
+    let output = unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid, ioprio) };
+
+    if output != 0 {
+        return last_error("change");
+    }
+
+    Ok(())
+}
+
+/// Gets the scheduling policy and real-time priority of a process, given a PID.
+#[cfg(target_os = "linux")]
+pub fn get_scheduler(pid: Pid) -> Option<(SchedPolicy, i32)> {
+    // SAFETY: This only reads its arguments.
+    let policy = unsafe { libc::sched_getscheduler(pid) };
+
+    // SAFETY: A zeroed `sched_param` is valid, and it is only written to.
+    let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+    // SAFETY: `param` is a valid pointer to write to.
+    if policy < 0 || unsafe { libc::sched_getparam(pid, &mut param) } != 0 {
+        return None;
+    }
+
+    let policy = SchedPolicy::from_libc(policy & !libc::SCHED_RESET_ON_FORK)?;
+    Some((policy, param.sched_priority))
+}
+
+/// Sets the scheduling policy and real-time priority of a process, given a PID.
+/// The priority must be 0 for non-real-time policies.
+#[cfg(target_os = "linux")]
+pub fn set_scheduler(pid: Pid, policy: SchedPolicy, priority: i32) -> anyhow::Result<()> {
+    // SAFETY: A zeroed `sched_param` is valid.
+    let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+    param.sched_priority = priority;
+
+    // SAFETY: `param` is a valid pointer that is only read from, and we act
+    // properly on an error (return value not 0).
+    let output = unsafe { libc::sched_setscheduler(pid, policy.to_libc(), &param) };
+
+    if output != 0 {
+        return last_error("change");
+    }
+
+    Ok(())
+}
+
+/// Gets the number of CPUs that can be used in an affinity.
+#[cfg(target_os = "linux")]
+pub fn num_cpus() -> usize {
+    // SAFETY: sysconf takes no pointers.
+    let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    usize::try_from(num_cpus).unwrap_or(1).max(1)
+}
+
+/// Gets the CPUs a process is allowed to run on, given a PID.
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: Pid) -> Option<Vec<usize>> {
+    // SAFETY: An empty `cpu_set_t` is valid.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+
+    // SAFETY: `set` is a valid pointer to write to, of the given size.
+    let output =
+        unsafe { libc::sched_getaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+    if output != 0 {
+        return None;
+    }
+
+    Some(
+        (0..libc::CPU_SETSIZE as usize)
+            // SAFETY: The CPU is within the bounds of the set.
+            .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+            .collect(),
+    )
+}
+
+/// Sets the CPUs a process is allowed to run on, given a PID.
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: Pid, cpus: &[usize]) -> anyhow::Result<()> {
+    // SAFETY: An empty `cpu_set_t` is valid.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus.iter().filter(|cpu| **cpu < libc::CPU_SETSIZE as usize) {
+        // SAFETY: The CPU is within the bounds of the set.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+
+    // SAFETY: `set` is a valid pointer that is only read from, and we act
+    // properly on an error (return value not 0).
+    let output =
+        unsafe { libc::sched_setaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+
+    if output != 0 {
+        return last_error("change");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Code around the CPU heatmap widget, which draws every core as a cell in a
+//! grid.
+
+use std::collections::BTreeMap;
+
+use tui::layout::Rect;
+
+use crate::{
+    collection::cpu::topology::{CpuLocation, get_cpu_locations},
+    options::config::cpu::CpuHeatmapGrouping,
+};
+
+/// A group of cores, drawn together under a label.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CoreGroup {
+    /// The label of the group. This is `None` if there's only one group.
+    pub label: Option<String>,
+    pub cores: Vec<usize>,
+}
+
+/// Where a core was drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct HeatmapCell {
+    pub core: usize,
+    pub row: usize,
+    pub column: usize,
+    pub area: Rect,
+}
+
+pub struct CpuHeatmapWidgetState {
+    pub(crate) grouping: CpuHeatmapGrouping,
+    pub locations: Vec<CpuLocation>,
+
+    /// The core selected with the keyboard or a click.
+    pub selected: Option<usize>,
+
+    /// The core under the mouse cursor.
+    pub hovered: Option<usize>,
+
+    /// The cells from the last draw, in display order.
+    pub cells: Vec<HeatmapCell>,
+}
+
+impl CpuHeatmapWidgetState {
+    pub(crate) fn new(grouping: CpuHeatmapGrouping) -> Self {
+        let locations = match grouping {
+            CpuHeatmapGrouping::None => Vec::new(),
+            CpuHeatmapGrouping::Socket | CpuHeatmapGrouping::Numa => get_cpu_locations(),
+        };
+
+        CpuHeatmapWidgetState {
+            grouping,
+            locations,
+            selected: None,
+            hovered: None,
+            cells: Vec::new(),
+        }
+    }
+
+    /// Split `cores` into groups based on the grouping setting. Cores with an
+    /// unknown location are put in a group at the end.
+    pub fn groups(&self, cores: impl Iterator<Item = usize>) -> Vec<CoreGroup> {
+        let mut groups: BTreeMap<(bool, usize), Vec<usize>> = BTreeMap::new();
+        for core in cores {
+            let location = self.locations.get(core).copied().unwrap_or_default();
+            let key = match self.grouping {
+                CpuHeatmapGrouping::None => None,
+                CpuHeatmapGrouping::Socket => location.socket,
+                CpuHeatmapGrouping::Numa => location.numa_node,
+            };
+            groups
+                .entry((key.is_none(), key.unwrap_or_default()))
+                .or_default()
+                .push(core);
+        }
+
+        let is_labelled = groups.len() > 1;
+        groups
+            .into_iter()
+            .map(|((is_unknown, id), cores)| {
+                let label = is_labelled.then(|| match (is_unknown, self.grouping) {
+                    (true, _) | (false, CpuHeatmapGrouping::None) => "Unknown".to_string(),
+                    (false, CpuHeatmapGrouping::Socket) => format!("Socket {id}"),
+                    (false, CpuHeatmapGrouping::Numa) => format!("NUMA node {id}"),
+                });
+                CoreGroup { label, cores }
+            })
+            .collect()
+    }
+
+    /// The core that details are shown for: the hovered core if there is one,
+    /// otherwise the selected core.
+    pub fn focused_core(&self) -> Option<usize> {
+        self.hovered.or(self.selected)
+    }
+
+    fn selected_cell(&self) -> Option<&HeatmapCell> {
+        let selected = self.selected?;
+        self.cells.iter().find(|cell| cell.core == selected)
+    }
+
+    /// Move the selection by `amount` cells in display order.
+    pub fn move_selection(&mut self, amount: i64) {
+        let Some(position) = self
+            .selected
+            .and_then(|selected| self.cells.iter().position(|cell| cell.core == selected))
+        else {
+            self.select_first();
+            return;
+        };
+
+        let new_position = (position as i64 + amount).clamp(0, self.cells.len() as i64 - 1);
+        self.selected = Some(self.cells[new_position as usize].core);
+    }
+
+    /// Move the selection by `amount` rows, to the closest cell in that row.
+    pub fn move_selection_rows(&mut self, amount: i64) {
+        let Some(&HeatmapCell { row, column, .. }) = self.selected_cell() else {
+            self.select_first();
+            return;
+        };
+
+        let last_row = self.cells.last().map(|cell| cell.row).unwrap_or_default();
+        let new_row = (row as i64 + amount).clamp(0, last_row as i64) as usize;
+        if let Some(cell) = self
+            .cells
+            .iter()
+            .filter(|cell| cell.row == new_row)
+            .min_by_key(|cell| cell.column.abs_diff(column))
+        {
+            self.selected = Some(cell.core);
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = self.cells.first().map(|cell| cell.core);
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.cells.last().map(|cell| cell.core);
+    }
+
+    /// Return the core drawn at (`x`, `y`), if any.
+    pub fn core_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.cells
+            .iter()
+            .find(|cell| {
+                let area = cell.area;
+                x >= area.x && x < area.right() && y >= area.y && y < area.bottom()
+            })
+            .map(|cell| cell.core)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::layout::Rect;
+
+    use super::{CoreGroup, CpuHeatmapWidgetState, HeatmapCell};
+    use crate::{collection::cpu::topology::CpuLocation, options::config::cpu::CpuHeatmapGrouping};
+
+    fn state(grouping: CpuHeatmapGrouping) -> CpuHeatmapWidgetState {
+        let mut state = CpuHeatmapWidgetState::new(CpuHeatmapGrouping::None);
+        state.grouping = grouping;
+        state.locations = vec![
+            CpuLocation {
+                socket: Some(1),
+                numa_node: Some(0),
+            },
+            CpuLocation {
+                socket: Some(0),
+                numa_node: Some(0),
+            },
+            CpuLocation {
+                socket: Some(1),
+                numa_node: Some(0),
+            },
+        ];
+        state
+    }
+
+    #[test]
+    fn groups() {
+        let cores = || 0..4;
+
+        assert_eq!(
+            state(CpuHeatmapGrouping::None).groups(cores()),
+            vec![CoreGroup {
+                label: None,
+                cores: vec![0, 1, 2, 3]
+            }]
+        );
+        assert_eq!(
+            state(CpuHeatmapGrouping::Socket).groups(cores()),
+            vec![
+                CoreGroup {
+                    label: Some("Socket 0".to_string()),
+                    cores: vec![1]
+                },
+                CoreGroup {
+                    label: Some("Socket 1".to_string()),
+                    cores: vec![0, 2]
+                },
+                CoreGroup {
+                    label: Some("Unknown".to_string()),
+                    cores: vec![3]
+                },
+            ]
+        );
+        assert_eq!(
+            state(CpuHeatmapGrouping::Numa).groups(0..3),
+            vec![CoreGroup {
+                label: None,
+                cores: vec![0, 1, 2]
+            }]
+        );
+    }
+
+    #[test]
+    fn selection() {
+        let mut state = state(CpuHeatmapGrouping::None);
+        // Two rows of three cells, with a shorter third row.
+        state.cells = (0..7)
+            .map(|core| HeatmapCell {
+                core,
+                row: core / 3,
+                column: core % 3,
+                area: Rect::new(core as u16 % 3 * 4, core as u16 / 3, 3, 1),
+            })
+            .collect();
+
+        state.move_selection(1);
+        assert_eq!(state.selected, Some(0));
+        state.move_selection(-1);
+        assert_eq!(state.selected, Some(0));
+        state.move_selection(2);
+        assert_eq!(state.selected, Some(2));
+
+        state.move_selection_rows(1);
+        assert_eq!(state.selected, Some(5));
+        state.move_selection_rows(1);
+        assert_eq!(state.selected, Some(6));
+        state.move_selection_rows(-5);
+        assert_eq!(state.selected, Some(0));
+
+        state.select_last();
+        assert_eq!(state.selected, Some(6));
+
+        assert_eq!(state.core_at(5, 1), Some(4));
+        assert_eq!(state.core_at(3, 1), None);
+    }
+}
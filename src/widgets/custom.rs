@@ -0,0 +1,47 @@
+//! Code around a custom metric graph widget.
+
+use std::time::Instant;
+
+use tui::style::Style;
+
+use crate::{
+    canvas::components::time_series::LegendPosition,
+    components::time_series::{AutoYAxisTimeGraph, TimeseriesConfig},
+};
+
+/// How to display a single series of a custom source.
+#[derive(Clone, Debug)]
+pub struct CustomSeries {
+    pub key: String,
+    pub name: String,
+    pub unit: String,
+    pub style: Option<Style>,
+}
+
+/// A time series graph widget displaying values from a custom source over time.
+pub struct CustomWidgetState {
+    pub graph: AutoYAxisTimeGraph,
+    pub source: String,
+    pub title: String,
+    pub max: Option<f64>,
+    pub legend_position: Option<LegendPosition>,
+
+    /// The series to show. If empty, every series from the source is shown.
+    pub series: Vec<CustomSeries>,
+}
+
+impl CustomWidgetState {
+    pub fn new(
+        config: TimeseriesConfig, autohide_timer: Option<Instant>, source: String, title: String,
+        max: Option<f64>, legend_position: Option<LegendPosition>, series: Vec<CustomSeries>,
+    ) -> Self {
+        CustomWidgetState {
+            graph: AutoYAxisTimeGraph::new(config, autohide_timer),
+            source,
+            title,
+            max,
+            legend_position,
+            series,
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use std::{borrow::Cow, cmp::max, num::NonZeroU16};
 
+use itertools::Itertools;
 use serde::Deserialize;
 
 use crate::{
     app::{AppConfigFields, data::StoredData},
-    canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    canvas::{
+        Painter,
+        components::data_table::{
+            ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+            SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        },
     },
     options::config::style::Styles,
     utils::{
@@ -199,6 +203,23 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
         Some(text)
     }
 
+    fn style_cell(
+        &self, column: &DiskWidgetColumn, painter: &Painter,
+    ) -> Option<tui::style::Style> {
+        let value = match column {
+            DiskWidgetColumn::Disk | DiskWidgetColumn::Mount => None,
+            DiskWidgetColumn::Used => self.used_bytes.map(|bytes| bytes as f64),
+            DiskWidgetColumn::Free => self.free_bytes.map(|bytes| bytes as f64),
+            DiskWidgetColumn::Total => self.total_bytes.map(|bytes| bytes as f64),
+            DiskWidgetColumn::UsedPercent => self.used_percent(),
+            DiskWidgetColumn::FreePercent => self.free_percent(),
+            DiskWidgetColumn::IoRead => self.io_read_rate_bytes.map(|bytes| bytes as f64),
+            DiskWidgetColumn::IoWrite => self.io_write_rate_bytes.map(|bytes| bytes as f64),
+        }?;
+
+        painter.styles.value_colours.disk_style(column, value)
+    }
+
     fn column_widths<C: DataTableColumn<DiskWidgetColumn>>(
         data: &[Self], _columns: &[C],
     ) -> Vec<u16>
@@ -319,6 +340,18 @@ impl DiskTableWidget {
     pub fn new(
         config: &AppConfigFields, palette: &Styles, columns: Option<&[DiskWidgetColumn]>,
     ) -> Self {
+        // Must check that the columns to sort by exist. If none do, fall back to 0.
+        let existing_columns = match columns {
+            Some(c) => c,
+            None => &default_disk_column_list(),
+        };
+        let sort_indices = config
+            .default_disk_sort_columns
+            .iter()
+            .filter_map(|column| existing_columns.iter().position(|c| c == column))
+            .unique()
+            .collect::<Vec<_>>();
+
         let props = SortDataTableProps {
             inner: DataTableProps {
                 title: Some(" Disks ".into()),
@@ -329,39 +362,28 @@ impl DiskTableWidget {
                 show_table_scroll_bar: config.show_table_scroll_bar,
                 show_current_entry_when_unfocused: false,
             },
-            sort_index: match &config.default_disk_sort_column {
-                Some(column) => {
-                    // Must check that the column used exists. If not, fall back to 0.
-
-                    let existing_columns = match columns {
-                        Some(c) => c,
-                        None => &default_disk_column_list(),
-                    };
-
-                    existing_columns
-                        .iter()
-                        .position(|c| c == column)
-                        .unwrap_or_default()
-                }
-                None => 0,
-            },
+            sort_index: sort_indices.first().copied().unwrap_or_default(),
             order: SortOrder::Ascending,
         };
 
         let styling = DataTableStyling::from_palette(palette);
 
-        match columns {
+        let mut table = match columns {
             Some(columns) => {
                 let columns = columns.iter().map(create_column).collect::<Vec<_>>();
-                Self {
-                    table: SortDataTable::new_sortable(columns, props, styling),
-                    force_update_data: false,
-                }
+                SortDataTable::new_sortable(columns, props, styling)
             }
-            None => Self {
-                table: SortDataTable::new_sortable(default_disk_columns(), props, styling),
-                force_update_data: false,
-            },
+            None => SortDataTable::new_sortable(default_disk_columns(), props, styling),
+        };
+
+        // Ties are broken by any other columns, in their default order.
+        for &index in sort_indices.iter().skip(1) {
+            table.add_sort_index(index);
+        }
+
+        Self {
+            table,
+            force_update_data: false,
         }
     }
 
@@ -375,9 +397,7 @@ impl DiskTableWidget {
     pub fn set_table_data(&mut self, data: &StoredData) {
         let mut data = data.disk_harvest.clone();
 
-        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            column.sort_by(&mut data, self.table.order());
-        }
+        self.table.sort_rows(&mut data);
         self.table.set_data(data);
         self.force_update_data = false;
     }
@@ -1,5 +1,7 @@
 pub mod battery_info;
 pub mod cpu_graph;
+pub mod cpu_heatmap;
+pub mod custom;
 pub mod disk_table;
 pub mod mem_graph;
 pub mod network_graph;
@@ -9,6 +11,8 @@ pub mod temperature_table;
 
 pub use battery_info::*;
 pub use cpu_graph::*;
+pub use cpu_heatmap::*;
+pub use custom::*;
 pub use disk_table::*;
 pub use mem_graph::*;
 pub use network_graph::*;
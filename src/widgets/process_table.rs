@@ -3,7 +3,7 @@ pub mod process_data;
 pub mod query;
 mod sort_table;
 
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{borrow::Cow, collections::BTreeMap, mem, time::Instant};
 
 use indexmap::IndexSet;
 use itertools::Itertools;
@@ -16,15 +16,15 @@ use sort_table::SortTableColumn;
 use crate::{
     app::{
         AppConfigFields, AppSearchState,
-        data::{ProcessData, StoredData},
+        data::{ProcessData, StoredData, WatchedProcess},
     },
     canvas::components::data_table::{
         Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
-        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder,
     },
     collection::processes::{Pid, ProcessHarvest},
-    options::config::style::Styles,
-    utils::int_hash::IntHashMap,
+    options::config::{process::ProcessGroupKey, style::Styles},
+    utils::int_hash::{IntHashMap, IntHashSet},
     widgets::query::QueryOptions,
 };
 
@@ -158,23 +158,30 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         Priority => SortColumn::new(Priority).default_descending(),
         #[cfg(unix)]
         Nice => SortColumn::new(Nice),
+        #[cfg(target_os = "linux")]
+        Affinity => SortColumn::soft(Affinity, Some(0.1)),
+        #[cfg(target_os = "linux")]
+        LastCpu => SortColumn::new(LastCpu),
         #[cfg(feature = "gpu")]
         GpuMemValue => SortColumn::new(GpuMemValue).default_descending(),
         #[cfg(feature = "gpu")]
         GpuMemPercent => SortColumn::new(GpuMemPercent).default_descending(),
         #[cfg(feature = "gpu")]
         GpuUtilPercent => SortColumn::new(GpuUtilPercent).default_descending(),
+        Exited => SortColumn::new(Exited).default_descending(),
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct ProcTableConfig {
     pub is_case_sensitive: bool,
     pub is_match_whole_word: bool,
     pub is_use_regex: bool,
     pub show_memory_as_values: bool,
     pub is_command: bool,
-    pub default_sort: Option<ProcColumn>,
+    /// The columns to sort by, in order of priority.
+    pub default_sort: Vec<ProcColumn>,
+    pub(crate) group_key: ProcessGroupKey,
 }
 
 /// A hacky workaround for now.
@@ -195,14 +202,302 @@ pub enum ProcWidgetColumn {
     Priority,
     #[cfg(unix)]
     Nice,
+    #[cfg(target_os = "linux")]
+    Affinity,
+    #[cfg(target_os = "linux")]
+    LastCpu,
     #[cfg(feature = "gpu")]
     GpuMem,
     #[cfg(feature = "gpu")]
     GpuUtil,
+    Exited,
+}
+
+impl ProcWidgetColumn {
+    /// Every column that can be chosen to be shown, in the default order.
+    pub const CHOOSABLE: &[ProcWidgetColumn] = &[
+        ProcWidgetColumn::PidOrCount,
+        ProcWidgetColumn::ProcNameOrCommand,
+        ProcWidgetColumn::Cpu,
+        ProcWidgetColumn::Mem,
+        ProcWidgetColumn::VirtualMem,
+        ProcWidgetColumn::ReadPerSecond,
+        ProcWidgetColumn::WritePerSecond,
+        ProcWidgetColumn::TotalRead,
+        ProcWidgetColumn::TotalWrite,
+        ProcWidgetColumn::User,
+        ProcWidgetColumn::State,
+        ProcWidgetColumn::Time,
+        ProcWidgetColumn::Priority,
+        #[cfg(unix)]
+        ProcWidgetColumn::Nice,
+        #[cfg(target_os = "linux")]
+        ProcWidgetColumn::Affinity,
+        #[cfg(target_os = "linux")]
+        ProcWidgetColumn::LastCpu,
+        #[cfg(feature = "gpu")]
+        ProcWidgetColumn::GpuMem,
+        #[cfg(feature = "gpu")]
+        ProcWidgetColumn::GpuUtil,
+    ];
+
+    /// A readable name for the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcWidgetColumn::PidOrCount => "PID/Count",
+            ProcWidgetColumn::ProcNameOrCommand => "Name/Command",
+            ProcWidgetColumn::Cpu => "CPU%",
+            ProcWidgetColumn::Mem => "Memory",
+            ProcWidgetColumn::VirtualMem => "Virtual memory",
+            ProcWidgetColumn::ReadPerSecond => "Read/s",
+            ProcWidgetColumn::WritePerSecond => "Write/s",
+            ProcWidgetColumn::TotalRead => "Total read",
+            ProcWidgetColumn::TotalWrite => "Total write",
+            ProcWidgetColumn::User => "User",
+            ProcWidgetColumn::State => "State",
+            ProcWidgetColumn::Time => "Time",
+            ProcWidgetColumn::Priority => "Priority",
+            #[cfg(unix)]
+            ProcWidgetColumn::Nice => "Nice",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Affinity => "Affinity",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::LastCpu => "Last CPU",
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuMem => "GPU memory",
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuUtil => "GPU%",
+            ProcWidgetColumn::Exited => "Exited",
+        }
+    }
+
+    /// The name of the column in the `processes.columns` config setting.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            ProcWidgetColumn::PidOrCount => "pid",
+            ProcWidgetColumn::ProcNameOrCommand => "name",
+            ProcWidgetColumn::Cpu => "cpu%",
+            ProcWidgetColumn::Mem => "mem",
+            ProcWidgetColumn::VirtualMem => "virt",
+            ProcWidgetColumn::ReadPerSecond => "read",
+            ProcWidgetColumn::WritePerSecond => "write",
+            ProcWidgetColumn::TotalRead => "tread",
+            ProcWidgetColumn::TotalWrite => "twrite",
+            ProcWidgetColumn::User => "user",
+            ProcWidgetColumn::State => "state",
+            ProcWidgetColumn::Time => "time",
+            ProcWidgetColumn::Priority => "priority",
+            #[cfg(unix)]
+            ProcWidgetColumn::Nice => "nice",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Affinity => "affinity",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::LastCpu => "last cpu",
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuMem => "gmem",
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuUtil => "gpu%",
+            ProcWidgetColumn::Exited => "exited",
+        }
+    }
+
+    /// The [`ProcColumn`] to show for this column, depending on whether
+    /// processes are grouped, the command is shown, and memory is shown as
+    /// values.
+    fn proc_column(&self, is_count: bool, is_command: bool, mem_as_values: bool) -> ProcColumn {
+        use ProcColumn::*;
+
+        match self {
+            ProcWidgetColumn::PidOrCount => {
+                if is_count {
+                    Count
+                } else {
+                    Pid
+                }
+            }
+            ProcWidgetColumn::ProcNameOrCommand => {
+                if is_command {
+                    Command
+                } else {
+                    Name
+                }
+            }
+            ProcWidgetColumn::Cpu => CpuPercent,
+            ProcWidgetColumn::Mem => {
+                if mem_as_values {
+                    MemValue
+                } else {
+                    MemPercent
+                }
+            }
+            ProcWidgetColumn::VirtualMem => VirtualMem,
+            ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
+            ProcWidgetColumn::WritePerSecond => WritePerSecond,
+            ProcWidgetColumn::TotalRead => TotalRead,
+            ProcWidgetColumn::TotalWrite => TotalWrite,
+            ProcWidgetColumn::User => User,
+            ProcWidgetColumn::State => State,
+            ProcWidgetColumn::Time => Time,
+            ProcWidgetColumn::Priority => Priority,
+            #[cfg(unix)]
+            ProcWidgetColumn::Nice => Nice,
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Affinity => Affinity,
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::LastCpu => LastCpu,
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuMem => {
+                if mem_as_values {
+                    GpuMemValue
+                } else {
+                    GpuMemPercent
+                }
+            }
+            #[cfg(feature = "gpu")]
+            ProcWidgetColumn::GpuUtil => GpuUtilPercent,
+            ProcWidgetColumn::Exited => Exited,
+        }
+    }
 }
 
 // This is temporary. Switch back to `ProcColumn` later!
 
+/// The processes that an action, like killing, applies to.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessTargets {
+    /// The name and PID of each process.
+    pub processes: Vec<(String, Pid)>,
+    /// Whether these are the marked processes, rather than the selected entry.
+    pub is_marked: bool,
+    /// If set, only the first this many processes were chosen, and the rest
+    /// are their descendants.
+    pub tree_roots: Option<usize>,
+}
+
+impl ProcessTargets {
+    /// How many names of marked processes to list before summarizing the rest.
+    const MAX_LISTED: usize = 10;
+
+    pub fn pids(&self) -> Vec<Pid> {
+        self.processes.iter().map(|(_, pid)| *pid).collect()
+    }
+
+    /// The name of the process with the given PID.
+    pub fn name_of(&self, pid: Pid) -> &str {
+        self.processes
+            .iter()
+            .find(|(_, p)| *p == pid)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or_default()
+    }
+
+    /// The chosen processes, without any descendants.
+    fn roots(&self) -> &[(String, Pid)] {
+        let len = self.tree_roots.map_or(self.processes.len(), |roots| {
+            roots.min(self.processes.len())
+        });
+        &self.processes[..len]
+    }
+
+    /// What to call the processes as a whole, e.g. in an event.
+    pub fn name(&self) -> &str {
+        match self.roots() {
+            [(name, _), rest @ ..] if rest.iter().all(|(other, _)| other == name) => name,
+            _ if self.is_marked => "marked processes",
+            _ => "grouped processes",
+        }
+    }
+
+    /// Describe the processes to confirm an action, like "process 'a' with PID
+    /// 1" or "3 marked processes".
+    pub fn describe(&self, max_name_width: usize) -> String {
+        let name = unicode_ellipsis::truncate_str(self.name(), max_name_width);
+        let roots = self.roots();
+        let description = match roots {
+            _ if self.is_marked => format!("{} marked processes", roots.len()),
+            [(_, pid)] => format!("process '{name}' with PID {pid}"),
+            [(first, _), rest @ ..] if rest.iter().any(|(other, _)| other != first) => {
+                format!("{} grouped processes", roots.len())
+            }
+            roots => format!("{} processes with the name '{name}'", roots.len()),
+        };
+
+        match self.processes.len() - roots.len() {
+            _ if self.tree_roots.is_none() => description,
+            1 => format!("{description} and 1 descendant"),
+            descendants => format!("{description} and {descendants} descendants"),
+        }
+    }
+
+    /// List the marked processes, like "a (1), b (2), and 3 more".
+    pub fn list(&self) -> Option<String> {
+        if !self.is_marked {
+            return None;
+        }
+
+        let roots = self.roots();
+        let mut list = roots
+            .iter()
+            .take(Self::MAX_LISTED)
+            .map(|(name, pid)| format!("{name} ({pid})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if roots.len() > Self::MAX_LISTED {
+            list.push_str(&format!(", and {} more", roots.len() - Self::MAX_LISTED));
+        }
+
+        Some(list)
+    }
+
+    /// Add every descendant of the processes, following the same parent links
+    /// as tree mode. The chosen processes stay first, followed by their
+    /// descendants level by level, so a chosen process may come before its
+    /// parent.
+    pub fn with_descendants(mut self, process_data: &ProcessData, is_using_command: bool) -> Self {
+        let roots = self.processes.len();
+        let mut seen: HashSet<Pid> = self.pids().into_iter().collect();
+        let mut index = 0;
+
+        while let Some(&(_, pid)) = self.processes.get(index) {
+            index += 1;
+
+            let Some(children) = process_data.process_parent_mapping.get(&pid) else {
+                continue;
+            };
+
+            for child in children {
+                let Some(process) = process_data.process_harvest.get(child) else {
+                    continue;
+                };
+
+                if seen.insert(process.pid) {
+                    let name = if is_using_command {
+                        &process.command
+                    } else {
+                        &process.name
+                    };
+                    self.processes.push((name.clone(), process.pid));
+                }
+            }
+        }
+
+        self.tree_roots = Some(roots);
+        self
+    }
+}
+
+/// A process that the selection is locked onto.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FollowedProcess {
+    pub pid: Pid,
+    pub name: String,
+    /// Whether the process has exited.
+    pub exited: bool,
+    /// Where the process was last selected, to notice when the selection is
+    /// moved to another process.
+    index: usize,
+}
+
 pub struct ProcWidgetState {
     pub(crate) mode: ProcWidgetMode,
 
@@ -229,6 +524,35 @@ pub struct ProcWidgetState {
     default_sort_order: SortOrder,
 
     pub is_sort_open: bool,
+
+    /// Whether recently exited processes are shown instead of running ones.
+    pub show_exited: bool,
+
+    /// The PIDs of marked processes. If any are marked, actions apply to them
+    /// instead of the selected entry.
+    pub marked_pids: HashSet<Pid>,
+
+    /// The PID of the entry last marked or unmarked, where a range of marks
+    /// starts from.
+    mark_anchor: Option<Pid>,
+
+    /// The process the selection follows as the table changes, if any.
+    pub followed: Option<FollowedProcess>,
+
+    /// Whether the usage history of the selected and watched processes is
+    /// shown.
+    pub show_history: bool,
+
+    /// If not empty, only these processes and their descendants are shown.
+    pub root_pids: Vec<Pid>,
+
+    /// What processes are grouped by in grouped mode. Grouping by name or
+    /// command follows whether the command is shown.
+    group_key: ProcessGroupKey,
+
+    /// The groups whose processes are listed under them.
+    expanded_groups: HashSet<String>,
+
     pub force_rerender: bool,
     pub force_update_data: bool,
     #[cfg(target_os = "linux")]
@@ -297,65 +621,18 @@ impl ProcWidgetState {
             pss
         };
 
-        let columns: Vec<SortColumn<ProcColumn>> = {
+        let mut columns: Vec<SortColumn<ProcColumn>> = {
             use ProcColumn::*;
 
             let is_count = matches!(mode, ProcWidgetMode::Grouped);
-            let is_command = table_config.is_command;
+            let is_command = table_config.is_command
+                || (is_count && table_config.group_key == ProcessGroupKey::Command);
             let mem_as_values = table_config.show_memory_as_values;
 
             match config_columns {
                 Some(columns) if !columns.is_empty() => columns
                     .into_iter()
-                    .map(|c| {
-                        let col = match c {
-                            ProcWidgetColumn::PidOrCount => {
-                                if is_count {
-                                    Count
-                                } else {
-                                    Pid
-                                }
-                            }
-                            ProcWidgetColumn::ProcNameOrCommand => {
-                                if is_command {
-                                    Command
-                                } else {
-                                    Name
-                                }
-                            }
-                            ProcWidgetColumn::Cpu => CpuPercent,
-                            ProcWidgetColumn::Mem => {
-                                if mem_as_values {
-                                    MemValue
-                                } else {
-                                    MemPercent
-                                }
-                            }
-                            ProcWidgetColumn::VirtualMem => VirtualMem,
-                            ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
-                            ProcWidgetColumn::WritePerSecond => WritePerSecond,
-                            ProcWidgetColumn::TotalRead => TotalRead,
-                            ProcWidgetColumn::TotalWrite => TotalWrite,
-                            ProcWidgetColumn::User => User,
-                            ProcWidgetColumn::State => State,
-                            ProcWidgetColumn::Time => Time,
-                            ProcWidgetColumn::Priority => Priority,
-                            #[cfg(unix)]
-                            ProcWidgetColumn::Nice => Nice,
-                            #[cfg(feature = "gpu")]
-                            ProcWidgetColumn::GpuMem => {
-                                if mem_as_values {
-                                    GpuMemValue
-                                } else {
-                                    GpuMemPercent
-                                }
-                            }
-                            #[cfg(feature = "gpu")]
-                            ProcWidgetColumn::GpuUtil => GpuUtilPercent,
-                        };
-
-                        make_column(col)
-                    })
+                    .map(|c| make_column(c.proc_column(is_count, is_command, mem_as_values)))
                     .collect(),
                 _ => {
                     let default_columns = [
@@ -381,6 +658,11 @@ impl ProcWidgetState {
             }
         };
 
+        // The exit time is only shown while showing exited processes.
+        let mut exited_column = make_column(ProcColumn::Exited);
+        exited_column.set_hidden(true);
+        columns.push(exited_column);
+
         let column_mapping = columns
             .iter()
             .map(|col| {
@@ -402,43 +684,55 @@ impl ProcWidgetState {
                     Priority => ProcWidgetColumn::Priority,
                     #[cfg(unix)]
                     Nice => ProcWidgetColumn::Nice,
+                    #[cfg(target_os = "linux")]
+                    Affinity => ProcWidgetColumn::Affinity,
+                    #[cfg(target_os = "linux")]
+                    LastCpu => ProcWidgetColumn::LastCpu,
                     #[cfg(feature = "gpu")]
                     GpuMemValue | GpuMemPercent => ProcWidgetColumn::GpuMem,
                     #[cfg(feature = "gpu")]
                     GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+                    Exited => ProcWidgetColumn::Exited,
                 }
             })
             .collect::<IndexSet<_>>();
 
-        let configured_default_sort = table_config.default_sort.and_then(|c| {
-            let widget_col = ProcWidgetColumn::from(&c);
-            column_mapping
-                .get_index_of(&widget_col)
-                .map(|index| (index, columns[index].default_order))
-        });
-
-        let (default_sort_index, default_sort_order) = if let Some(pair) = configured_default_sort {
-            pair
-        } else if matches!(mode, ProcWidgetMode::Tree { .. }) {
-            if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
+        // Any configured columns to sort by that aren't shown are skipped.
+        let configured_sort_keys = table_config
+            .default_sort
+            .iter()
+            .filter_map(|c| {
+                let widget_col = ProcWidgetColumn::from(c);
+                column_mapping
+                    .get_index_of(&widget_col)
+                    .map(|index| (index, columns[index].default_order))
+            })
+            .collect::<Vec<_>>();
+
+        let (default_sort_index, default_sort_order) =
+            if let Some(&pair) = configured_sort_keys.first() {
+                pair
+            } else if matches!(mode, ProcWidgetMode::Tree { .. }) {
+                if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
+                    (index, columns[index].default_order)
+                } else {
+                    (0, columns[0].default_order)
+                }
+            } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
                 (index, columns[index].default_order)
             } else {
                 (0, columns[0].default_order)
-            }
-        } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
-            (index, columns[index].default_order)
-        } else {
-            (0, columns[0].default_order)
-        };
+            };
 
         let sort_table = Self::new_sort_table(config, colours);
-        let table = Self::new_process_table(
+        let mut table = Self::new_process_table(
             config,
             colours,
             columns,
             default_sort_index,
             default_sort_order,
         );
+        table.set_sort_keys(configured_sort_keys);
 
         let id_pid_map = HashMap::default();
 
@@ -449,6 +743,14 @@ impl ProcWidgetState {
             id_pid_map,
             column_mapping,
             is_sort_open: false,
+            show_exited: false,
+            marked_pids: HashSet::default(),
+            mark_anchor: None,
+            followed: None,
+            show_history: !config.process_watch_list.is_empty(),
+            root_pids: Vec::new(),
+            group_key: table_config.group_key,
+            expanded_groups: HashSet::default(),
             mode,
             force_rerender: true,
             force_update_data: false,
@@ -458,6 +760,7 @@ impl ProcWidgetState {
             hide_k_threads: config.hide_k_threads,
         };
         table.sort_table.set_data(table.column_text());
+        table.update_title();
 
         table
     }
@@ -474,6 +777,14 @@ impl ProcWidgetState {
             .unwrap_or(false)
     }
 
+    /// Whether a column is shown in the table.
+    pub fn is_column_shown(&self, column: ProcWidgetColumn) -> bool {
+        self.column_mapping
+            .get_index_of(&column)
+            .and_then(|index| self.table.columns.get(index))
+            .is_some_and(|col| !col.is_hidden())
+    }
+
     pub fn is_mem_percent(&self) -> bool {
         self.column_mapping
             .get_index_of(&ProcWidgetColumn::Mem)
@@ -496,18 +807,206 @@ impl ProcWidgetState {
     /// need to update the actual *stored* data, call it before this
     /// function.
     pub fn set_table_data(&mut self, stored_data: &StoredData) {
-        let data = match &self.mode {
+        // Marks are kept by PID, so forget any for processes that are gone.
+        let process_harvest = &stored_data.process_data.process_harvest;
+        self.marked_pids
+            .retain(|pid| process_harvest.contains_key(pid));
+
+        let tree_pids = (!self.root_pids.is_empty())
+            .then(|| stored_data.process_data.subtree_pids(&self.root_pids));
+
+        let mut data = match &self.mode {
+            _ if self.show_exited => self.get_list_data(
+                stored_data
+                    .process_data
+                    .exited
+                    .iter()
+                    .map(|exited| (&exited.process, Some(exited.exit_time))),
+                process_harvest,
+            ),
             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_data(&stored_data.process_data.process_harvest)
+                self.get_normal_data(process_harvest, tree_pids.as_ref())
+            }
+            ProcWidgetMode::Tree(collapse) => {
+                self.get_tree_data(collapse, stored_data, tree_pids.as_ref())
             }
-            ProcWidgetMode::Tree(collapse) => self.get_tree_data(collapse, stored_data),
         };
+        if !self.show_exited && !self.marked_pids.is_empty() {
+            for row in &mut data {
+                row.marked = self.is_marked(row);
+            }
+        }
+
+        // If the selection was moved while following, follow the newly selected
+        // process instead.
+        if let Some(followed) = &self.followed {
+            if followed.index != self.table.current_index() && !self.show_exited {
+                self.followed = None;
+                self.follow_selected();
+            }
+        }
+
         self.table.set_data(data);
+        self.update_followed(process_harvest);
         self.force_update_data = false;
     }
 
+    /// Keep the followed process selected wherever it moves to, or note that
+    /// it has exited.
+    fn update_followed(&mut self, process_harvest: &BTreeMap<Pid, ProcessHarvest>) {
+        if self.show_exited {
+            return;
+        }
+
+        let Some(followed) = &self.followed else {
+            return;
+        };
+        if followed.exited {
+            return;
+        }
+
+        let pid = followed.pid;
+        let index = self
+            .table
+            .data()
+            .iter()
+            .position(|entry| self.entry_pids(entry).contains(&pid));
+
+        let exited = !process_harvest.contains_key(&pid);
+        if let Some(index) = index {
+            self.table.set_position(index);
+        }
+
+        if let Some(followed) = &mut self.followed {
+            // A process hidden by a search or a collapsed branch is still
+            // followed, just not selected.
+            followed.exited = exited;
+            followed.index = self.table.current_index();
+        }
+        self.update_title();
+    }
+
+    /// Toggle showing the usage history of the selected and watched processes.
+    pub fn toggle_history(&mut self) {
+        self.show_history = !self.show_history;
+        self.force_rerender = true;
+    }
+
+    /// The processes to show the usage history of, which is the selected one
+    /// followed by any on the watch list.
+    pub fn history_processes<'a>(
+        &self, process_data: &'a ProcessData, watch_list: &[WatchedProcess],
+    ) -> Vec<&'a ProcessHarvest> {
+        // A group has no one process to show, but its expanded members do.
+        let selected = if self.show_exited {
+            None
+        } else {
+            self.table
+                .current_item()
+                .filter(|entry| entry.group.is_none())
+                .and_then(|entry| process_data.process_harvest.get(&entry.pid))
+        };
+        let selected_pid = selected.map(|process| process.pid);
+
+        let watched = process_data.process_harvest.values().filter(|process| {
+            Some(process.pid) != selected_pid
+                && watch_list.iter().any(|watched| watched.matches(process))
+        });
+
+        selected.into_iter().chain(watched).collect()
+    }
+
+    /// Only show the given processes and their descendants, or every process
+    /// if there are none.
+    pub fn set_root_pids(&mut self, root_pids: Vec<Pid>) {
+        self.root_pids = root_pids;
+        self.reset_position();
+        self.update_title();
+        self.force_data_update();
+    }
+
+    /// Toggle only showing the selected process and its descendants. A grouped
+    /// entry shows the trees of all of its processes.
+    pub fn toggle_tree_filter(&mut self) {
+        if !self.root_pids.is_empty() {
+            self.set_root_pids(Vec::new());
+        } else if !self.show_exited {
+            if let Some(entry) = self.table.current_item() {
+                let root_pids = self.entry_pids(entry);
+                self.set_root_pids(root_pids);
+            }
+        }
+    }
+
+    /// Move the selection back to the top, without losing track of the
+    /// followed process.
+    fn reset_position(&mut self) {
+        self.table.state.display_start_index = 0;
+        self.table.state.current_index = 0;
+        if let Some(followed) = &mut self.followed {
+            followed.index = 0;
+        }
+    }
+
+    /// Start following the selected process.
+    fn follow_selected(&mut self) {
+        if let Some(entry) = self.table.current_item() {
+            self.followed = Some(FollowedProcess {
+                pid: entry.pid,
+                name: entry.id.to_string(),
+                exited: false,
+                index: self.table.current_index(),
+            });
+        }
+        self.update_title();
+    }
+
+    /// Toggle following the selected process, keeping it selected as the table
+    /// is sorted and updated.
+    pub fn toggle_follow(&mut self) {
+        if self.followed.is_some() {
+            self.followed = None;
+            self.update_title();
+        } else if !self.show_exited {
+            self.follow_selected();
+        }
+    }
+
+    /// Set the table title, which notes which process is followed.
+    fn update_title(&mut self) {
+        let title = match &self.followed {
+            _ if self.show_exited => " Exited Processes ".into(),
+            Some(FollowedProcess {
+                pid,
+                name,
+                exited: false,
+                ..
+            }) => format!(" Processes - following {name} ({pid}) ").into(),
+            Some(FollowedProcess {
+                pid,
+                name,
+                exited: true,
+                ..
+            }) => format!(" Processes - {name} ({pid}) has exited ").into(),
+            None if !self.root_pids.is_empty() => format!(
+                " Processes - tree of {} ",
+                self.root_pids.iter().map(Pid::to_string).join(", ")
+            )
+            .into(),
+            None if matches!(self.mode, ProcWidgetMode::Grouped) => match self.group_key() {
+                ProcessGroupKey::Name | ProcessGroupKey::Command => " Processes ".into(),
+                ProcessGroupKey::User => " Processes - by user ".into(),
+                ProcessGroupKey::Parent => " Processes - by parent ".into(),
+                ProcessGroupKey::Exe => " Processes - by executable ".into(),
+            },
+            None => " Processes ".into(),
+        };
+        self.table.props.title = Some(title);
+    }
+
     fn get_tree_data(
         &self, collapsed: &TreeCollapsed, stored_data: &StoredData,
+        tree_pids: Option<&IntHashSet<Pid>>,
     ) -> Vec<ProcWidgetData> {
         const BRANCH_END: char = '└';
         const BRANCH_SPLIT: char = '├';
@@ -525,12 +1024,35 @@ impl ProcWidgetState {
             ..
         } = &stored_data.process_data;
 
+        // When only some trees are shown, they start from their roots rather than
+        // from the orphans.
+        let top_pids = match tree_pids {
+            Some(pids) => Cow::Owned(
+                self.root_pids
+                    .iter()
+                    .copied()
+                    .filter(|pid| {
+                        pids.contains(pid)
+                            && process_harvest.get(pid).is_some_and(|process| {
+                                process.parent_pid.is_none_or(|ppid| !pids.contains(&ppid))
+                            })
+                    })
+                    .unique()
+                    .collect_vec(),
+            ),
+            None => Cow::Borrowed(orphan_pids),
+        };
+
         // Only keep a set of the kept PIDs.
         let kept_pids = stored_data
             .process_data
             .process_harvest
             .iter()
             .filter_map(|(pid, process)| {
+                if tree_pids.is_some_and(|pids| !pids.contains(pid)) {
+                    return None;
+                }
+
                 if search_query
                     .as_ref()
                     .map(|q| q.check(process, is_using_command))
@@ -576,7 +1098,7 @@ impl ProcWidgetState {
 
             // We do a simple DFS traversal to build our filtered parent-to-tree mappings.
             let mut visited_pids: IntHashMap<Pid, bool> = IntHashMap::default();
-            let mut stack = orphan_pids
+            let mut stack = top_pids
                 .iter()
                 .filter_map(|process| process_harvest.get(process))
                 .collect_vec();
@@ -644,7 +1166,7 @@ impl ProcWidgetState {
 
         let mut data = vec![];
         let mut prefixes = vec![];
-        let mut stack = orphan_pids
+        let mut stack = top_pids
             .iter()
             .filter_map(|pid| {
                 if filtered_tree.contains_key(pid) {
@@ -657,14 +1179,9 @@ impl ProcWidgetState {
             })
             .collect_vec();
 
+        // Processes tied on every sort column stay in PID order.
         stack.sort_unstable_by_key(|p| p.pid);
-
-        let column = self
-            .table
-            .columns
-            .get(self.table.sort_index())
-            .expect("columns should contain the current sort index");
-        sort_skip_pid_asc(column.inner(), &mut stack, self.table.order());
+        self.table.sort_rows(&mut stack);
 
         let mut length_stack = vec![stack.len()];
         stack.reverse();
@@ -763,7 +1280,7 @@ impl ProcWidgetState {
                         })
                         .collect_vec();
 
-                    column.sort_by(&mut children, self.table.order().rev());
+                    self.table.sort_rows_reversed(&mut children);
 
                     length_stack.push(children.len());
                     stack.extend(children);
@@ -785,12 +1302,28 @@ impl ProcWidgetState {
 
     fn get_normal_data(
         &mut self, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+        tree_pids: Option<&IntHashSet<Pid>>,
+    ) -> Vec<ProcWidgetData> {
+        self.get_list_data(
+            process_harvest
+                .values()
+                .filter(|process| tree_pids.is_none_or(|pids| pids.contains(&process.pid)))
+                .map(|process| (process, None)),
+            process_harvest,
+        )
+    }
+
+    /// Get the data for a flat list of processes, each with when it exited if
+    /// it has.
+    fn get_list_data<'a>(
+        &mut self, processes: impl Iterator<Item = (&'a ProcessHarvest, Option<Instant>)>,
+        process_harvest: &BTreeMap<Pid, ProcessHarvest>,
     ) -> Vec<ProcWidgetData> {
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
 
-        let filtered_iter = process_harvest.values().filter(|process| {
+        let filtered_iter = processes.filter(|(process, _)| {
             #[cfg(target_os = "linux")]
             if self.hide_k_threads && process.process_type.is_kernel() {
                 return false;
@@ -803,73 +1336,70 @@ impl ProcWidgetState {
         });
 
         let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
+        let mut members: HashMap<String, Vec<ProcWidgetData>> = HashMap::default();
         let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
-            let mut id_process_mapping: HashMap<&String, ProcWidgetData> = HashMap::default();
+            let group_key = self.group_key();
+            let mut id_process_mapping: HashMap<String, ProcWidgetData> = HashMap::default();
 
-            for process in filtered_iter {
-                let id = if is_using_command {
-                    &process.command
-                } else {
-                    &process.name
-                };
-                let pid = process.pid;
+            for (process, exit_time) in filtered_iter {
+                let id = group_name(group_key, process, process_harvest);
+                let data = ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
+                    .exit_time(exit_time);
 
-                if let Some(entry) = id_pid_map.get_mut(id) {
-                    entry.push(pid);
-                } else {
-                    id_pid_map.insert(id.clone(), vec![pid]);
+                id_pid_map.entry(id.clone()).or_default().push(process.pid);
+                if self.expanded_groups.contains(&id) {
+                    members.entry(id.clone()).or_default().push(data.clone());
                 }
 
-                if let Some(pwd) = id_process_mapping.get_mut(id) {
-                    pwd.cpu_usage_percent += process.cpu_usage_percent;
-
-                    match &mut pwd.mem_usage {
-                        MemUsage::Percent(usage) => {
-                            *usage += process.mem_usage_percent;
-                        }
-                        MemUsage::Bytes(usage) => {
-                            *usage += process.mem_usage;
-                        }
-                    }
-
-                    pwd.rps += process.read_per_sec;
-                    pwd.wps += process.write_per_sec;
-                    pwd.total_read += process.total_read;
-                    pwd.total_write += process.total_write;
-                    pwd.time = pwd.time.max(process.time);
-                    #[cfg(feature = "gpu")]
-                    {
-                        pwd.gpu_usage += process.gpu_util;
-                        match &mut pwd.gpu_mem_usage {
-                            MemUsage::Percent(usage) => {
-                                *usage += process.gpu_mem_percent;
-                            }
-                            MemUsage::Bytes(usage) => {
-                                *usage += process.gpu_mem;
-                            }
-                        }
-                    }
-
+                if let Some(pwd) = id_process_mapping.get_mut(&id) {
+                    pwd.add(&data);
                     pwd.num_similar += 1;
                 } else {
-                    id_process_mapping.insert(
-                        id,
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent),
-                    );
+                    id_process_mapping.insert(id.clone(), data.group(id));
                 }
             }
 
             id_process_mapping.into_values().collect()
         } else {
             filtered_iter
-                .map(|process| ProcWidgetData::from_data(process, is_using_command, is_mem_percent))
+                .map(|(process, exit_time)| {
+                    ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
+                        .exit_time(exit_time)
+                })
                 .collect()
         };
 
         self.id_pid_map = id_pid_map;
 
-        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
+        // Running processes are already in PID order, which is kept for
+        // any processes tied on every sort column.
+        self.table.sort_rows(&mut filtered_data);
+
+        // List the processes of any expanded groups under them.
+        if !members.is_empty() {
+            filtered_data = filtered_data
+                .into_iter()
+                .flat_map(|group| {
+                    let mut group_members = group
+                        .group
+                        .as_ref()
+                        .and_then(|id| members.remove(id))
+                        .unwrap_or_default();
+                    self.table.sort_rows(&mut group_members);
+
+                    let last = group_members.len().saturating_sub(1);
+                    let group_members =
+                        group_members
+                            .into_iter()
+                            .enumerate()
+                            .map(move |(index, member)| {
+                                let branch = if index == last { "└─ " } else { "├─ " };
+                                member.prefix(Some(branch.to_string()))
+                            });
+
+                    std::iter::once(group).chain(group_members)
+                })
+                .collect();
         }
 
         filtered_data
@@ -935,6 +1465,197 @@ impl ProcWidgetState {
         self.force_rerender_and_update();
     }
 
+    /// Toggle between showing running processes and recently exited ones.
+    /// While showing exited processes, they are sorted by when they exited.
+    pub fn toggle_exited(&mut self) {
+        self.show_exited = !self.show_exited;
+
+        if self.show_exited {
+            self.show_column(ProcWidgetColumn::Exited);
+            self.select_column(ProcWidgetColumn::Exited);
+            self.table.set_order(SortOrder::Descending);
+        } else {
+            self.hide_column(ProcWidgetColumn::Exited);
+        }
+        self.update_title();
+
+        self.reset_position();
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+    }
+
+    /// The PIDs of the processes in an entry.
+    fn entry_pids(&self, entry: &ProcWidgetData) -> Vec<Pid> {
+        match &entry.group {
+            Some(group) => self
+                .id_pid_map
+                .get(group)
+                .cloned()
+                .unwrap_or_else(|| vec![entry.pid]),
+            None => vec![entry.pid],
+        }
+    }
+
+    /// Whether every process in an entry is marked.
+    fn is_marked(&self, entry: &ProcWidgetData) -> bool {
+        self.entry_pids(entry)
+            .iter()
+            .all(|pid| self.marked_pids.contains(pid))
+    }
+
+    /// Mark or unmark entries, given their indices.
+    fn set_marked(&mut self, indices: impl Iterator<Item = usize>, marked: bool) {
+        let pids: Vec<Pid> = indices
+            .filter_map(|index| self.table.data().get(index))
+            .flat_map(|entry| self.entry_pids(entry))
+            .collect();
+
+        if marked {
+            self.marked_pids.extend(pids);
+        } else {
+            for pid in pids {
+                self.marked_pids.remove(&pid);
+            }
+        }
+
+        self.force_data_update();
+    }
+
+    /// Toggle marking the selected entry, and then move to the next one.
+    pub fn toggle_mark(&mut self) {
+        if self.show_exited {
+            return;
+        }
+
+        let Some(entry) = self.table.current_item() else {
+            return;
+        };
+        let marked = !self.is_marked(entry);
+        self.mark_anchor = Some(entry.pid);
+
+        let index = self.table.current_index();
+        self.set_marked(std::iter::once(index), marked);
+        self.table.increment_position(1);
+    }
+
+    /// Mark every entry from the one last marked or unmarked to the selected
+    /// one.
+    pub fn mark_range(&mut self) {
+        if self.show_exited {
+            return;
+        }
+
+        let current = self.table.current_index();
+        let anchor = self
+            .mark_anchor
+            .and_then(|anchor| {
+                self.table
+                    .data()
+                    .iter()
+                    .position(|entry| entry.pid == anchor)
+            })
+            .unwrap_or(current);
+
+        self.set_marked(anchor.min(current)..=anchor.max(current), true);
+        self.mark_anchor = self.table.current_item().map(|entry| entry.pid);
+    }
+
+    /// Mark every shown entry, which are the ones that match the search if
+    /// there is one. If they are all already marked, unmark them instead.
+    pub fn mark_all(&mut self) {
+        if self.show_exited {
+            return;
+        }
+
+        let len = self.table.data().len();
+        let marked = !self.table.data().iter().all(|entry| self.is_marked(entry));
+        self.set_marked(0..len, marked);
+    }
+
+    /// Unmark every process.
+    pub fn clear_marks(&mut self) {
+        self.marked_pids.clear();
+        self.mark_anchor = None;
+        self.force_data_update();
+    }
+
+    /// The processes an action applies to, along with the entry to use for
+    /// their current state: the marked processes if there are any, otherwise
+    /// the selected entry. Exited processes can't be acted on, as their PIDs
+    /// may have been reused.
+    pub fn action_targets(
+        &self, process_data: &ProcessData,
+    ) -> Option<(ProcWidgetData, ProcessTargets)> {
+        if self.show_exited {
+            return None;
+        }
+
+        let is_using_command = self.is_using_command();
+
+        if !self.marked_pids.is_empty() {
+            let processes: Vec<&ProcessHarvest> = self
+                .marked_pids
+                .iter()
+                .filter_map(|pid| process_data.process_harvest.get(pid))
+                .sorted_by_key(|process| process.pid)
+                .collect();
+            let entry = ProcWidgetData::from_data(
+                processes.first()?,
+                is_using_command,
+                self.is_mem_percent(),
+            );
+            let processes = processes
+                .into_iter()
+                .map(|process| {
+                    let name = if is_using_command {
+                        &process.command
+                    } else {
+                        &process.name
+                    };
+                    (name.clone(), process.pid)
+                })
+                .collect();
+
+            return Some((
+                entry,
+                ProcessTargets {
+                    processes,
+                    is_marked: true,
+                    tree_roots: None,
+                },
+            ));
+        }
+
+        // A group may not be named after its processes, so use their own names.
+        let entry = self.table.current_item()?;
+        let processes = self
+            .entry_pids(entry)
+            .into_iter()
+            .map(|pid| {
+                let name = match process_data.process_harvest.get(&pid) {
+                    Some(process) if entry.group.is_some() => {
+                        if is_using_command {
+                            process.command.clone()
+                        } else {
+                            process.name.clone()
+                        }
+                    }
+                    _ => entry.id.to_string(),
+                };
+                (name, pid)
+            })
+            .collect();
+
+        Some((
+            entry.clone(),
+            ProcessTargets {
+                processes,
+                is_marked: false,
+                tree_roots: None,
+            },
+        ))
+    }
+
     /// Marks the selected column as hidden, and automatically resets the
     /// selected column to the default sort index and order.
     fn hide_column(&mut self, column: ProcWidgetColumn) {
@@ -945,51 +1666,243 @@ impl ProcWidgetState {
                 if self.table.sort_index() == index {
                     self.table.set_sort_index(self.default_sort_index);
                     self.table.set_order(self.default_sort_order);
+                } else {
+                    self.table.remove_sort_index(index);
+                }
+            }
+        }
+    }
+
+    /// Marks the selected column as shown.
+    fn show_column(&mut self, column: ProcWidgetColumn) {
+        if let Some(index) = self.column_mapping.get_index_of(&column) {
+            if let Some(col) = self.table.columns.get_mut(index) {
+                col.set_hidden(false);
+            }
+        }
+    }
+
+    /// Select a column. If the column is already selected, then just toggle the
+    /// sort order.
+    pub fn select_column(&mut self, column: ProcWidgetColumn) {
+        if let Some(index) = self.column_mapping.get_index_of(&column) {
+            self.table.set_sort_index(index);
+            self.force_data_update();
+        }
+    }
+
+    /// The columns of the table, in order, other than the exit time.
+    pub fn shown_columns(&self) -> Vec<ProcWidgetColumn> {
+        self.column_mapping
+            .iter()
+            .filter(|column| **column != ProcWidgetColumn::Exited)
+            .copied()
+            .collect()
+    }
+
+    /// Replace the columns of the table. Columns that were already shown keep
+    /// their settings, and the table stays sorted by the same columns if they
+    /// are still shown.
+    pub fn set_columns(&mut self, columns: &[ProcWidgetColumn]) {
+        let sort_keys = self
+            .table
+            .sort_keys()
+            .into_iter()
+            .filter_map(|(index, order)| {
+                self.column_mapping
+                    .get_index(index)
+                    .map(|column| (*column, order))
+            })
+            .collect::<Vec<_>>();
+        let default_sort_column = self
+            .column_mapping
+            .get_index(self.default_sort_index)
+            .copied();
+
+        let is_count = matches!(self.mode, ProcWidgetMode::Grouped);
+        let is_command = self.is_using_command();
+        let mem_as_values = self
+            .column_mapping
+            .get_index_of(&ProcWidgetColumn::Mem)
+            .is_some()
+            && !self.is_mem_percent();
+
+        let mut old_columns = mem::take(&mut self.table.columns)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut take_old_column = |column: &ProcWidgetColumn| {
+            self.column_mapping
+                .get_index_of(column)
+                .and_then(|index| old_columns.get_mut(index))
+                .and_then(Option::take)
+        };
+
+        let mut new_columns = columns
+            .iter()
+            .filter(|column| **column != ProcWidgetColumn::Exited)
+            .map(|column| {
+                take_old_column(column).unwrap_or_else(|| {
+                    let mut new_column =
+                        make_column(column.proc_column(is_count, is_command, mem_as_values));
+                    if is_count {
+                        match column {
+                            ProcWidgetColumn::PidOrCount => {
+                                new_column.default_order = SortOrder::Descending
+                            }
+                            ProcWidgetColumn::User | ProcWidgetColumn::State => {
+                                new_column.set_hidden(true)
+                            }
+                            _ => {}
+                        }
+                    }
+                    new_column
+                })
+            })
+            .collect::<Vec<_>>();
+        new_columns.push(
+            take_old_column(&ProcWidgetColumn::Exited).unwrap_or_else(|| {
+                let mut exited_column = make_column(ProcColumn::Exited);
+                exited_column.set_hidden(!self.show_exited);
+                exited_column
+            }),
+        );
+
+        self.column_mapping = new_columns
+            .iter()
+            .map(|column| ProcWidgetColumn::from(column.inner()))
+            .collect();
+        self.table.columns = new_columns;
+
+        let index_of = |column: ProcWidgetColumn| self.column_mapping.get_index_of(&column);
+        match default_sort_column.and_then(index_of) {
+            Some(index) => self.default_sort_index = index,
+            None => {
+                self.default_sort_index = index_of(ProcWidgetColumn::Cpu).unwrap_or(0);
+                if let Some(column) = self.table.columns.get(self.default_sort_index) {
+                    self.default_sort_order = column.default_order;
+                }
+            }
+        }
+        match sort_keys.first().and_then(|(column, _)| index_of(*column)) {
+            Some(_) => {
+                let sort_keys = sort_keys
+                    .iter()
+                    .filter_map(|(column, order)| index_of(*column).map(|index| (index, *order)))
+                    .collect::<Vec<_>>();
+                self.table.set_sort_keys(sort_keys);
+            }
+            None => {
+                self.table.set_sort_index(self.default_sort_index);
+                self.table.set_order(self.default_sort_order);
+            }
+        }
+
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+    }
+
+    pub fn collapse_current_tree_branch_entry(&mut self) {
+        match &mut self.mode {
+            ProcWidgetMode::Tree(collapsed) => {
+                if let Some(process) = self.table.current_item() {
+                    collapsed.collapse(process.pid);
+                    self.force_data_update();
+                }
+            }
+            ProcWidgetMode::Grouped => self.set_current_group_expanded(Some(false)),
+            ProcWidgetMode::Normal => {}
+        }
+    }
+
+    pub fn expand_current_tree_branch_entry(&mut self) {
+        match &mut self.mode {
+            ProcWidgetMode::Tree(collapsed) => {
+                if let Some(process) = self.table.current_item() {
+                    collapsed.expand(process.pid);
+                    self.force_data_update();
                 }
             }
+            ProcWidgetMode::Grouped => self.set_current_group_expanded(Some(true)),
+            ProcWidgetMode::Normal => {}
         }
     }
 
-    /// Marks the selected column as shown.
-    fn show_column(&mut self, column: ProcWidgetColumn) {
-        if let Some(index) = self.column_mapping.get_index_of(&column) {
-            if let Some(col) = self.table.columns.get_mut(index) {
-                col.set_hidden(false);
+    pub fn toggle_current_tree_branch_entry(&mut self) {
+        match &mut self.mode {
+            ProcWidgetMode::Tree(collapsed) => {
+                if let Some(process) = self.table.current_item() {
+                    collapsed.toggle(process.pid);
+                    self.force_data_update();
+                }
             }
+            ProcWidgetMode::Grouped => self.set_current_group_expanded(None),
+            ProcWidgetMode::Normal => {}
         }
     }
 
-    /// Select a column. If the column is already selected, then just toggle the
-    /// sort order.
-    pub fn select_column(&mut self, column: ProcWidgetColumn) {
-        if let Some(index) = self.column_mapping.get_index_of(&column) {
-            self.table.set_sort_index(index);
-            self.force_data_update();
-        }
-    }
+    /// Expand or collapse the selected group, or toggle it if `expanded` is
+    /// not set. If a process listed under a group is selected, its group is
+    /// used, and selected if it is collapsed.
+    fn set_current_group_expanded(&mut self, expanded: Option<bool>) {
+        let index = self.table.current_index();
+        let Some((group_index, group)) = self.table.data()
+            [..(index + 1).min(self.table.data().len())]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, entry)| Some((index, entry.group.clone()?)))
+        else {
+            return;
+        };
 
-    pub fn collapse_current_tree_branch_entry(&mut self) {
-        if let ProcWidgetMode::Tree(collapsed) = &mut self.mode {
-            if let Some(process) = self.table.current_item() {
-                collapsed.collapse(process.pid);
-                self.force_data_update();
-            }
+        let expanded = expanded.unwrap_or(!self.expanded_groups.contains(&group));
+        if expanded {
+            self.expanded_groups.insert(group);
+        } else {
+            self.expanded_groups.remove(&group);
+            self.table.set_position(group_index);
         }
+        self.force_data_update();
     }
 
-    pub fn expand_current_tree_branch_entry(&mut self) {
-        if let ProcWidgetMode::Tree(collapsed) = &mut self.mode {
-            if let Some(process) = self.table.current_item() {
-                collapsed.expand(process.pid);
-                self.force_data_update();
+    /// What processes are currently grouped by.
+    fn group_key(&self) -> ProcessGroupKey {
+        match self.group_key {
+            ProcessGroupKey::Name | ProcessGroupKey::Command if self.is_using_command() => {
+                ProcessGroupKey::Command
             }
+            ProcessGroupKey::Name | ProcessGroupKey::Command => ProcessGroupKey::Name,
+            key => key,
         }
     }
 
-    pub fn toggle_current_tree_branch_entry(&mut self) {
-        if let ProcWidgetMode::Tree(collapsed) = &mut self.mode {
-            if let Some(process) = self.table.current_item() {
-                collapsed.toggle(process.pid);
+    /// Group processes by the next key, from the name, to the command, user,
+    /// parent process, and executable. If processes aren't grouped, group
+    /// them by the current key instead.
+    pub fn cycle_group_key(&mut self) {
+        match self.mode {
+            ProcWidgetMode::Normal => self.toggle_tab(),
+            ProcWidgetMode::Tree(_) => {}
+            ProcWidgetMode::Grouped => {
+                let key = match self.group_key() {
+                    ProcessGroupKey::Name => ProcessGroupKey::Command,
+                    ProcessGroupKey::Command => ProcessGroupKey::User,
+                    ProcessGroupKey::User => ProcessGroupKey::Parent,
+                    ProcessGroupKey::Parent => ProcessGroupKey::Exe,
+                    ProcessGroupKey::Exe => ProcessGroupKey::Name,
+                };
+
+                // Grouping by the command shows it, and grouping by anything
+                // else goes back to the name.
+                if (key == ProcessGroupKey::Command) != self.is_using_command() {
+                    self.toggle_command();
+                }
+
+                self.group_key = key;
+                self.expanded_groups.clear();
+                self.reset_position();
+                self.update_title();
                 self.force_data_update();
             }
         }
@@ -1065,6 +1978,8 @@ impl ProcWidgetState {
                         _ => unreachable!(),
                     }
 
+                    self.expanded_groups.clear();
+                    self.update_title();
                     self.sort_table.set_data(self.column_text());
                     self.force_rerender_and_update();
                 }
@@ -1111,8 +2026,7 @@ impl ProcWidgetState {
                 }
             }
         }
-        self.table.state.display_start_index = 0;
-        self.table.state.current_index = 0;
+        self.reset_position();
 
         self.force_data_update();
     }
@@ -1122,15 +2036,49 @@ impl ProcWidgetState {
         self.force_data_update();
     }
 
+    /// The index of the column selected in the sort table. The sort table
+    /// only lists shown columns, so this may differ from its position.
+    fn sort_table_column(&self) -> Option<usize> {
+        self.table
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden())
+            .nth(self.sort_table.current_index())
+            .map(|(index, _)| index)
+    }
+
+    /// The position of the current sort column in the sort table.
+    pub(crate) fn sort_table_position(&self) -> usize {
+        self.table
+            .columns
+            .iter()
+            .take(self.table.sort_index())
+            .filter(|column| !column.is_hidden())
+            .count()
+    }
+
     /// Sets the [`ProcWidgetState`]'s current sort index to whatever was in the
     /// sort table if possible, then closes the sort table.
     pub(crate) fn use_sort_table_value(&mut self) {
-        self.table.set_sort_index(self.sort_table.current_index());
+        if let Some(index) = self.sort_table_column() {
+            self.table.set_sort_index(index);
+        }
 
         self.is_sort_open = false;
         self.force_rerender_and_update();
     }
 
+    /// Adds whatever was in the sort table as another column to sort by,
+    /// leaving the sort table open.
+    pub(crate) fn add_sort_table_value(&mut self) {
+        if let Some(index) = self.sort_table_column() {
+            self.table.add_sort_index(index);
+        }
+
+        self.force_rerender_and_update();
+    }
+
     #[cfg(test)]
     pub(crate) fn test_equality(&self, other: &Self) -> bool {
         self.mode == other.mode
@@ -1150,14 +2098,24 @@ impl ProcWidgetState {
     }
 }
 
-#[inline]
-fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder) {
-    let descending = matches!(order, SortOrder::Descending);
-    match column {
-        ProcColumn::Pid if !descending => {}
-        _ => {
-            column.sort_data(data, descending);
-        }
+/// The name of the group a process is in when grouped by `key`.
+fn group_name(
+    key: ProcessGroupKey, process: &ProcessHarvest, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+) -> String {
+    const UNKNOWN: &str = "N/A";
+
+    match key {
+        ProcessGroupKey::Name => process.name.clone(),
+        ProcessGroupKey::Command => process.command.clone(),
+        ProcessGroupKey::User => process.user.as_deref().unwrap_or(UNKNOWN).to_string(),
+        ProcessGroupKey::Parent => match process.parent_pid {
+            Some(ppid) => match process_harvest.get(&ppid) {
+                Some(parent) => format!("{} ({ppid})", parent.name),
+                None => format!("PID {ppid}"),
+            },
+            None => UNKNOWN.to_string(),
+        },
+        ProcessGroupKey::Exe => process.exe.as_deref().unwrap_or(UNKNOWN).to_string(),
     }
 }
 
@@ -1166,6 +2124,7 @@ mod test {
     use std::time::Duration;
 
     use super::*;
+    use crate::app::data::ExitedProcess;
     #[cfg(target_os = "linux")]
     use crate::collection::processes::ProcessType;
     use crate::widgets::MemUsage;
@@ -1190,7 +2149,9 @@ mod test {
             #[cfg(not(target_family = "unix"))]
             user: Some("N/A".into()),
             num_similar: 0,
+            group: None,
             disabled: false,
+            marked: false,
             time: Duration::from_secs(0),
             #[cfg(feature = "gpu")]
             gpu_mem_usage: MemUsage::Percent(1.1),
@@ -1201,6 +2162,11 @@ mod test {
             #[cfg(unix)]
             nice: 0,
             priority: -20,
+            #[cfg(target_os = "linux")]
+            cpu_affinity: None,
+            #[cfg(target_os = "linux")]
+            last_cpu: None,
+            exit_time: None,
         };
 
         let b = ProcWidgetData {
@@ -1233,7 +2199,7 @@ mod test {
 
         // Assume we had sorted over by pid.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        make_column(ProcColumn::CpuPercent).sort_by(&mut data, SortOrder::Descending);
         assert_eq!(
             [&c, &b, &a, &d].iter().map(|d| d.pid).collect::<Vec<_>>(),
             data.iter().map(|d| d.pid).collect::<Vec<_>>(),
@@ -1241,14 +2207,14 @@ mod test {
 
         // Note that the PID ordering for ties is still ascending.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Ascending);
+        make_column(ProcColumn::CpuPercent).sort_by(&mut data, SortOrder::Ascending);
         assert_eq!(
             [&a, &d, &b, &c].iter().map(|d| d.pid).collect::<Vec<_>>(),
             data.iter().map(|d| d.pid).collect::<Vec<_>>(),
         );
 
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Descending);
+        make_column(ProcColumn::MemPercent).sort_by(&mut data, SortOrder::Descending);
         assert_eq!(
             [&b, &a, &c, &d].iter().map(|d| d.pid).collect::<Vec<_>>(),
             data.iter().map(|d| d.pid).collect::<Vec<_>>(),
@@ -1256,11 +2222,42 @@ mod test {
 
         // Note that the PID ordering for ties is still ascending.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Ascending);
+        make_column(ProcColumn::MemPercent).sort_by(&mut data, SortOrder::Ascending);
         assert_eq!(
             [&c, &d, &a, &b].iter().map(|d| d.pid).collect::<Vec<_>>(),
             data.iter().map(|d| d.pid).collect::<Vec<_>>(),
         );
+
+        // Ties in CPU usage can be broken by another column instead of the PID.
+        let state = init_state(
+            ProcTableConfig {
+                default_sort: vec![ProcColumn::CpuPercent, ProcColumn::MemPercent],
+                ..Default::default()
+            },
+            &[
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::Cpu,
+                ProcWidgetColumn::Mem,
+            ],
+        );
+        assert_eq!(
+            state.table.sort_keys(),
+            vec![(1, SortOrder::Descending), (2, SortOrder::Descending)]
+        );
+
+        data.sort_by_key(|p| p.pid);
+        state.table.sort_rows(&mut data);
+        assert_eq!(
+            [&c, &b, &a, &d].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
+        );
+
+        data.sort_by_key(|p| p.pid);
+        state.table.sort_rows_reversed(&mut data);
+        assert_eq!(
+            [&d, &a, &b, &c].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
+        );
     }
 
     fn get_columns(table: &ProcessTable) -> Vec<ProcColumn> {
@@ -1312,14 +2309,14 @@ mod test {
         );
 
         let table_config = ProcTableConfig {
-            default_sort: Some(ProcColumn::MemPercent),
+            default_sort: vec![ProcColumn::MemPercent],
             ..Default::default()
         };
         let state_mem = init_state(table_config, &init_columns);
         assert_eq!(state_mem.table.sort_index(), 3);
 
         let table_config = ProcTableConfig {
-            default_sort: Some(ProcColumn::Pid),
+            default_sort: vec![ProcColumn::Pid],
             ..Default::default()
         };
         let state_pid = init_state(table_config, &init_columns);
@@ -1337,7 +2334,7 @@ mod test {
         ];
 
         let table_config = ProcTableConfig {
-            default_sort: Some(ProcColumn::MemPercent),
+            default_sort: vec![ProcColumn::MemPercent],
             ..Default::default()
         };
         let state = init_state(table_config, &init_columns);
@@ -1362,6 +2359,99 @@ mod test {
         assert_eq!(get_columns(&state.table), columns);
     }
 
+    #[test]
+    fn set_columns() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+            ProcWidgetColumn::User,
+        ];
+        let mut state = init_default_state(&init_columns);
+        state.toggle_mem_percentage();
+        state.toggle_tab();
+        state.select_column(ProcWidgetColumn::Mem);
+
+        // Columns that stay keep their settings, and new ones follow the mode.
+        state.set_columns(&[
+            ProcWidgetColumn::Mem,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::State,
+        ]);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::MemValue, ProcColumn::Name, ProcColumn::Count]
+        );
+        assert_eq!(
+            state.shown_columns(),
+            vec![
+                ProcWidgetColumn::Mem,
+                ProcWidgetColumn::ProcNameOrCommand,
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::State,
+            ]
+        );
+        assert_eq!(state.table.sort_index(), 0);
+        assert_eq!(state.table.order(), SortOrder::Descending);
+
+        // If the sorted column is removed, the default sort is used instead.
+        state.set_columns(&[ProcWidgetColumn::PidOrCount, ProcWidgetColumn::Cpu]);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::Count, ProcColumn::CpuPercent]
+        );
+        assert_eq!(state.table.sort_index(), 1);
+
+        state.toggle_tab();
+        state.set_columns(&[
+            ProcWidgetColumn::State,
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::Cpu,
+        ]);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::State, ProcColumn::Pid, ProcColumn::CpuPercent]
+        );
+        assert_eq!(state.table.sort_index(), 2);
+        assert!(state.is_column_shown(ProcWidgetColumn::State));
+        assert!(!state.is_column_shown(ProcWidgetColumn::Affinity));
+
+        // Every column being sorted by follows its column.
+        state.table.add_sort_index(1);
+        state.set_columns(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::State,
+        ]);
+        assert_eq!(
+            state.table.sort_keys(),
+            vec![(1, SortOrder::Descending), (0, SortOrder::Ascending)]
+        );
+        state.set_columns(&[
+            ProcWidgetColumn::State,
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::Cpu,
+        ]);
+        assert_eq!(
+            state.table.sort_keys(),
+            vec![(2, SortOrder::Descending), (1, SortOrder::Ascending)]
+        );
+
+        // The exit time is still shown for exited processes.
+        state.toggle_exited();
+        assert_eq!(
+            get_columns(&state.table),
+            vec![
+                ProcColumn::State,
+                ProcColumn::Pid,
+                ProcColumn::CpuPercent,
+                ProcColumn::Exited
+            ]
+        );
+    }
+
     #[test]
     fn toggle_count_pid() {
         let init_columns = [
@@ -1693,6 +2783,452 @@ mod test {
         assert_eq!(get_columns(&state.table), original_columns);
     }
 
+    #[test]
+    fn toggle_exited() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let process = |pid, name: &str| ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut stored_data = StoredData::default();
+        stored_data
+            .process_data
+            .process_harvest
+            .insert(1, process(1, "init"));
+        for (pid, secs_ago) in [(2, 10), (3, 5)] {
+            stored_data.process_data.exited.push_back(ExitedProcess {
+                process: process(pid, "job"),
+                exit_time: now - Duration::from_secs(secs_ago),
+            });
+        }
+
+        state.toggle_exited();
+        state.set_table_data(&stored_data);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::Pid, ProcColumn::Name, ProcColumn::Exited]
+        );
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(3));
+
+        state.toggle_exited();
+        state.set_table_data(&stored_data);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::Pid, ProcColumn::Name]
+        );
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(1));
+    }
+
+    #[test]
+    fn mark_processes() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let mut stored_data = StoredData::default();
+        for pid in 1..=5 {
+            stored_data.process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    name: if pid == 1 { "init" } else { "worker" }.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        state.set_table_data(&stored_data);
+
+        let marked_rows = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .filter(|row| row.marked)
+                .map(|row| row.pid)
+                .collect::<Vec<_>>()
+        };
+
+        // Marking moves to the next row, and a range starts from the last mark.
+        state.toggle_mark();
+        assert_eq!(state.table.current_index(), 1);
+        state.table.set_position(3);
+        state.mark_range();
+        state.set_table_data(&stored_data);
+        assert_eq!(marked_rows(&state), vec![1, 2, 3, 4]);
+
+        let (_, targets) = state.action_targets(&stored_data.process_data).unwrap();
+        assert!(targets.is_marked);
+        assert_eq!(targets.pids(), vec![1, 2, 3, 4]);
+        assert_eq!(targets.name(), "marked processes");
+
+        // Marks are kept by PID, so they are forgotten once the process is gone.
+        stored_data.process_data.process_harvest.remove(&1);
+        state.set_table_data(&stored_data);
+        assert_eq!(marked_rows(&state), vec![2, 3, 4]);
+        let (_, targets) = state.action_targets(&stored_data.process_data).unwrap();
+        assert_eq!(targets.name(), "worker");
+
+        state.mark_all();
+        state.set_table_data(&stored_data);
+        assert_eq!(marked_rows(&state), vec![2, 3, 4, 5]);
+        state.mark_all();
+        state.set_table_data(&stored_data);
+        assert!(marked_rows(&state).is_empty());
+
+        // Without marks, actions apply to the selected row.
+        state.clear_marks();
+        let (_, targets) = state.action_targets(&stored_data.process_data).unwrap();
+        assert!(!targets.is_marked);
+        assert_eq!(
+            targets.pids(),
+            vec![state.table.current_item().unwrap().pid]
+        );
+    }
+
+    #[test]
+    fn follow_process() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let mut stored_data = StoredData::default();
+        let set_cpu = |stored_data: &mut StoredData, cpus: &[(Pid, f32)]| {
+            stored_data.process_data.process_harvest.clear();
+            for &(pid, cpu_usage_percent) in cpus {
+                stored_data.process_data.process_harvest.insert(
+                    pid,
+                    ProcessHarvest {
+                        pid,
+                        name: format!("p{pid}"),
+                        cpu_usage_percent,
+                        ..Default::default()
+                    },
+                );
+            }
+        };
+        let selected_pid = |state: &ProcWidgetState| state.table.current_item().unwrap().pid;
+
+        // Sorted by CPU usage, so PID 3 starts at the bottom.
+        set_cpu(&mut stored_data, &[(1, 30.0), (2, 20.0), (3, 10.0)]);
+        state.set_table_data(&stored_data);
+        state.table.set_position(2);
+        state.toggle_follow();
+        assert_eq!(state.followed.as_ref().unwrap().pid, 3);
+
+        // The selection moves with the process as it climbs.
+        set_cpu(&mut stored_data, &[(1, 30.0), (2, 20.0), (3, 50.0)]);
+        state.set_table_data(&stored_data);
+        assert_eq!(state.table.current_index(), 0);
+        assert_eq!(selected_pid(&state), 3);
+
+        // Moving the selection follows the newly selected process instead.
+        state.table.set_position(2);
+        state.set_table_data(&stored_data);
+        assert_eq!(state.followed.as_ref().unwrap().pid, 2);
+
+        set_cpu(&mut stored_data, &[(1, 30.0), (3, 50.0)]);
+        state.set_table_data(&stored_data);
+        assert!(state.followed.as_ref().unwrap().exited);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes - p2 (2) has exited ")
+        );
+
+        state.toggle_follow();
+        assert!(state.followed.is_none());
+        assert_eq!(state.table.props.title.as_deref(), Some(" Processes "));
+    }
+
+    #[test]
+    fn group_keys() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::VirtualMem,
+        ];
+        let table_config = ProcTableConfig {
+            group_key: ProcessGroupKey::User,
+            ..Default::default()
+        };
+        let mut state = init_state(table_config, &init_columns);
+        state.toggle_tab();
+
+        let mut stored_data = StoredData::default();
+        for (pid, parent_pid, name, user, exe) in [
+            (1, None, "init", "root", Some("/sbin/init")),
+            (2, Some(1), "bash", "alice", Some("/bin/bash")),
+            (3, Some(2), "vim", "alice", Some("/usr/bin/vim")),
+            (4, Some(1), "bash", "bob", None),
+        ] {
+            stored_data.process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    parent_pid,
+                    name: name.to_string(),
+                    user: Some(user.into()),
+                    exe: exe.map(str::to_string),
+                    cpu_usage_percent: pid as f32,
+                    virtual_mem: 100,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let groups = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.id.to_prefixed_string(),
+                        entry.num_similar,
+                        entry.virtual_mem,
+                    )
+                })
+                .sorted()
+                .collect_vec()
+        };
+
+        state.set_table_data(&stored_data);
+        assert_eq!(
+            groups(&state),
+            vec![
+                ("alice".to_string(), 2, 200),
+                ("bob".to_string(), 1, 100),
+                ("root".to_string(), 1, 100),
+            ]
+        );
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes - by user ")
+        );
+
+        // Sorted by CPU usage, so alice's processes come first. Expanding the
+        // group lists them under it, and they can be acted on one by one.
+        state.toggle_current_tree_branch_entry();
+        state.set_table_data(&stored_data);
+        let rows = state
+            .table
+            .data()
+            .iter()
+            .map(|entry| (entry.id.to_prefixed_string(), state.entry_pids(entry)))
+            .collect_vec();
+        assert_eq!(
+            rows[..3],
+            [
+                ("alice".to_string(), vec![2, 3]),
+                ("├─ vim".to_string(), vec![3]),
+                ("└─ bash".to_string(), vec![2]),
+            ]
+        );
+
+        // The group's processes keep their own names when acted on.
+        let (_, targets) = state.action_targets(&stored_data.process_data).unwrap();
+        assert_eq!(targets.describe(20), "2 grouped processes");
+
+        // Collapsing from a process in the group collapses the group.
+        state.table.set_position(2);
+        state.toggle_current_tree_branch_entry();
+        state.set_table_data(&stored_data);
+        assert_eq!(state.table.data().len(), 3);
+        assert_eq!(state.table.current_index(), 0);
+
+        state.cycle_group_key();
+        state.set_table_data(&stored_data);
+        assert_eq!(
+            groups(&state).into_iter().map(|(id, ..)| id).collect_vec(),
+            vec!["N/A", "bash (2)", "init (1)"]
+        );
+
+        state.cycle_group_key();
+        state.set_table_data(&stored_data);
+        assert_eq!(
+            groups(&state).into_iter().map(|(id, ..)| id).collect_vec(),
+            vec!["/bin/bash", "/sbin/init", "/usr/bin/vim", "N/A"]
+        );
+
+        // Grouping by the command shows it.
+        state.cycle_group_key();
+        state.cycle_group_key();
+        assert!(state.is_using_command());
+        state.cycle_group_key();
+        assert!(!state.is_using_command());
+        assert_eq!(state.group_key(), ProcessGroupKey::User);
+    }
+
+    #[test]
+    fn tree_filter() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let mut stored_data = StoredData::default();
+        let process_data = &mut stored_data.process_data;
+        for (pid, parent_pid) in [
+            (1, None),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(3)),
+            (5, Some(1)),
+        ] {
+            process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    parent_pid,
+                    name: format!("p{pid}"),
+                    ..Default::default()
+                },
+            );
+            if let Some(parent_pid) = parent_pid {
+                process_data
+                    .process_parent_mapping
+                    .entry(parent_pid)
+                    .or_default()
+                    .push(pid);
+            }
+        }
+        process_data.orphan_pids = vec![1];
+
+        let shown_pids = |state: &ProcWidgetState| {
+            let mut pids = state.table.data().iter().map(|p| p.pid).collect_vec();
+            pids.sort_unstable();
+            pids
+        };
+
+        state.set_table_data(&stored_data);
+        state.table.set_position(1);
+        assert_eq!(state.table.current_item().unwrap().pid, 2);
+
+        state.toggle_tree_filter();
+        state.set_table_data(&stored_data);
+        assert_eq!(shown_pids(&state), vec![2, 3, 4]);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes - tree of 2 ")
+        );
+
+        // In tree mode, the tree starts from the filtered process.
+        state.mode = ProcWidgetMode::Tree(TreeCollapsed::new(false));
+        state.set_table_data(&stored_data);
+        assert_eq!(shown_pids(&state), vec![2, 3, 4]);
+        assert_eq!(state.table.data()[0].pid, 2);
+        assert!(state.table.data().iter().all(|p| !p.disabled));
+
+        state.toggle_tree_filter();
+        state.set_table_data(&stored_data);
+        assert_eq!(shown_pids(&state), vec![1, 2, 3, 4, 5]);
+        assert_eq!(state.table.props.title.as_deref(), Some(" Processes "));
+    }
+
+    #[test]
+    fn history_processes() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let mut stored_data = StoredData::default();
+        for (pid, name) in [(1, "init"), (2, "firefox"), (3, "firefox"), (4, "sh")] {
+            stored_data.process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    name: name.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        state.set_table_data(&stored_data);
+        state.table.set_position(2);
+
+        let watch_list = [
+            WatchedProcess::Name("firefox".to_string()),
+            WatchedProcess::Pid(4),
+        ];
+        let pids = |state: &ProcWidgetState| {
+            state
+                .history_processes(&stored_data.process_data, &watch_list)
+                .iter()
+                .map(|process| process.pid)
+                .collect::<Vec<_>>()
+        };
+
+        // The selected process comes first, and is only shown once.
+        assert_eq!(pids(&state), vec![3, 2, 4]);
+
+        // A grouped entry is not one process.
+        state.toggle_tab();
+        state.set_table_data(&stored_data);
+        assert_eq!(pids(&state), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn targets_with_descendants() {
+        let mut process_data = ProcessData::default();
+        for (pid, parent_pid) in [
+            (1, None),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(1)),
+            (5, None),
+        ] {
+            process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    parent_pid,
+                    name: format!("p{pid}"),
+                    ..Default::default()
+                },
+            );
+        }
+        process_data.process_parent_mapping.insert(1, vec![2, 4]);
+        process_data.process_parent_mapping.insert(2, vec![3]);
+
+        let targets = ProcessTargets {
+            processes: vec![("p2".to_string(), 2)],
+            ..Default::default()
+        }
+        .with_descendants(&process_data, false);
+        assert_eq!(targets.pids(), vec![2, 3]);
+        assert_eq!(targets.name(), "p2");
+        assert_eq!(
+            targets.describe(20),
+            "process 'p2' with PID 2 and 1 descendant"
+        );
+
+        // A marked child is only included once, among the marked processes and
+        // so before its parent.
+        let targets = ProcessTargets {
+            processes: vec![("p1".to_string(), 1), ("p3".to_string(), 3)],
+            is_marked: true,
+            tree_roots: None,
+        }
+        .with_descendants(&process_data, false);
+        assert_eq!(targets.pids(), vec![1, 3, 2, 4]);
+        assert_eq!(targets.describe(20), "2 marked processes and 2 descendants");
+        assert_eq!(targets.list().as_deref(), Some("p1 (1), p3 (3)"));
+    }
+
     /// Sanity test to ensure tree collapse logic works, both when
     /// enabled-by-default or disabled-by-default.
     #[test]
@@ -1758,14 +3294,14 @@ mod test {
         let mut normal_proc_harvest: BTreeMap<Pid, ProcessHarvest> = BTreeMap::new();
         normal_proc_harvest.insert(1, process_harvest.clone());
         normal_proc_harvest.insert(2, k_process_harvest.clone());
-        let default_normal_results = state.get_normal_data(&normal_proc_harvest).len();
+        let default_normal_results = state.get_normal_data(&normal_proc_harvest, None).len();
         assert!(default_normal_results == 2);
         state.toggle_k_thread();
-        let filtered_normal_results = state.get_normal_data(&normal_proc_harvest).len();
+        let filtered_normal_results = state.get_normal_data(&normal_proc_harvest, None).len();
         assert!(filtered_normal_results == 1);
         // test that get_normal_data in grouped mode is still filtered
         state.mode = ProcWidgetMode::Grouped;
-        let filtered_grouped_results = state.get_normal_data(&normal_proc_harvest).len();
+        let filtered_grouped_results = state.get_normal_data(&normal_proc_harvest, None).len();
         assert!(filtered_grouped_results == 1);
         // test that get_tree_data is filtered on toggle_k_thread
         let tree_collapsed = TreeCollapsed::new(false);
@@ -1780,12 +3316,12 @@ mod test {
             ..Default::default()
         };
         let default_tree_results = state
-            .get_tree_data(&tree_collapsed, &tree_stored_data)
+            .get_tree_data(&tree_collapsed, &tree_stored_data, None)
             .len();
         assert!(default_tree_results == 2);
         state.toggle_k_thread();
         let filtered_tree_results = state
-            .get_tree_data(&tree_collapsed, &tree_stored_data)
+            .get_tree_data(&tree_collapsed, &tree_stored_data, None)
             .len();
         assert!(filtered_tree_results == 1);
     }
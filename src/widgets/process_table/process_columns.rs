@@ -33,12 +33,17 @@ pub enum ProcColumn {
     #[cfg(unix)]
     Nice,
     Priority,
+    #[cfg(target_os = "linux")]
+    Affinity,
+    #[cfg(target_os = "linux")]
+    LastCpu,
     #[cfg(feature = "gpu")]
     GpuMemValue,
     #[cfg(feature = "gpu")]
     GpuMemPercent,
     #[cfg(feature = "gpu")]
     GpuUtilPercent,
+    Exited,
 }
 
 impl ProcColumn {
@@ -69,6 +74,12 @@ impl ProcColumn {
             #[cfg(unix)]
             ProcColumn::Nice => &["Nice"],
             ProcColumn::Priority => &["Priority"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => &["Affinity"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => &["Last CPU", "LastCPU"],
+            // Only shown for exited processes, so it can't be configured.
+            ProcColumn::Exited => &[],
         }
     }
 }
@@ -94,12 +105,17 @@ impl ColumnHeader for ProcColumn {
             #[cfg(unix)]
             ProcColumn::Nice => "Nice",
             ProcColumn::Priority => "Priority",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => "Affinity",
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => "Last CPU",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue => "GMem",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent => "GMem%",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => "GPU%",
+            ProcColumn::Exited => "Exited",
         }
         .into()
     }
@@ -183,6 +199,20 @@ impl SortsRow for ProcColumn {
             ProcColumn::Priority => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.priority, b.priority));
             }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => {
+                // Sort by how many CPUs are allowed first, as that matters more than which.
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        a.cpu_affinity.as_ref().map(|cpus| (cpus.len(), cpus)),
+                        b.cpu_affinity.as_ref().map(|cpus| (cpus.len(), cpus)),
+                    )
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.last_cpu, b.last_cpu));
+            }
             #[cfg(unix)]
             ProcColumn::Nice => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.nice, b.nice));
@@ -197,6 +227,9 @@ impl SortsRow for ProcColumn {
             ProcColumn::GpuUtilPercent => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.gpu_usage, b.gpu_usage));
             }
+            ProcColumn::Exited => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.exit_time, b.exit_time));
+            }
         }
     }
 }
@@ -223,6 +256,10 @@ impl ProcColumn {
             #[cfg(unix)]
             "nice" => Some(ProcColumn::Nice),
             "priority" => Some(ProcColumn::Priority),
+            #[cfg(target_os = "linux")]
+            "affinity" => Some(ProcColumn::Affinity),
+            #[cfg(target_os = "linux")]
+            "last cpu" | "lastcpu" => Some(ProcColumn::LastCpu),
             #[cfg(feature = "gpu")]
             "gmem" | "gmem%" => Some(ProcColumn::GpuMemPercent),
             #[cfg(feature = "gpu")]
@@ -259,12 +296,17 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::User => ProcWidgetColumn::User,
             ProcColumn::Time => ProcWidgetColumn::Time,
             ProcColumn::Priority => ProcWidgetColumn::Priority,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => ProcWidgetColumn::Affinity,
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => ProcWidgetColumn::LastCpu,
             #[cfg(unix)]
             ProcColumn::Nice => ProcWidgetColumn::Nice,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent | ProcColumn::GpuMemValue => ProcWidgetColumn::GpuMem,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+            ProcColumn::Exited => ProcWidgetColumn::Exited,
         }
     }
 }
@@ -4,15 +4,13 @@ use std::{
     fmt::Display,
     num::NonZeroU16,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use concat_string::concat_string;
 use tui::widgets::Row;
 
 use super::process_columns::ProcColumn;
-#[cfg(target_os = "linux")]
-use crate::collection::processes::ProcessType;
 use crate::{
     canvas::{
         Painter,
@@ -20,8 +18,13 @@ use crate::{
     },
     collection::processes::{Pid, ProcessHarvest},
     dec_bytes_per_second_string,
-    utils::data_units::{GIBI_LIMIT, GIGA_LIMIT, get_binary_bytes, get_decimal_bytes},
+    utils::{
+        data_units::{GIBI_LIMIT, GIGA_LIMIT, get_binary_bytes, get_decimal_bytes},
+        general::format_age,
+    },
 };
+#[cfg(target_os = "linux")]
+use crate::{collection::processes::ProcessType, utils::general::format_cpu_list};
 
 #[derive(Clone, Debug)]
 enum IdType {
@@ -211,7 +214,12 @@ pub struct ProcWidgetData {
     pub process_char: char,
     pub user: Option<Arc<str>>,
     pub num_similar: u64,
+    /// The group this entry is, in grouped mode. Processes listed under an
+    /// expanded group have none.
+    pub group: Option<String>,
     pub disabled: bool,
+    /// Whether the process is marked, so actions apply to it.
+    pub marked: bool,
     pub time: Duration,
     #[cfg(feature = "gpu")]
     pub gpu_mem_usage: MemUsage,
@@ -223,6 +231,14 @@ pub struct ProcWidgetData {
     #[cfg(unix)]
     pub nice: i32,
     pub priority: i32,
+    /// The CPUs the process is allowed to run on.
+    #[cfg(target_os = "linux")]
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// The CPU the process last ran on.
+    #[cfg(target_os = "linux")]
+    pub last_cpu: Option<usize>,
+    /// When the process exited, if it has.
+    pub exit_time: Option<Instant>,
 }
 
 impl ProcWidgetData {
@@ -257,7 +273,9 @@ impl ProcWidgetData {
             process_char: process.process_state.1,
             user: process.user.clone(),
             num_similar: 1,
+            group: None,
             disabled: false,
+            marked: false,
             time: process.time,
             #[cfg(feature = "gpu")]
             gpu_mem_usage: if is_mem_percent {
@@ -272,6 +290,11 @@ impl ProcWidgetData {
             #[cfg(unix)]
             nice: process.nice,
             priority: process.priority,
+            #[cfg(target_os = "linux")]
+            cpu_affinity: process.cpu_affinity.clone(),
+            #[cfg(target_os = "linux")]
+            last_cpu: process.last_cpu,
+            exit_time: None,
         }
     }
 
@@ -280,6 +303,57 @@ impl ProcWidgetData {
         self
     }
 
+    pub fn exit_time(mut self, exit_time: Option<Instant>) -> Self {
+        self.exit_time = exit_time;
+        self
+    }
+
+    /// Make this entry the group with the given name, which is shown in place
+    /// of the process name.
+    pub fn group(mut self, group: String) -> Self {
+        self.id.id_type = IdType::Name(group.clone());
+        self.group = Some(group);
+        self
+    }
+
+    /// Add the CPUs of another process to this one. A group can run on any CPU
+    /// one of its processes can, and only has a last CPU if they all share it.
+    #[cfg(target_os = "linux")]
+    pub fn add_cpus(&mut self, cpu_affinity: &Option<Vec<usize>>, last_cpu: Option<usize>) {
+        if let (Some(cpus), Some(other_cpus)) = (&mut self.cpu_affinity, cpu_affinity) {
+            cpus.extend(other_cpus);
+            cpus.sort_unstable();
+            cpus.dedup();
+        }
+        if self.last_cpu != last_cpu {
+            self.last_cpu = None;
+        }
+    }
+
+    /// The CPUs the process is allowed to run on, as ranges.
+    #[cfg(target_os = "linux")]
+    fn affinity_string(&self) -> String {
+        self.cpu_affinity
+            .as_deref()
+            .map(format_cpu_list)
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+
+    /// The CPU the process last ran on.
+    #[cfg(target_os = "linux")]
+    fn last_cpu_string(&self) -> String {
+        self.last_cpu
+            .map(|cpu| cpu.to_string())
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+
+    /// How long ago the process exited, if it has.
+    fn exit_age(&self) -> String {
+        self.exit_time
+            .map(|exit_time| format_age(exit_time.elapsed()))
+            .unwrap_or_default()
+    }
+
     pub fn prefix(mut self, prefix: Option<String>) -> Self {
         self.id.prefix = prefix;
         self
@@ -295,11 +369,15 @@ impl ProcWidgetData {
                 unreachable!("trying to add together two different memory usage types!")
             }
         };
+        self.virtual_mem += other.virtual_mem;
         self.rps += other.rps;
         self.wps += other.wps;
         self.total_read += other.total_read;
         self.total_write += other.total_write;
         self.time = self.time.max(other.time);
+        self.exit_time = self.exit_time.max(other.exit_time);
+        #[cfg(target_os = "linux")]
+        self.add_cpus(&other.cpu_affinity, other.last_cpu);
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem_usage = match (&self.gpu_mem_usage, &other.gpu_mem_usage) {
@@ -340,8 +418,52 @@ impl ProcWidgetData {
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_usage.to_string(),
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => self.affinity_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => self.last_cpu_string(),
+            ProcColumn::Exited => self.exit_age(),
         }
     }
+
+    /// The numeric value shown in `column`, if it has one.
+    fn column_value(&self, column: &ProcColumn) -> Option<f64> {
+        fn mem_value(mem_usage: &MemUsage) -> f64 {
+            match mem_usage {
+                MemUsage::Percent(percent) => (*percent).into(),
+                MemUsage::Bytes(bytes) => *bytes as f64,
+            }
+        }
+
+        Some(match column {
+            ProcColumn::Priority => self.priority.into(),
+            #[cfg(unix)]
+            ProcColumn::Nice => self.nice.into(),
+            ProcColumn::CpuPercent => self.cpu_usage_percent.into(),
+            ProcColumn::MemValue | ProcColumn::MemPercent => mem_value(&self.mem_usage),
+            ProcColumn::VirtualMem => self.virtual_mem as f64,
+            ProcColumn::Pid => self.pid as f64,
+            ProcColumn::Count => self.num_similar as f64,
+            ProcColumn::ReadPerSecond => self.rps as f64,
+            ProcColumn::WritePerSecond => self.wps as f64,
+            ProcColumn::TotalRead => self.total_read as f64,
+            ProcColumn::TotalWrite => self.total_write as f64,
+            ProcColumn::Time => self.time.as_secs_f64(),
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => mem_value(&self.gpu_mem_usage),
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuUtilPercent => self.gpu_usage.into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity | ProcColumn::LastCpu => return None,
+            ProcColumn::Name
+            | ProcColumn::Command
+            | ProcColumn::State
+            | ProcColumn::User
+            | ProcColumn::Exited => {
+                return None;
+            }
+        })
+    }
 }
 
 impl DataToCell<ProcColumn> for ProcWidgetData {
@@ -384,23 +506,33 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
             }
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Affinity => self.affinity_string().into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => self.last_cpu_string().into(),
+            ProcColumn::Exited => self.exit_age().into(),
         })
     }
 
-    #[cfg(target_os = "linux")]
     #[inline(always)]
     fn style_cell(&self, column: &ProcColumn, painter: &Painter) -> Option<tui::style::Style> {
         match column {
+            #[cfg(target_os = "linux")]
             ProcColumn::Name | ProcColumn::Command if self.process_type.is_thread() => {
                 Some(painter.styles.thread_text_style)
             }
-            _ => None,
+            _ => painter
+                .styles
+                .value_colours
+                .proc_style(column, self.column_value(column)?),
         }
     }
 
     #[inline(always)]
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
-        if self.disabled {
+        if self.marked {
+            row.style(painter.styles.marked_text_style)
+        } else if self.disabled {
             row.style(painter.styles.disabled_text_style)
         } else {
             row
@@ -1,12 +1,16 @@
 use std::{borrow::Cow, cmp::max, num::NonZeroU16};
 
+use itertools::Itertools;
 use serde::Deserialize;
 
 use crate::{
     app::{AppConfigFields, data::TypedTemperature},
-    canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    canvas::{
+        Painter,
+        components::data_table::{
+            ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+            SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        },
     },
     options::config::style::Styles,
     utils::general::sort_partial_fn,
@@ -18,12 +22,11 @@ pub struct TempWidgetData {
     pub temperature: Option<TypedTemperature>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "generate_schema",
     derive(schemars::JsonSchema, strum::VariantArray)
 )]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub enum TempWidgetColumn {
     Sensor,
     Temperature,
@@ -84,6 +87,22 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
         })
     }
 
+    fn style_cell(
+        &self, column: &TempWidgetColumn, painter: &Painter,
+    ) -> Option<tui::style::Style> {
+        match (column, &self.temperature) {
+            (TempWidgetColumn::Temperature, Some(temperature)) => {
+                let value = match temperature {
+                    TypedTemperature::Celsius(value)
+                    | TypedTemperature::Kelvin(value)
+                    | TypedTemperature::Fahrenheit(value) => *value,
+                };
+                painter.styles.value_colours.temp_style(value.into())
+            }
+            _ => None,
+        }
+    }
+
     fn column_widths<C: DataTableColumn<TempWidgetColumn>>(
         data: &[TempWidgetData], _columns: &[C],
     ) -> Vec<u16>
@@ -128,6 +147,17 @@ impl TempWidgetState {
             SortColumn::soft(TempWidgetColumn::Temperature, None).default_descending(),
         ];
 
+        // This is hard-coded, but there's only two columns so it's fine.
+        let sort_indices = config
+            .default_temp_sort_columns
+            .iter()
+            .map(|column| match column {
+                TempWidgetColumn::Sensor => 0,
+                TempWidgetColumn::Temperature => 1,
+            })
+            .unique()
+            .collect::<Vec<_>>();
+
         let props = SortDataTableProps {
             inner: DataTableProps {
                 title: Some(" Temperatures ".into()),
@@ -138,18 +168,21 @@ impl TempWidgetState {
                 show_table_scroll_bar: config.show_table_scroll_bar,
                 show_current_entry_when_unfocused: false,
             },
-            // This is hard-coded, but there's only two columns so it's fine.
-            sort_index: match config.default_temp_sort_column {
-                Some(TempWidgetColumn::Temperature) => 1,
-                Some(TempWidgetColumn::Sensor) | None => 0,
-            },
+            sort_index: sort_indices.first().copied().unwrap_or_default(),
             order: SortOrder::Ascending,
         };
 
         let styling = DataTableStyling::from_palette(palette);
 
+        let mut table = SortDataTable::new_sortable(columns, props, styling);
+
+        // Ties are broken by the other column, in its default order.
+        for &index in sort_indices.iter().skip(1) {
+            table.add_sort_index(index);
+        }
+
         Self {
-            table: SortDataTable::new_sortable(columns, props, styling),
+            table,
             force_update_data: false,
         }
     }
@@ -163,9 +196,7 @@ impl TempWidgetState {
     /// Update the current table data.
     pub fn set_table_data(&mut self, data: &[TempWidgetData]) {
         let mut data = data.to_vec();
-        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            column.sort_by(&mut data, self.table.order());
-        }
+        self.table.sort_rows(&mut data);
         self.table.set_data(data);
         self.force_update_data = false;
     }
@@ -107,6 +107,30 @@ fn test_conflicting_temps() {
         .stderr(predicate::str::contains("cannot be used with"));
 }
 
+#[test]
+fn test_conflicting_monitor() {
+    no_cfg_btm_command()
+        .arg("--pid")
+        .arg("1")
+        .arg("--")
+        .arg("ls")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_monitor_no_running_pid() {
+    no_cfg_btm_command()
+        .arg("--pid")
+        .arg("2147483647")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No process with PID 2147483647 is running",
+        ));
+}
+
 #[test]
 fn test_invalid_default_widget_1() {
     no_cfg_btm_command()
@@ -21,6 +21,14 @@ fn test_empty_layout() {
         .stderr(predicate::str::contains("at least one widget"));
 }
 
+#[test]
+fn test_empty_named_layout() {
+    btm_command(&["-C", "./tests/invalid_configs/empty_named_layout.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[[layout.gpu.row]]"));
+}
+
 #[test]
 fn test_invalid_layout_widget_type() {
     btm_command(&[
@@ -169,3 +177,45 @@ fn test_invalid_proc_default_sort() {
     .failure()
     .stderr(predicate::str::contains("doesn't match"));
 }
+
+#[test]
+fn test_invalid_custom_source() {
+    btm_command(&["-C", "./tests/invalid_configs/invalid_custom_source.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no custom source named"));
+}
+
+#[test]
+fn test_duplicate_keybinding() {
+    btm_command(&["-C", "./tests/invalid_configs/duplicate_keybinding.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("conflicts with"));
+}
+
+#[test]
+fn test_invalid_keybinding() {
+    btm_command(&["-C", "./tests/invalid_configs/invalid_keybinding.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a valid key"));
+}
+
+#[test]
+fn test_missing_theme_file() {
+    btm_command(&["-C", "./tests/invalid_configs/missing_theme_file.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no theme file called 'missing.toml'",
+        ));
+}
+
+#[test]
+fn test_invalid_value_colours() {
+    btm_command(&["-C", "./tests/invalid_configs/invalid_value_colours.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a numeric column"));
+}
@@ -174,6 +174,11 @@ fn test_theme() {
     run_and_kill_cfg("./tests/valid_configs/theme.toml");
 }
 
+#[test]
+fn test_theme_file() {
+    run_and_kill_cfg("./tests/valid_configs/theme_file.toml");
+}
+
 #[test]
 fn test_styling_sanity_check() {
     run_and_kill_cfg("./tests/valid_configs/styling.toml");
@@ -194,6 +199,26 @@ fn test_filtering() {
     run_and_kill_cfg("./tests/valid_configs/filtering.toml");
 }
 
+#[test]
+fn test_custom() {
+    run_and_kill_cfg("./tests/valid_configs/custom.toml");
+}
+
+#[test]
+fn test_cpu_heatmap() {
+    run_and_kill_cfg("./tests/valid_configs/cpu_heatmap.toml");
+}
+
+#[test]
+fn test_keybindings() {
+    run_and_kill_cfg("./tests/valid_configs/keybindings.toml");
+}
+
+#[test]
+fn test_named_layouts() {
+    run_and_kill_cfg("./tests/valid_configs/named_layouts.toml");
+}
+
 #[test]
 fn test_proc_columns() {
     run_and_kill_cfg("./tests/valid_configs/proc_columns.toml");